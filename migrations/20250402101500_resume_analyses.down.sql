@@ -0,0 +1,4 @@
+-- Add down migration script here
+ALTER TABLE "resumes" DROP COLUMN IF EXISTS latest_analysis_id;
+
+DROP TABLE IF EXISTS "resume_analyses";
@@ -0,0 +1,29 @@
+-- Add up migration script here
+CREATE TABLE "resume_analyses" (
+    id UUID NOT NULL PRIMARY KEY DEFAULT (uuid_generate_v4()),
+    resume_id UUID NOT NULL REFERENCES "resumes" (id) ON DELETE CASCADE,
+    analysis_result JSONB NOT NULL,
+    model VARCHAR(100) NOT NULL,
+    model_version VARCHAR(50) NOT NULL,
+    started_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    completed_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    duration_ms BIGINT NOT NULL
+);
+
+CREATE INDEX resume_analyses_resume_id_idx ON resume_analyses(resume_id, completed_at DESC);
+
+ALTER TABLE "resumes"
+    ADD COLUMN latest_analysis_id UUID REFERENCES "resume_analyses" (id) ON DELETE SET NULL;
+
+-- Carry existing results over so every resume with an analysis has a history entry
+WITH backfill AS (
+    INSERT INTO resume_analyses (resume_id, analysis_result, model, model_version, started_at, completed_at, duration_ms)
+    SELECT id, analysis_result, 'legacy', 'unknown', COALESCE(uploaded_at, NOW()), COALESCE(uploaded_at, NOW()), 0
+    FROM resumes
+    WHERE analysis_result IS NOT NULL
+    RETURNING id, resume_id
+)
+UPDATE resumes
+SET latest_analysis_id = backfill.id
+FROM backfill
+WHERE resumes.id = backfill.resume_id;
@@ -0,0 +1,6 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS resumes_pending_analysis_idx;
+
+ALTER TABLE "resumes" DROP COLUMN IF EXISTS analysis_status;
+
+DROP TYPE IF EXISTS "analysis_status";
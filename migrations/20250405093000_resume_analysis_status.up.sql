@@ -0,0 +1,10 @@
+-- Add up migration script here
+CREATE TYPE analysis_status AS ENUM ('pending', 'completed', 'failed');
+
+ALTER TABLE "resumes"
+    ADD COLUMN analysis_status analysis_status NOT NULL DEFAULT 'pending';
+
+UPDATE resumes
+SET analysis_status = CASE WHEN analysis_result IS NULL THEN 'failed'::analysis_status ELSE 'completed'::analysis_status END;
+
+CREATE INDEX resumes_pending_analysis_idx ON resumes(uploaded_at) WHERE analysis_status = 'pending';
@@ -0,0 +1,4 @@
+-- Add down migration script here
+ALTER TABLE "resume_analyses" DROP COLUMN IF EXISTS schema_version;
+
+ALTER TABLE "resumes" DROP COLUMN IF EXISTS analysis_schema_version;
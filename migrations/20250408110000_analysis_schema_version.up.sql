@@ -0,0 +1,7 @@
+-- Add up migration script here
+-- Results stored before versioning are in the legacy (version 1) format
+ALTER TABLE "resumes"
+    ADD COLUMN analysis_schema_version INTEGER NOT NULL DEFAULT 1;
+
+ALTER TABLE "resume_analyses"
+    ADD COLUMN schema_version INTEGER NOT NULL DEFAULT 1;
@@ -0,0 +1,7 @@
+-- Add down migration script here
+ALTER TABLE "resumes"
+    DROP COLUMN IF EXISTS document_format,
+    DROP COLUMN IF EXISTS extracted_text,
+    DROP COLUMN IF EXISTS page_count,
+    DROP COLUMN IF EXISTS word_count,
+    DROP COLUMN IF EXISTS language;
@@ -0,0 +1,7 @@
+-- Add up migration script here
+ALTER TABLE "resumes"
+    ADD COLUMN document_format VARCHAR(10),
+    ADD COLUMN extracted_text TEXT,
+    ADD COLUMN page_count INTEGER,
+    ADD COLUMN word_count INTEGER,
+    ADD COLUMN language VARCHAR(10);
@@ -0,0 +1,8 @@
+-- Add down migration script here
+DROP TRIGGER IF EXISTS resumes_search_vector_trigger ON resumes;
+
+DROP FUNCTION IF EXISTS resumes_search_vector_update();
+
+DROP INDEX IF EXISTS resumes_search_vector_idx;
+
+ALTER TABLE "resumes" DROP COLUMN IF EXISTS search_vector;
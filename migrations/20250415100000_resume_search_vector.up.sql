@@ -0,0 +1,34 @@
+-- Add up migration script here
+ALTER TABLE "resumes" ADD COLUMN search_vector tsvector;
+
+-- Skills carry the most weight, then the summary and job history, then the full document text
+CREATE FUNCTION resumes_search_vector_update() RETURNS trigger AS $$
+BEGIN
+    NEW.search_vector :=
+        setweight(to_tsvector('english', coalesce(
+            (SELECT string_agg(skill #>> '{}', ' ') FROM jsonb_path_query(NEW.analysis_result, '$.skills[*]') AS skill),
+            ''
+        )), 'A') ||
+        setweight(to_tsvector('english', coalesce(NEW.analysis_result ->> 'summary', '') || ' ' || coalesce(
+            (SELECT string_agg(entry #>> '{}', ' ') FROM jsonb_path_query(NEW.analysis_result, '$.experience[*].title') AS entry),
+            ''
+        ) || ' ' || coalesce(
+            (SELECT string_agg(entry #>> '{}', ' ') FROM jsonb_path_query(NEW.analysis_result, '$.experience[*].company') AS entry),
+            ''
+        )), 'B') ||
+        setweight(to_tsvector('english', coalesce(
+            (SELECT string_agg(entry #>> '{}', ' ') FROM jsonb_path_query(NEW.analysis_result, '$.education[*].degree') AS entry),
+            ''
+        )), 'C') ||
+        setweight(to_tsvector('english', coalesce(NEW.extracted_text, '')), 'D');
+    RETURN NEW;
+END
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER resumes_search_vector_trigger
+    BEFORE INSERT OR UPDATE OF analysis_result, extracted_text ON resumes
+    FOR EACH ROW EXECUTE FUNCTION resumes_search_vector_update();
+
+UPDATE resumes SET analysis_result = analysis_result;
+
+CREATE INDEX resumes_search_vector_idx ON resumes USING GIN (search_vector);
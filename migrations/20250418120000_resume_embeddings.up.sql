@@ -0,0 +1,11 @@
+-- Add up migration script here
+CREATE TABLE "resume_embeddings" (
+    resume_id UUID NOT NULL REFERENCES "resumes" (id) ON DELETE CASCADE,
+    model VARCHAR(100) NOT NULL,
+    dimensions INTEGER NOT NULL,
+    embedding REAL[] NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (resume_id, model)
+);
+
+CREATE INDEX resume_embeddings_model_idx ON resume_embeddings(model);
@@ -0,0 +1,3 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS "job_descriptions";
+DROP TYPE IF EXISTS job_seniority;
@@ -0,0 +1,17 @@
+-- Add up migration script here
+CREATE TYPE job_seniority AS ENUM ('intern', 'junior', 'mid', 'senior', 'lead', 'principal');
+
+CREATE TABLE "job_descriptions" (
+    id UUID NOT NULL PRIMARY KEY DEFAULT (uuid_generate_v4()),
+    user_id UUID NOT NULL REFERENCES "users" (id) ON DELETE CASCADE,
+    title VARCHAR(200) NOT NULL,
+    description TEXT NOT NULL,
+    required_skills TEXT[] NOT NULL DEFAULT '{}',
+    preferred_skills TEXT[] NOT NULL DEFAULT '{}',
+    seniority job_seniority,
+    min_years_experience INTEGER,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE INDEX job_descriptions_user_id_idx ON job_descriptions(user_id);
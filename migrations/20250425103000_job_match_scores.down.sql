@@ -0,0 +1,3 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS "job_match_scores";
+ALTER TABLE job_descriptions DROP COLUMN IF EXISTS match_version;
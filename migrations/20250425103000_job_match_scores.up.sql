@@ -0,0 +1,16 @@
+-- Add up migration script here
+-- Bumped whenever a field the matcher reads changes, so cached scores for older versions go stale
+ALTER TABLE job_descriptions ADD COLUMN match_version INTEGER NOT NULL DEFAULT 1;
+
+CREATE TABLE "job_match_scores" (
+    job_id UUID NOT NULL REFERENCES "job_descriptions" (id) ON DELETE CASCADE,
+    resume_id UUID NOT NULL REFERENCES "resumes" (id) ON DELETE CASCADE,
+    job_match_version INTEGER NOT NULL,
+    resume_analysis_id UUID,
+    score DOUBLE PRECISION NOT NULL,
+    report JSONB NOT NULL,
+    computed_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (job_id, resume_id)
+);
+
+CREATE INDEX job_match_scores_ranking_idx ON job_match_scores(job_id, score DESC);
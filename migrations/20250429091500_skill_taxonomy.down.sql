@@ -0,0 +1,3 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS "skill_aliases";
+DROP TABLE IF EXISTS "skills";
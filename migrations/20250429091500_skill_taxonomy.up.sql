@@ -0,0 +1,166 @@
+-- Add up migration script here
+CREATE TABLE "skills" (
+    id UUID NOT NULL PRIMARY KEY DEFAULT (uuid_generate_v4()),
+    name VARCHAR(100) NOT NULL,
+    category VARCHAR(50) NOT NULL,
+    parent_id UUID REFERENCES "skills" (id) ON DELETE SET NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX skills_name_idx ON skills(LOWER(name));
+
+-- Aliases are stored normalized (lowercase, single spaces)
+CREATE TABLE "skill_aliases" (
+    alias VARCHAR(100) NOT NULL PRIMARY KEY,
+    skill_id UUID NOT NULL REFERENCES "skills" (id) ON DELETE CASCADE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE INDEX skill_aliases_skill_id_idx ON skill_aliases(skill_id);
+
+INSERT INTO skills (name, category) VALUES
+    ('JavaScript', 'language'),
+    ('TypeScript', 'language'),
+    ('Python', 'language'),
+    ('Rust', 'language'),
+    ('Go', 'language'),
+    ('Java', 'language'),
+    ('Kotlin', 'language'),
+    ('C', 'language'),
+    ('C++', 'language'),
+    ('C#', 'language'),
+    ('Ruby', 'language'),
+    ('PHP', 'language'),
+    ('Swift', 'language'),
+    ('SQL', 'language'),
+    ('HTML', 'language'),
+    ('CSS', 'language'),
+    ('React', 'framework'),
+    ('Angular', 'framework'),
+    ('Vue.js', 'framework'),
+    ('Node.js', 'framework'),
+    ('Express', 'framework'),
+    ('Next.js', 'framework'),
+    ('Django', 'framework'),
+    ('Flask', 'framework'),
+    ('FastAPI', 'framework'),
+    ('Spring', 'framework'),
+    ('Ruby on Rails', 'framework'),
+    ('.NET', 'framework'),
+    ('Tokio', 'framework'),
+    ('Axum', 'framework'),
+    ('PostgreSQL', 'database'),
+    ('MySQL', 'database'),
+    ('SQLite', 'database'),
+    ('MongoDB', 'database'),
+    ('Redis', 'database'),
+    ('Elasticsearch', 'database'),
+    ('AWS', 'cloud'),
+    ('Google Cloud', 'cloud'),
+    ('Azure', 'cloud'),
+    ('Docker', 'devops'),
+    ('Kubernetes', 'devops'),
+    ('Terraform', 'devops'),
+    ('CI/CD', 'devops'),
+    ('Linux', 'devops'),
+    ('Git', 'tool'),
+    ('GraphQL', 'practice'),
+    ('REST', 'practice'),
+    ('Microservices', 'practice'),
+    ('Machine Learning', 'data'),
+    ('Deep Learning', 'data'),
+    ('Natural Language Processing', 'data'),
+    ('TensorFlow', 'data'),
+    ('PyTorch', 'data'),
+    ('Pandas', 'data');
+
+UPDATE skills AS child
+SET parent_id = parent.id
+FROM (VALUES
+    ('TypeScript', 'JavaScript'),
+    ('React', 'JavaScript'),
+    ('Angular', 'TypeScript'),
+    ('Vue.js', 'JavaScript'),
+    ('Node.js', 'JavaScript'),
+    ('Express', 'Node.js'),
+    ('Next.js', 'React'),
+    ('Django', 'Python'),
+    ('Flask', 'Python'),
+    ('FastAPI', 'Python'),
+    ('Pandas', 'Python'),
+    ('Spring', 'Java'),
+    ('Ruby on Rails', 'Ruby'),
+    ('.NET', 'C#'),
+    ('Tokio', 'Rust'),
+    ('Axum', 'Rust'),
+    ('PostgreSQL', 'SQL'),
+    ('MySQL', 'SQL'),
+    ('SQLite', 'SQL'),
+    ('Deep Learning', 'Machine Learning'),
+    ('Natural Language Processing', 'Machine Learning'),
+    ('TensorFlow', 'Machine Learning'),
+    ('PyTorch', 'Machine Learning')
+) AS relation(child_name, parent_name)
+JOIN skills parent ON parent.name = relation.parent_name
+WHERE child.name = relation.child_name;
+
+INSERT INTO skill_aliases (alias, skill_id)
+SELECT alias.alias, skills.id
+FROM (VALUES
+    ('js', 'JavaScript'),
+    ('javascript es6', 'JavaScript'),
+    ('es6', 'JavaScript'),
+    ('ecmascript', 'JavaScript'),
+    ('vanilla js', 'JavaScript'),
+    ('ts', 'TypeScript'),
+    ('py', 'Python'),
+    ('python3', 'Python'),
+    ('python 3', 'Python'),
+    ('rustlang', 'Rust'),
+    ('golang', 'Go'),
+    ('java se', 'Java'),
+    ('cpp', 'C++'),
+    ('c plus plus', 'C++'),
+    ('csharp', 'C#'),
+    ('c sharp', 'C#'),
+    ('ror', 'Ruby on Rails'),
+    ('rails', 'Ruby on Rails'),
+    ('html5', 'HTML'),
+    ('css3', 'CSS'),
+    ('reactjs', 'React'),
+    ('react.js', 'React'),
+    ('angularjs', 'Angular'),
+    ('vue', 'Vue.js'),
+    ('vuejs', 'Vue.js'),
+    ('node', 'Node.js'),
+    ('nodejs', 'Node.js'),
+    ('express.js', 'Express'),
+    ('expressjs', 'Express'),
+    ('nextjs', 'Next.js'),
+    ('spring boot', 'Spring'),
+    ('dotnet', '.NET'),
+    ('asp.net', '.NET'),
+    ('postgres', 'PostgreSQL'),
+    ('postgresql database', 'PostgreSQL'),
+    ('psql', 'PostgreSQL'),
+    ('mongo', 'MongoDB'),
+    ('elastic search', 'Elasticsearch'),
+    ('amazon web services', 'AWS'),
+    ('gcp', 'Google Cloud'),
+    ('google cloud platform', 'Google Cloud'),
+    ('microsoft azure', 'Azure'),
+    ('k8s', 'Kubernetes'),
+    ('kube', 'Kubernetes'),
+    ('continuous integration', 'CI/CD'),
+    ('ci cd', 'CI/CD'),
+    ('github', 'Git'),
+    ('restful', 'REST'),
+    ('rest api', 'REST'),
+    ('restful apis', 'REST'),
+    ('micro services', 'Microservices'),
+    ('ml', 'Machine Learning'),
+    ('dl', 'Deep Learning'),
+    ('nlp', 'Natural Language Processing'),
+    ('tf', 'TensorFlow')
+) AS alias(alias, skill_name)
+JOIN skills ON skills.name = alias.skill_name;
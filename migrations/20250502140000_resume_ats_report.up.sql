@@ -0,0 +1,2 @@
+-- Add up migration script here
+ALTER TABLE resumes ADD COLUMN ats_report JSONB;
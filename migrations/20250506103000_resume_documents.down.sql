@@ -0,0 +1,5 @@
+-- Add down migration script here
+ALTER TABLE resume_documents DROP CONSTRAINT IF EXISTS resume_documents_current_resume_id_fkey;
+ALTER TABLE resumes DROP COLUMN IF EXISTS version;
+ALTER TABLE resumes DROP COLUMN IF EXISTS document_id;
+DROP TABLE IF EXISTS "resume_documents";
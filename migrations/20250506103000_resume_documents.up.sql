@@ -0,0 +1,32 @@
+-- Add up migration script here
+CREATE TABLE "resume_documents" (
+    id UUID NOT NULL PRIMARY KEY DEFAULT (uuid_generate_v4()),
+    user_id UUID NOT NULL REFERENCES "users" (id) ON DELETE CASCADE,
+    title VARCHAR(255) NOT NULL,
+    current_resume_id UUID,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+ALTER TABLE resumes
+    ADD COLUMN document_id UUID,
+    ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
+
+-- Every existing upload becomes the only version of its own document
+UPDATE resumes SET document_id = uuid_generate_v4();
+
+INSERT INTO resume_documents (id, user_id, title, current_resume_id, created_at, updated_at)
+SELECT document_id, user_id, regexp_replace(file_path, '^.*/', ''), id, uploaded_at, uploaded_at
+FROM resumes;
+
+ALTER TABLE resumes
+    ALTER COLUMN document_id SET NOT NULL,
+    ADD CONSTRAINT resumes_document_id_fkey
+        FOREIGN KEY (document_id) REFERENCES resume_documents (id) ON DELETE CASCADE;
+
+ALTER TABLE resume_documents
+    ADD CONSTRAINT resume_documents_current_resume_id_fkey
+        FOREIGN KEY (current_resume_id) REFERENCES resumes (id) ON DELETE SET NULL;
+
+CREATE UNIQUE INDEX resumes_document_version_idx ON resumes(document_id, version);
+CREATE INDEX resume_documents_user_id_idx ON resume_documents(user_id, updated_at DESC);
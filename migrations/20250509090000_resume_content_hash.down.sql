@@ -0,0 +1,4 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS resumes_content_hash_idx;
+ALTER TABLE resumes DROP COLUMN IF EXISTS content_hash;
+ALTER TABLE resumes DROP COLUMN IF EXISTS file_name;
@@ -0,0 +1,8 @@
+-- Add up migration script here
+ALTER TABLE resumes
+    ADD COLUMN file_name TEXT,
+    ADD COLUMN content_hash VARCHAR(64);
+
+UPDATE resumes SET file_name = regexp_replace(file_path, '^.*/', '');
+
+CREATE INDEX resumes_content_hash_idx ON resumes(content_hash);
@@ -0,0 +1,3 @@
+-- Add down migration script here
+ALTER TABLE resumes DROP COLUMN IF EXISTS batch_id;
+DROP TABLE IF EXISTS "resume_batches";
@@ -0,0 +1,15 @@
+-- Add up migration script here
+CREATE TABLE "resume_batches" (
+    id UUID NOT NULL PRIMARY KEY DEFAULT (uuid_generate_v4()),
+    user_id UUID NOT NULL REFERENCES "users" (id) ON DELETE CASCADE,
+    file_count INTEGER NOT NULL DEFAULT 0,
+    failed_count INTEGER NOT NULL DEFAULT 0,
+    results JSONB NOT NULL DEFAULT '[]',
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE INDEX resume_batches_user_id_idx ON resume_batches(user_id);
+
+ALTER TABLE resumes ADD COLUMN batch_id UUID REFERENCES resume_batches (id) ON DELETE SET NULL;
+
+CREATE INDEX resumes_batch_id_idx ON resumes(batch_id) WHERE batch_id IS NOT NULL;
@@ -0,0 +1,10 @@
+-- Add down migration script here
+UPDATE users SET role = 'user' WHERE role = 'reviewer';
+
+ALTER TYPE user_role RENAME TO user_role_old;
+CREATE TYPE user_role AS ENUM ('admin', 'user');
+ALTER TABLE users
+    ALTER COLUMN role DROP DEFAULT,
+    ALTER COLUMN role TYPE user_role USING role::text::user_role,
+    ALTER COLUMN role SET DEFAULT 'user';
+DROP TYPE user_role_old;
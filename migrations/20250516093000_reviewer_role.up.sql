@@ -0,0 +1,2 @@
+-- Add up migration script here
+ALTER TYPE user_role ADD VALUE IF NOT EXISTS 'reviewer';
@@ -0,0 +1,4 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS "resume_notes";
+DROP TABLE IF EXISTS "resume_tags";
+DROP TABLE IF EXISTS "tags";
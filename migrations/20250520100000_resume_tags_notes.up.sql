@@ -0,0 +1,31 @@
+-- Add up migration script here
+CREATE TABLE "tags" (
+    id UUID NOT NULL PRIMARY KEY DEFAULT (uuid_generate_v4()),
+    user_id UUID NOT NULL REFERENCES "users" (id) ON DELETE CASCADE,
+    name VARCHAR(50) NOT NULL,
+    colour VARCHAR(7) NOT NULL DEFAULT '#6b7280',
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX tags_user_id_name_idx ON tags(user_id, lower(name));
+
+CREATE TABLE "resume_tags" (
+    resume_id UUID NOT NULL REFERENCES "resumes" (id) ON DELETE CASCADE,
+    tag_id UUID NOT NULL REFERENCES "tags" (id) ON DELETE CASCADE,
+    tagged_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    PRIMARY KEY (resume_id, tag_id)
+);
+
+CREATE INDEX resume_tags_tag_id_idx ON resume_tags(tag_id);
+
+CREATE TABLE "resume_notes" (
+    id UUID NOT NULL PRIMARY KEY DEFAULT (uuid_generate_v4()),
+    resume_id UUID NOT NULL REFERENCES "resumes" (id) ON DELETE CASCADE,
+    author_id UUID NOT NULL REFERENCES "users" (id) ON DELETE CASCADE,
+    body TEXT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE INDEX resume_notes_resume_id_idx ON resume_notes(resume_id, created_at);
@@ -0,0 +1,5 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS "resume_stage_history";
+ALTER TABLE resumes DROP COLUMN IF EXISTS stage_changed_at;
+ALTER TABLE resumes DROP COLUMN IF EXISTS stage_id;
+DROP TABLE IF EXISTS "pipeline_stages";
@@ -0,0 +1,43 @@
+-- Add up migration script here
+CREATE TABLE "pipeline_stages" (
+    id UUID NOT NULL PRIMARY KEY DEFAULT (uuid_generate_v4()),
+    name VARCHAR(50) NOT NULL,
+    position INTEGER NOT NULL,
+    terminal BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX pipeline_stages_name_idx ON pipeline_stages(lower(name));
+
+INSERT INTO pipeline_stages (name, position, terminal) VALUES
+    ('New', 0, FALSE),
+    ('Screening', 1, FALSE),
+    ('Interview', 2, FALSE),
+    ('Offer', 3, FALSE),
+    ('Hired', 4, TRUE),
+    ('Rejected', 5, TRUE);
+
+ALTER TABLE resumes ADD COLUMN stage_id UUID REFERENCES pipeline_stages (id);
+ALTER TABLE resumes ADD COLUMN stage_changed_at TIMESTAMP WITH TIME ZONE DEFAULT NOW();
+
+UPDATE resumes SET stage_id = (SELECT id FROM pipeline_stages WHERE name = 'New'), stage_changed_at = uploaded_at;
+
+ALTER TABLE resumes ALTER COLUMN stage_id SET NOT NULL;
+
+CREATE INDEX resumes_stage_id_idx ON resumes(stage_id);
+
+-- Stage names are copied so the history still reads after a stage is renamed or removed
+CREATE TABLE "resume_stage_history" (
+    id UUID NOT NULL PRIMARY KEY DEFAULT (uuid_generate_v4()),
+    resume_id UUID NOT NULL REFERENCES "resumes" (id) ON DELETE CASCADE,
+    from_stage_id UUID REFERENCES "pipeline_stages" (id) ON DELETE SET NULL,
+    from_stage VARCHAR(50),
+    to_stage_id UUID REFERENCES "pipeline_stages" (id) ON DELETE SET NULL,
+    to_stage VARCHAR(50) NOT NULL,
+    changed_by UUID REFERENCES "users" (id) ON DELETE SET NULL,
+    note TEXT,
+    changed_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE INDEX resume_stage_history_resume_id_idx ON resume_stage_history(resume_id, changed_at);
@@ -0,0 +1,31 @@
+-- Add up migration script here
+-- Only a SHA-256 of the token is kept, the token itself is shown once when the link is created
+CREATE TABLE "share_links" (
+    id UUID NOT NULL PRIMARY KEY DEFAULT (uuid_generate_v4()),
+    resume_id UUID NOT NULL REFERENCES "resumes" (id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES "users" (id) ON DELETE CASCADE,
+    token_hash VARCHAR(64) NOT NULL UNIQUE,
+    token_prefix VARCHAR(8) NOT NULL,
+    password_hash VARCHAR(255),
+    redacted BOOLEAN NOT NULL DEFAULT FALSE,
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    max_views INTEGER,
+    view_count INTEGER NOT NULL DEFAULT 0,
+    last_viewed_at TIMESTAMP WITH TIME ZONE,
+    revoked_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE INDEX share_links_resume_id_idx ON share_links(resume_id);
+
+CREATE TABLE "share_link_access" (
+    id UUID NOT NULL PRIMARY KEY DEFAULT (uuid_generate_v4()),
+    share_link_id UUID NOT NULL REFERENCES "share_links" (id) ON DELETE CASCADE,
+    granted BOOLEAN NOT NULL,
+    reason VARCHAR(50),
+    ip VARCHAR(64),
+    user_agent VARCHAR(512),
+    accessed_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE INDEX share_link_access_link_idx ON share_link_access(share_link_id, accessed_at);
@@ -0,0 +1,3 @@
+-- Add down migration script here
+DELETE FROM resumes WHERE deleted_at IS NOT NULL;
+ALTER TABLE resumes DROP COLUMN IF EXISTS deleted_at;
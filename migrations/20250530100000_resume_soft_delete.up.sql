@@ -0,0 +1,4 @@
+-- Add up migration script here
+ALTER TABLE resumes ADD COLUMN deleted_at TIMESTAMP WITH TIME ZONE;
+
+CREATE INDEX resumes_deleted_at_idx ON resumes(deleted_at) WHERE deleted_at IS NOT NULL;
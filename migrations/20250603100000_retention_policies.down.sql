@@ -0,0 +1,8 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS resumes_uploaded_at_idx;
+-- Anonymized resumes have no file to point at
+DELETE FROM resumes WHERE file_path IS NULL;
+ALTER TABLE resumes ALTER COLUMN file_path SET NOT NULL;
+ALTER TABLE resumes DROP COLUMN IF EXISTS anonymized_at;
+DROP TABLE IF EXISTS "retention_policies";
+DROP TYPE IF EXISTS retention_action;
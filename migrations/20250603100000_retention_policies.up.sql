@@ -0,0 +1,25 @@
+-- Add up migration script here
+CREATE TYPE retention_action AS ENUM ('delete', 'anonymize');
+
+-- The row without a user is the global default, a user's own row overrides it.
+-- No retention_months means resumes are kept indefinitely.
+CREATE TABLE "retention_policies" (
+    id UUID NOT NULL PRIMARY KEY DEFAULT (uuid_generate_v4()),
+    user_id UUID UNIQUE REFERENCES "users" (id) ON DELETE CASCADE,
+    retention_months INTEGER CHECK (retention_months > 0),
+    action retention_action NOT NULL DEFAULT 'delete',
+    updated_by UUID REFERENCES "users" (id) ON DELETE SET NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX retention_policies_global_idx ON retention_policies ((user_id IS NULL)) WHERE user_id IS NULL;
+
+INSERT INTO retention_policies (user_id, retention_months, action) VALUES (NULL, NULL, 'delete');
+
+ALTER TABLE resumes ADD COLUMN anonymized_at TIMESTAMP WITH TIME ZONE;
+
+-- Anonymizing a resume removes its stored file
+ALTER TABLE resumes ALTER COLUMN file_path DROP NOT NULL;
+
+CREATE INDEX resumes_uploaded_at_idx ON resumes(uploaded_at);
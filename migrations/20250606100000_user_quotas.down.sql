@@ -0,0 +1,4 @@
+-- Add down migration script here
+ALTER TABLE resumes DROP COLUMN IF EXISTS file_size;
+DROP TABLE IF EXISTS "analysis_usage";
+DROP TABLE IF EXISTS "quotas";
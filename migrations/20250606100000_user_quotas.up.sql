@@ -0,0 +1,30 @@
+-- Add up migration script here
+-- A quota is set either for a role or for one user, a user's own quota replaces their role's.
+-- A missing limit is unlimited, and so is a role without a quota.
+CREATE TABLE "quotas" (
+    id UUID NOT NULL PRIMARY KEY DEFAULT (uuid_generate_v4()),
+    user_id UUID UNIQUE REFERENCES "users" (id) ON DELETE CASCADE,
+    role user_role UNIQUE,
+    max_resumes INTEGER CHECK (max_resumes >= 0),
+    max_storage_bytes BIGINT CHECK (max_storage_bytes >= 0),
+    max_analyses_per_day INTEGER CHECK (max_analyses_per_day >= 0),
+    updated_by UUID REFERENCES "users" (id) ON DELETE SET NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    CHECK ((user_id IS NULL) <> (role IS NULL))
+);
+
+INSERT INTO quotas (role, max_resumes, max_storage_bytes, max_analyses_per_day) VALUES
+    ('user', 500, 1073741824, 200),
+    ('reviewer', 500, 1073741824, 200);
+
+-- Analyses started per user and UTC day
+CREATE TABLE "analysis_usage" (
+    user_id UUID NOT NULL REFERENCES "users" (id) ON DELETE CASCADE,
+    day DATE NOT NULL,
+    analyses INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (user_id, day)
+);
+
+-- Unknown for uploads from before sizes were recorded, filled in from the files at startup
+ALTER TABLE resumes ADD COLUMN file_size BIGINT;
@@ -5,15 +5,23 @@ use tower_http::trace::TraceLayer;
 
 use crate::{
     AppState,
-    routes::{auth::auth_routes, users::user_routes, resumes::resume_routes},
+    routes::{auth::auth_routes, health::health_routes, users::user_routes, resumes::resume_routes, jobs::job_routes, skills::skill_routes, tags::tag_routes, stages::stage_routes, share::share_routes, retention::retention_routes, quotas::quota_routes},
     services::middleware::auth,
 };
 
 pub fn create_api(app_state: Arc<AppState>) -> Router {
     let api_route = Router::new()
         .nest("/auth", auth_routes())
+        .nest("/health", health_routes())
         .nest("/users", user_routes().layer(from_fn(auth)))
         .nest("/resumes", resume_routes().layer(from_fn(auth)))
+        .nest("/jobs", job_routes().layer(from_fn(auth)))
+        .nest("/skills", skill_routes())
+        .nest("/tags", tag_routes().layer(from_fn(auth)))
+        .nest("/stages", stage_routes().layer(from_fn(auth)))
+        .nest("/share", share_routes())
+        .nest("/retention", retention_routes().layer(from_fn(auth)))
+        .nest("/quotas", quota_routes().layer(from_fn(auth)))
         .layer(TraceLayer::new_for_http())
         .layer(Extension(app_state));
 
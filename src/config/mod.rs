@@ -6,6 +6,32 @@ pub struct Config {
     pub jwt_secret: String,
     pub jwt_expiration: i64,
     pub port: u16,
+    pub nlp_model: String,
+    pub nlp_model_version: String,
+    pub nlp_provider: String,
+    pub nlp_service_url: String,
+    pub nlp_timeout_secs: u64,
+    pub nlp_auth_header: Option<String>,
+    pub nlp_max_retries: u32,
+    pub nlp_retry_backoff_ms: u64,
+    pub nlp_cb_failure_threshold: u32,
+    pub nlp_cb_open_secs: u64,
+    pub nlp_cb_half_open_max_calls: u32,
+    pub nlp_pending_retry_secs: u64,
+    pub embedding_provider: String,
+    pub embedding_service_url: String,
+    pub embedding_model: String,
+    pub embedding_dimensions: usize,
+    pub bulk_max_upload_bytes: usize,
+    pub bulk_max_files: usize,
+    pub bulk_max_file_bytes: u64,
+    pub bulk_max_extracted_bytes: u64,
+    pub blind_review_roles: Vec<String>,
+    pub share_link_default_hours: i64,
+    pub share_link_max_hours: i64,
+    pub trash_retention_days: i64,
+    pub trash_purge_interval_secs: u64,
+    pub retention_interval_secs: u64,
 }
 
 impl Config {
@@ -13,12 +39,74 @@ impl Config {
         let database_url = var("DATABASE_URL").expect("DATABASE_URL must be set");
         let jwt_secret = var("JWT_SECRET").expect("JWT_SECRET must be set");
         let jwt_maxage = var("JWT_MAXAGE").expect("JWT_MAXAGE must be set");
+        let nlp_model = var("NLP_MODEL").unwrap_or("resume-analyzer".to_string());
+        let nlp_model_version = var("NLP_MODEL_VERSION").unwrap_or("1.0".to_string());
+        let nlp_provider = var("NLP_PROVIDER").unwrap_or("http".to_string());
+        // A typo must not quietly send real traffic to the HTTP service
+        if !["http", "stub"].contains(&nlp_provider.as_str()) {
+            panic!("NLP_PROVIDER must be http or stub, got {}", nlp_provider);
+        }
+        let nlp_service_url = var("NLP_SERVICE_URL")
+            .unwrap_or("http://host.docker.internal:8000/analyze_resume/".to_string());
+        let nlp_timeout_secs = var("NLP_TIMEOUT_SECS").unwrap_or("30".to_string());
+        let nlp_auth_header = var("NLP_AUTH_HEADER").ok();
+        let nlp_max_retries = var("NLP_MAX_RETRIES").unwrap_or("2".to_string());
+        let nlp_retry_backoff_ms = var("NLP_RETRY_BACKOFF_MS").unwrap_or("500".to_string());
+        let nlp_cb_failure_threshold = var("NLP_CB_FAILURE_THRESHOLD").unwrap_or("5".to_string());
+        let nlp_cb_open_secs = var("NLP_CB_OPEN_SECS").unwrap_or("30".to_string());
+        let nlp_cb_half_open_max_calls = var("NLP_CB_HALF_OPEN_MAX_CALLS").unwrap_or("1".to_string());
+        let nlp_pending_retry_secs = var("NLP_PENDING_RETRY_SECS").unwrap_or("60".to_string());
+        let embedding_provider = var("EMBEDDING_PROVIDER").unwrap_or("local".to_string());
+        let embedding_service_url = var("EMBEDDING_SERVICE_URL")
+            .unwrap_or("http://host.docker.internal:8000/embed/".to_string());
+        let embedding_model = var("EMBEDDING_MODEL").unwrap_or("all-MiniLM-L6-v2".to_string());
+        let embedding_dimensions = var("EMBEDDING_DIMENSIONS").unwrap_or("384".to_string());
+        let bulk_max_upload_bytes = var("BULK_MAX_UPLOAD_BYTES").unwrap_or("104857600".to_string());
+        let bulk_max_files = var("BULK_MAX_FILES").unwrap_or("500".to_string());
+        let bulk_max_file_bytes = var("BULK_MAX_FILE_BYTES").unwrap_or("10485760".to_string());
+        let blind_review_roles = var("BLIND_REVIEW_ROLES").unwrap_or("reviewer".to_string());
+        let bulk_max_extracted_bytes = var("BULK_MAX_EXTRACTED_BYTES").unwrap_or("209715200".to_string());
+        let share_link_default_hours = var("SHARE_LINK_DEFAULT_HOURS").unwrap_or("72".to_string());
+        let share_link_max_hours = var("SHARE_LINK_MAX_HOURS").unwrap_or("720".to_string());
+        let trash_retention_days = var("TRASH_RETENTION_DAYS").unwrap_or("30".to_string());
+        let trash_purge_interval_secs = var("TRASH_PURGE_INTERVAL_SECS").unwrap_or("3600".to_string());
+        let retention_interval_secs = var("RETENTION_INTERVAL_SECS").unwrap_or("86400".to_string());
 
         Config {
             database_url,
             jwt_secret,
             jwt_expiration: jwt_maxage.parse::<i64>().unwrap(),
             port: 8080,
+            nlp_model,
+            nlp_model_version,
+            nlp_provider,
+            nlp_service_url,
+            nlp_timeout_secs: nlp_timeout_secs.parse::<u64>().unwrap(),
+            nlp_auth_header,
+            nlp_max_retries: nlp_max_retries.parse::<u32>().unwrap(),
+            nlp_retry_backoff_ms: nlp_retry_backoff_ms.parse::<u64>().unwrap(),
+            nlp_cb_failure_threshold: nlp_cb_failure_threshold.parse::<u32>().unwrap(),
+            nlp_cb_open_secs: nlp_cb_open_secs.parse::<u64>().unwrap(),
+            nlp_cb_half_open_max_calls: nlp_cb_half_open_max_calls.parse::<u32>().unwrap(),
+            nlp_pending_retry_secs: nlp_pending_retry_secs.parse::<u64>().unwrap(),
+            embedding_provider,
+            embedding_service_url,
+            embedding_model,
+            embedding_dimensions: embedding_dimensions.parse::<usize>().unwrap(),
+            bulk_max_upload_bytes: bulk_max_upload_bytes.parse::<usize>().unwrap(),
+            bulk_max_files: bulk_max_files.parse::<usize>().unwrap(),
+            bulk_max_file_bytes: bulk_max_file_bytes.parse::<u64>().unwrap(),
+            bulk_max_extracted_bytes: bulk_max_extracted_bytes.parse::<u64>().unwrap(),
+            blind_review_roles: blind_review_roles
+                .split(',')
+                .map(|role| role.trim().to_lowercase())
+                .filter(|role| !role.is_empty())
+                .collect(),
+            share_link_default_hours: share_link_default_hours.parse::<i64>().unwrap(),
+            share_link_max_hours: share_link_max_hours.parse::<i64>().unwrap(),
+            trash_retention_days: trash_retention_days.parse::<i64>().unwrap(),
+            trash_purge_interval_secs: trash_purge_interval_secs.parse::<u64>().unwrap(),
+            retention_interval_secs: retention_interval_secs.parse::<u64>().unwrap(),
         }
     }
 }
\ No newline at end of file
@@ -1,12 +1,12 @@
-use std::{env, sync::Arc};
+use std::{env, net::SocketAddr, sync::Arc, time::Duration};
 
 use api::create_api;
 use axum::{http::{header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE}, HeaderValue, Method}, routing::get, Json};
 use config::Config;
 use dotenvy::dotenv;
-use services::database::DBClient;
+use services::{analysis::process_pending_analyses, circuit_breaker::CircuitBreaker, database::DBClient, embeddings::{build_embedder, Embedder}, nlp::{build_analyzer, Analyzer}, quota::backfill_file_sizes, retention::apply_retention, scheduler::Scheduler, taxonomy::TaxonomyStore, trash::purge_expired_trash};
 use sqlx::postgres::PgPoolOptions;
-use tokio::net::TcpListener;
+use tokio::{net::TcpListener, sync::Notify};
 use tower_http::cors::CorsLayer;
 use tracing_subscriber::filter::LevelFilter;
 
@@ -22,6 +22,11 @@ pub struct AppState {
     pub env: Config,
     pub db_client: DBClient,
     pub http_client: reqwest::Client,
+    pub analyzer: Arc<dyn Analyzer>,
+    pub circuit_breaker: Arc<CircuitBreaker>,
+    pub embedder: Arc<dyn Embedder>,
+    pub taxonomy: Arc<TaxonomyStore>,
+    pub pending_analyses: Arc<Notify>,
 }
 
 pub async fn run()
@@ -61,12 +66,35 @@ pub async fn run()
 
     let db_client = DBClient::new(pool);
     let http_client = reqwest::Client::new();
+    let circuit_breaker = Arc::new(CircuitBreaker::new(
+        config.nlp_cb_failure_threshold,
+        Duration::from_secs(config.nlp_cb_open_secs),
+        config.nlp_cb_half_open_max_calls,
+    ));
+    let analyzer = build_analyzer(&config, http_client.clone(), circuit_breaker.clone());
+    let embedder = build_embedder(&config, http_client.clone());
+    let taxonomy = Arc::new(TaxonomyStore::default());
+    if let Err(e) = taxonomy.reload(&db_client).await {
+        tracing::warn!("Could not load skill taxonomy, skills won't be normalized: {}", e);
+    }
     let app_state = AppState {
         env: config.clone(),
         db_client,
         http_client,
+        analyzer,
+        circuit_breaker,
+        embedder,
+        taxonomy,
+        pending_analyses: Arc::new(Notify::new()),
     };
 
+    tokio::spawn(process_pending_analyses(Arc::new(app_state.clone())));
+    tokio::spawn(backfill_file_sizes(Arc::new(app_state.clone())));
+    Scheduler::new()
+        .every("trash purge", Duration::from_secs(config.trash_purge_interval_secs), purge_expired_trash)
+        .every("retention", Duration::from_secs(config.retention_interval_secs), apply_retention)
+        .start(Arc::new(app_state.clone()));
+
     let app = create_api(Arc::new(app_state.clone()))
         .route("/", get(|| async {Json("Hello, World!")}))
         .route("/health", get(|| async {Json("OK")}))
@@ -79,5 +107,8 @@ pub async fn run()
         .unwrap();
 
     println!("Listening on: {}", listener.local_addr().unwrap());
-    axum::serve(listener, app).await.unwrap();
+    // Peer addresses feed the share link access log
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+        .await
+        .unwrap();
 }
\ No newline at end of file
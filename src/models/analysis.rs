@@ -0,0 +1,34 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use super::analysis_result::{self, AnalysisResult};
+
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow, sqlx::Type)]
+pub struct ResumeAnalysis {
+    pub id: Uuid,
+    pub resume_id: Uuid,
+    #[serde(rename = "analysisResult")]
+    pub analysis_result: Value,
+    #[serde(rename = "schemaVersion")]
+    pub schema_version: i32,
+    pub model: String,
+    #[serde(rename = "modelVersion")]
+    pub model_version: String,
+    #[serde(rename = "startedAt")]
+    pub started_at: DateTime<Utc>,
+    #[serde(rename = "completedAt")]
+    pub completed_at: DateTime<Utc>,
+    #[serde(rename = "durationMs")]
+    pub duration_ms: i64,
+}
+
+impl ResumeAnalysis {
+    /// The stored result upgraded to the current schema, if it can be read.
+    pub fn analysis(&self) -> Option<AnalysisResult> {
+        analysis_result::upgrade(self.schema_version, self.analysis_result.clone())
+            .map_err(|e| tracing::warn!("Could not upgrade analysis {}: {}", self.id, e))
+            .ok()
+    }
+}
@@ -0,0 +1,375 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Version written by this build; bump it together with a new migrator in `upgrade`.
+pub const CURRENT_SCHEMA_VERSION: i32 = 2;
+
+/// Results stored before the schema was versioned are treated as version 1.
+pub const LEGACY_SCHEMA_VERSION: i32 = 1;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct ContactInfo {
+    pub name: Option<String>,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+    pub location: Option<String>,
+    #[serde(default)]
+    pub links: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct ExperienceEntry {
+    pub title: Option<String>,
+    pub company: Option<String>,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct EducationEntry {
+    pub institution: Option<String>,
+    pub degree: Option<String>,
+    pub field: Option<String>,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+}
+
+/// Scores are on a 0-100 scale.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct Scores {
+    pub overall: Option<f64>,
+    pub skills: Option<f64>,
+    pub experience: Option<f64>,
+    pub education: Option<f64>,
+    pub formatting: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AnalysisResult {
+    pub schema_version: i32,
+    #[serde(default)]
+    pub contact: ContactInfo,
+    pub summary: Option<String>,
+    #[serde(default)]
+    pub skills: Vec<String>,
+    #[serde(default)]
+    pub experience: Vec<ExperienceEntry>,
+    #[serde(default)]
+    pub education: Vec<EducationEntry>,
+    #[serde(default)]
+    pub scores: Scores,
+}
+
+impl Default for AnalysisResult {
+    fn default() -> Self {
+        AnalysisResult {
+            schema_version: CURRENT_SCHEMA_VERSION,
+            contact: ContactInfo::default(),
+            summary: None,
+            skills: Vec::new(),
+            experience: Vec::new(),
+            education: Vec::new(),
+            scores: Scores::default(),
+        }
+    }
+}
+
+impl AnalysisResult {
+    /// Parses a response from the NLP service, upgrading older formats and validating the result.
+    pub fn from_response(value: Value) -> Result<Self, String> {
+        let version = value
+            .get("schema_version")
+            .and_then(Value::as_i64)
+            .map(|v| v as i32)
+            .unwrap_or(LEGACY_SCHEMA_VERSION);
+
+        let result = upgrade(version, value)?;
+        result.validate()?;
+        Ok(result)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.schema_version != CURRENT_SCHEMA_VERSION {
+            return Err(format!(
+                "expected schema version {}, got {}",
+                CURRENT_SCHEMA_VERSION, self.schema_version
+            ));
+        }
+
+        if self.skills.iter().any(|skill| skill.trim().is_empty()) {
+            return Err("skills must not contain empty entries".to_string());
+        }
+
+        let scores = [
+            ("overall", self.scores.overall),
+            ("skills", self.scores.skills),
+            ("experience", self.scores.experience),
+            ("education", self.scores.education),
+            ("formatting", self.scores.formatting),
+        ];
+        let out_of_range = scores
+            .into_iter()
+            .filter_map(|(name, score)| score.map(|score| (name, score)))
+            .find(|(_, score)| !(0.0..=100.0).contains(score));
+        if let Some((name, score)) = out_of_range {
+            return Err(format!("{} score {} is outside 0-100", name, score));
+        }
+
+        Ok(())
+    }
+
+    pub fn to_value(&self) -> Value {
+        serde_json::to_value(self).unwrap_or(Value::Null)
+    }
+}
+
+/// Brings a stored or received result at `version` up to `CURRENT_SCHEMA_VERSION`.
+pub fn upgrade(version: i32, value: Value) -> Result<AnalysisResult, String> {
+    match version {
+        LEGACY_SCHEMA_VERSION => upgrade_v1(value),
+        CURRENT_SCHEMA_VERSION => serde_json::from_value(value).map_err(|e| e.to_string()),
+        _ => Err(format!("unsupported analysis schema version {}", version)),
+    }
+}
+
+fn string_field(object: &serde_json::Map<String, Value>, keys: &[&str]) -> Option<String> {
+    keys.iter()
+        .filter_map(|key| object.get(*key))
+        .find_map(|value| match value {
+            Value::String(s) if !s.trim().is_empty() => Some(s.trim().to_string()),
+            Value::Number(n) => Some(n.to_string()),
+            _ => None,
+        })
+}
+
+fn number_field(object: &serde_json::Map<String, Value>, keys: &[&str]) -> Option<f64> {
+    keys.iter()
+        .filter_map(|key| object.get(*key))
+        .find_map(Value::as_f64)
+}
+
+/// Version 1 was the flat, untyped payload the NLP service returned before versioning:
+/// contact fields at the top level, skills as strings or `{ "name": .. }` objects,
+/// and experience/education either as free text or loosely keyed objects.
+fn upgrade_v1(value: Value) -> Result<AnalysisResult, String> {
+    let object = value
+        .as_object()
+        .ok_or_else(|| "analysis result must be a JSON object".to_string())?;
+
+    let contact_source = object
+        .get("contact")
+        .and_then(Value::as_object)
+        .unwrap_or(object);
+
+    let links = match contact_source.get("links").or_else(|| object.get("urls")) {
+        Some(Value::Array(links)) => links
+            .iter()
+            .filter_map(Value::as_str)
+            .map(|link| link.to_string())
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    let contact = ContactInfo {
+        name: string_field(contact_source, &["name", "full_name"]),
+        email: string_field(contact_source, &["email"]),
+        phone: string_field(contact_source, &["phone", "phone_number"]),
+        location: string_field(contact_source, &["location", "address"]),
+        links,
+    };
+
+    let skills = match object.get("skills") {
+        Some(Value::Array(skills)) => skills
+            .iter()
+            .filter_map(|skill| match skill {
+                Value::String(s) => Some(s.trim().to_string()),
+                Value::Object(o) => string_field(o, &["name", "skill"]),
+                _ => None,
+            })
+            .filter(|skill| !skill.is_empty())
+            .collect(),
+        Some(Value::String(skills)) => skills
+            .split(',')
+            .map(|skill| skill.trim().to_string())
+            .filter(|skill| !skill.is_empty())
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    let experience = match object.get("experience") {
+        Some(Value::Array(entries)) => entries
+            .iter()
+            .filter_map(|entry| match entry {
+                Value::String(s) => Some(ExperienceEntry {
+                    description: Some(s.clone()),
+                    ..Default::default()
+                }),
+                Value::Object(o) => Some(ExperienceEntry {
+                    title: string_field(o, &["title", "position", "role"]),
+                    company: string_field(o, &["company", "organization", "employer"]),
+                    start_date: string_field(o, &["start_date", "start", "from"]),
+                    end_date: string_field(o, &["end_date", "end", "to"]),
+                    description: string_field(o, &["description", "summary"]),
+                }),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    let education = match object.get("education") {
+        Some(Value::Array(entries)) => entries
+            .iter()
+            .filter_map(|entry| match entry {
+                Value::String(s) => Some(EducationEntry {
+                    degree: Some(s.clone()),
+                    ..Default::default()
+                }),
+                Value::Object(o) => Some(EducationEntry {
+                    institution: string_field(o, &["institution", "school", "university"]),
+                    degree: string_field(o, &["degree", "qualification"]),
+                    field: string_field(o, &["field", "major", "field_of_study"]),
+                    start_date: string_field(o, &["start_date", "start", "from"]),
+                    end_date: string_field(o, &["end_date", "end", "to", "year"]),
+                }),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    let score_source = object
+        .get("scores")
+        .and_then(Value::as_object)
+        .unwrap_or(object);
+
+    let scores = Scores {
+        overall: number_field(score_source, &["overall", "overall_score", "score"]),
+        skills: number_field(score_source, &["skills", "skills_score"]),
+        experience: number_field(score_source, &["experience", "experience_score"]),
+        education: number_field(score_source, &["education", "education_score"]),
+        formatting: number_field(score_source, &["formatting", "formatting_score"]),
+    };
+
+    Ok(AnalysisResult {
+        schema_version: CURRENT_SCHEMA_VERSION,
+        contact,
+        summary: string_field(object, &["summary", "profile", "objective"]),
+        skills,
+        experience,
+        education,
+        scores,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn upgrades_flat_legacy_payload() {
+        let stored = json!({
+            "name": "Jane Doe",
+            "email": "jane@example.com",
+            "phone_number": "+1 555 010 0000",
+            "address": "Berlin",
+            "urls": ["https://github.com/jane"],
+            "skills": ["Rust", { "name": "SQL" }, " ", 42],
+            "experience": [
+                "Freelance consulting",
+                { "position": "Engineer", "employer": "Acme", "from": "2019", "to": "2023", "summary": "APIs" }
+            ],
+            "education": [{ "school": "TU Berlin", "qualification": "MSc", "major": "CS", "year": 2018 }],
+            "overall_score": 81.5,
+            "skills_score": 90
+        });
+
+        let result = upgrade(LEGACY_SCHEMA_VERSION, stored).unwrap();
+
+        assert_eq!(result.schema_version, CURRENT_SCHEMA_VERSION);
+        assert_eq!(
+            result.contact,
+            ContactInfo {
+                name: Some("Jane Doe".to_string()),
+                email: Some("jane@example.com".to_string()),
+                phone: Some("+1 555 010 0000".to_string()),
+                location: Some("Berlin".to_string()),
+                links: vec!["https://github.com/jane".to_string()],
+            }
+        );
+        assert_eq!(result.skills, vec!["Rust", "SQL"]);
+        assert_eq!(result.experience[0].description.as_deref(), Some("Freelance consulting"));
+        assert_eq!(
+            result.experience[1],
+            ExperienceEntry {
+                title: Some("Engineer".to_string()),
+                company: Some("Acme".to_string()),
+                start_date: Some("2019".to_string()),
+                end_date: Some("2023".to_string()),
+                description: Some("APIs".to_string()),
+            }
+        );
+        assert_eq!(result.education[0].institution.as_deref(), Some("TU Berlin"));
+        assert_eq!(result.education[0].end_date.as_deref(), Some("2018"));
+        assert_eq!(result.scores.overall, Some(81.5));
+        assert_eq!(result.scores.skills, Some(90.0));
+        assert!(result.validate().is_ok());
+    }
+
+    #[test]
+    fn upgrades_legacy_payload_with_nested_contact_and_comma_skills() {
+        let stored = json!({
+            "contact": { "full_name": "John Roe", "location": "Oslo" },
+            "skills": "Go, Kubernetes, ,Terraform",
+            "scores": { "overall": 70 },
+            "profile": "Platform engineer"
+        });
+
+        let result = upgrade(LEGACY_SCHEMA_VERSION, stored).unwrap();
+
+        assert_eq!(result.contact.name.as_deref(), Some("John Roe"));
+        assert_eq!(result.contact.location.as_deref(), Some("Oslo"));
+        assert_eq!(result.skills, vec!["Go", "Kubernetes", "Terraform"]);
+        assert_eq!(result.scores.overall, Some(70.0));
+        assert_eq!(result.summary.as_deref(), Some("Platform engineer"));
+    }
+
+    #[test]
+    fn current_payload_round_trips() {
+        let result = AnalysisResult {
+            summary: Some("Backend developer".to_string()),
+            skills: vec!["Rust".to_string()],
+            ..Default::default()
+        };
+
+        assert_eq!(upgrade(CURRENT_SCHEMA_VERSION, result.to_value()).unwrap(), result);
+    }
+
+    #[test]
+    fn rejects_unreadable_payloads() {
+        assert!(upgrade(LEGACY_SCHEMA_VERSION, json!(["not", "an", "object"])).is_err());
+        assert!(upgrade(CURRENT_SCHEMA_VERSION, json!({ "skills": "Rust" })).is_err());
+        assert!(upgrade(CURRENT_SCHEMA_VERSION + 1, json!({})).is_err());
+    }
+
+    #[test]
+    fn responses_without_a_version_are_treated_as_legacy() {
+        let result = AnalysisResult::from_response(json!({ "name": "Jane Doe", "skills": ["Rust"] })).unwrap();
+
+        assert_eq!(result.contact.name.as_deref(), Some("Jane Doe"));
+        assert_eq!(result.skills, vec!["Rust"]);
+    }
+
+    #[test]
+    fn responses_with_out_of_range_scores_are_rejected() {
+        let response = json!({ "schema_version": CURRENT_SCHEMA_VERSION, "scores": { "overall": 120.0 } });
+
+        assert_eq!(
+            AnalysisResult::from_response(response).unwrap_err(),
+            "overall score 120 is outside 0-100"
+        );
+    }
+}
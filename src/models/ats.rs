@@ -0,0 +1,30 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum AtsSeverity {
+    Info,
+    Warning,
+    Critical,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AtsIssue {
+    pub code: String,
+    pub severity: AtsSeverity,
+    pub message: String,
+    pub suggestion: String,
+}
+
+/// Result of the rule-based ATS compatibility check, stored in `resumes.ats_report`.
+///
+/// `score` starts at 100 and loses points per issue depending on its severity,
+/// `passed` is false as soon as there is a critical issue.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AtsReport {
+    pub score: i32,
+    pub passed: bool,
+    pub issues: Vec<AtsIssue>,
+    pub checked_at: DateTime<Utc>,
+}
@@ -0,0 +1,48 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum BatchFileStatus {
+    Created,
+    Failed,
+}
+
+/// Outcome for one file of a bulk upload, files from a ZIP name their archive.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BatchFileResult {
+    pub file_name: String,
+    pub archive: Option<String>,
+    pub status: BatchFileStatus,
+    pub resume_id: Option<Uuid>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
+pub struct ResumeBatch {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    #[serde(rename = "fileCount")]
+    pub file_count: i32,
+    #[serde(rename = "failedCount")]
+    pub failed_count: i32,
+    pub results: Value,
+    #[serde(rename = "createdAt")]
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl ResumeBatch {
+    pub fn results(&self) -> Vec<BatchFileResult> {
+        serde_json::from_value(self.results.clone()).unwrap_or_default()
+    }
+}
+
+/// Analysis status of the resumes a batch created that still exist.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct BatchProgress {
+    pub pending: i64,
+    pub completed: i64,
+    pub failed: i64,
+}
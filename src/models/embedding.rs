@@ -0,0 +1,13 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
+pub struct ResumeEmbedding {
+    pub resume_id: Uuid,
+    pub model: String,
+    pub dimensions: i32,
+    pub embedding: Vec<f32>,
+    #[serde(rename = "createdAt")]
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,71 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "job_seniority", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum Seniority {
+    Intern,
+    Junior,
+    Mid,
+    Senior,
+    Lead,
+    Principal,
+}
+
+impl Seniority {
+    pub fn to_str(self) -> &'static str {
+        match self {
+            Seniority::Intern => "intern",
+            Seniority::Junior => "junior",
+            Seniority::Mid => "mid",
+            Seniority::Senior => "senior",
+            Seniority::Lead => "lead",
+            Seniority::Principal => "principal",
+        }
+    }
+
+    /// Years of experience a level usually implies, used when a job doesn't state a minimum.
+    pub fn typical_years(&self) -> f64 {
+        match self {
+            Seniority::Intern => 0.0,
+            Seniority::Junior => 1.0,
+            Seniority::Mid => 3.0,
+            Seniority::Senior => 5.0,
+            Seniority::Lead => 7.0,
+            Seniority::Principal => 10.0,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
+pub struct JobDescription {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub title: String,
+    pub description: String,
+    #[serde(rename = "requiredSkills")]
+    pub required_skills: Vec<String>,
+    #[serde(rename = "preferredSkills")]
+    pub preferred_skills: Vec<String>,
+    pub seniority: Option<Seniority>,
+    #[serde(rename = "minYearsExperience")]
+    pub min_years_experience: Option<i32>,
+    #[serde(rename = "matchVersion")]
+    pub match_version: i32,
+    #[serde(rename = "createdAt")]
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// A cached resume score for a job, `total` is the number of rows matching the ranking filters.
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
+pub struct JobMatchScore {
+    pub resume_id: Uuid,
+    pub score: f64,
+    pub report: Value,
+    pub total: i64,
+}
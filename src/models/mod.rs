@@ -1,2 +1,14 @@
 pub mod users;
-pub mod resume;
\ No newline at end of file
+pub mod resume;
+pub mod analysis;
+pub mod analysis_result;
+pub mod search;
+pub mod embedding;pub mod job;
+pub mod skill;
+pub mod ats;
+pub mod batch;
+pub mod tag;
+pub mod pipeline;
+pub mod share;
+pub mod retention;
+pub mod quota;
\ No newline at end of file
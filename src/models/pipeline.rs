@@ -0,0 +1,47 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A column of the hiring pipeline, new candidates start in the lowest position.
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
+pub struct PipelineStage {
+    pub id: Uuid,
+    pub name: String,
+    pub position: i32,
+    /// Stages such as hired or rejected that end a candidate's pipeline
+    pub terminal: bool,
+    #[serde(rename = "createdAt")]
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// One move through the pipeline, the first entry of a candidate has no `from_stage`.
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
+pub struct StageTransition {
+    pub id: Uuid,
+    #[serde(rename = "resumeId")]
+    pub resume_id: Uuid,
+    pub version: i32,
+    #[serde(rename = "fromStageId")]
+    pub from_stage_id: Option<Uuid>,
+    #[serde(rename = "fromStage")]
+    pub from_stage: Option<String>,
+    #[serde(rename = "toStageId")]
+    pub to_stage_id: Option<Uuid>,
+    #[serde(rename = "toStage")]
+    pub to_stage: String,
+    #[serde(rename = "changedBy")]
+    pub changed_by: Option<Uuid>,
+    #[serde(rename = "changedByName")]
+    pub changed_by_name: Option<String>,
+    pub note: Option<String>,
+    #[serde(rename = "changedAt")]
+    pub changed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
+pub struct PipelineStageCount {
+    pub stage_id: Uuid,
+    pub count: i64,
+}
@@ -0,0 +1,49 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::users::UserRole;
+
+/// Limits for a role or for one user, a user's own quota replaces their role's. Missing limits are unlimited.
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
+pub struct Quota {
+    pub id: Uuid,
+    pub user_id: Option<Uuid>,
+    #[serde(rename = "userEmail")]
+    pub user_email: Option<String>,
+    pub role: Option<UserRole>,
+    #[serde(rename = "maxResumes")]
+    pub max_resumes: Option<i32>,
+    #[serde(rename = "maxStorageBytes")]
+    pub max_storage_bytes: Option<i64>,
+    #[serde(rename = "maxAnalysesPerDay")]
+    pub max_analyses_per_day: Option<i32>,
+    #[serde(rename = "updatedBy")]
+    pub updated_by: Option<Uuid>,
+    #[serde(rename = "createdAt")]
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// What a user has used against the quota that applies to them.
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
+pub struct QuotaUsage {
+    #[serde(rename = "quotaId")]
+    pub quota_id: Option<Uuid>,
+    /// Whether the user's own quota applies rather than their role's
+    #[serde(rename = "userQuota")]
+    pub user_quota: bool,
+    #[serde(rename = "maxResumes")]
+    pub max_resumes: Option<i32>,
+    #[serde(rename = "maxStorageBytes")]
+    pub max_storage_bytes: Option<i64>,
+    #[serde(rename = "maxAnalysesPerDay")]
+    pub max_analyses_per_day: Option<i32>,
+    /// Trashed resumes count until they are purged
+    pub resumes: i64,
+    #[serde(rename = "storageBytes")]
+    pub storage_bytes: i64,
+    #[serde(rename = "analysesToday")]
+    pub analyses_today: i32,
+}
@@ -3,13 +3,113 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use uuid::Uuid;
 
+use super::{analysis_result::{self, AnalysisResult}, ats::AtsReport};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "analysis_status", rename_all = "lowercase")]
+pub enum AnalysisStatus {
+    Pending,
+    Completed,
+    Failed,
+}
+
+impl AnalysisStatus {
+    pub fn to_str(self) -> &'static str {
+        match self {
+            AnalysisStatus::Pending => "pending",
+            AnalysisStatus::Completed => "completed",
+            AnalysisStatus::Failed => "failed",
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow, sqlx::Type)]
 pub struct Resume {
     pub id: Uuid,
     pub user_id: Uuid,
-    pub file_path: String,
+    /// None once the resume has been anonymized and its file removed
+    pub file_path: Option<String>,
     #[serde(rename = "analysisResult")]
     pub analysis_result: Option<Value>,
     #[serde(rename = "createdAt")]
     pub uploaded_at: Option<DateTime<Utc>>,
-}
\ No newline at end of file
+    #[serde(rename = "latestAnalysisId")]
+    pub latest_analysis_id: Option<Uuid>,
+    #[serde(rename = "analysisStatus")]
+    pub analysis_status: AnalysisStatus,
+    #[serde(rename = "analysisSchemaVersion")]
+    pub analysis_schema_version: i32,
+    #[serde(rename = "documentFormat")]
+    pub document_format: Option<String>,
+    #[serde(rename = "extractedText")]
+    pub extracted_text: Option<String>,
+    #[serde(rename = "pageCount")]
+    pub page_count: Option<i32>,
+    #[serde(rename = "wordCount")]
+    pub word_count: Option<i32>,
+    pub language: Option<String>,
+    #[serde(rename = "atsReport")]
+    pub ats_report: Option<Value>,
+    #[serde(rename = "documentId")]
+    pub document_id: Uuid,
+    pub version: i32,
+    #[serde(rename = "fileName")]
+    pub file_name: Option<String>,
+    /// SHA-256 of the uploaded bytes, identical uploads share storage and analysis
+    #[serde(rename = "contentHash")]
+    pub content_hash: Option<String>,
+    #[serde(rename = "stageId")]
+    pub stage_id: Uuid,
+    #[serde(rename = "stageChangedAt")]
+    pub stage_changed_at: Option<DateTime<Utc>>,
+    /// Set while the resume is in the trash
+    #[serde(rename = "deletedAt")]
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// Set once a retention policy has stripped the resume of personal details
+    #[serde(rename = "anonymizedAt")]
+    pub anonymized_at: Option<DateTime<Utc>>,
+}
+
+/// A candidate's resume across revisions, each upload being one numbered version.
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
+pub struct ResumeDocument {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub title: String,
+    #[serde(rename = "currentResumeId")]
+    pub current_resume_id: Option<Uuid>,
+    #[serde(rename = "versionCount")]
+    pub version_count: i64,
+    #[serde(rename = "latestVersion")]
+    pub latest_version: i32,
+    #[serde(rename = "createdAt")]
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl Resume {
+    /// Name the file was uploaded under, stored files are named by content hash.
+    pub fn original_file_name(&self) -> String {
+        self.file_name.clone().unwrap_or_else(|| {
+            let file_path = self.file_path.as_deref().unwrap_or_default();
+            std::path::Path::new(file_path)
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or(file_path)
+                .to_string()
+        })
+    }
+
+    /// The stored analysis upgraded to the current schema, if there is one and it can be read.
+    pub fn analysis(&self) -> Option<AnalysisResult> {
+        let value = self.analysis_result.as_ref()?;
+        analysis_result::upgrade(self.analysis_schema_version, value.clone())
+            .map_err(|e| tracing::warn!("Could not upgrade analysis of resume {}: {}", self.id, e))
+            .ok()
+    }
+
+    pub fn ats(&self) -> Option<AtsReport> {
+        serde_json::from_value(self.ats_report.clone()?).ok()
+    }
+}
@@ -0,0 +1,70 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "retention_action", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum RetentionAction {
+    /// The resume, its history and its file are removed
+    Delete,
+    /// The resume is kept for statistics with personal details stripped and its file removed
+    Anonymize,
+}
+
+impl RetentionAction {
+    pub fn to_str(self) -> &'static str {
+        match self {
+            RetentionAction::Delete => "delete",
+            RetentionAction::Anonymize => "anonymize",
+        }
+    }
+}
+
+/// How long resumes are kept. The policy without a user is the global default.
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
+pub struct RetentionPolicy {
+    pub id: Uuid,
+    pub user_id: Option<Uuid>,
+    #[serde(rename = "userEmail")]
+    pub user_email: Option<String>,
+    /// None keeps resumes indefinitely
+    #[serde(rename = "retentionMonths")]
+    pub retention_months: Option<i32>,
+    pub action: RetentionAction,
+    #[serde(rename = "updatedBy")]
+    pub updated_by: Option<Uuid>,
+    #[serde(rename = "createdAt")]
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// A resume past the retention period of the policy that applies to its owner.
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
+pub struct RetentionCandidate {
+    #[serde(rename = "resumeId")]
+    pub resume_id: Uuid,
+    pub user_id: Uuid,
+    #[serde(rename = "documentId")]
+    pub document_id: Uuid,
+    #[serde(rename = "filePath")]
+    pub file_path: Option<String>,
+    #[serde(rename = "uploadedAt")]
+    pub uploaded_at: Option<DateTime<Utc>>,
+    #[serde(rename = "deletedAt")]
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub action: RetentionAction,
+    #[serde(rename = "retentionMonths")]
+    pub retention_months: i32,
+    /// Whether the owner's own policy applies rather than the global one
+    #[serde(rename = "userPolicy")]
+    pub user_policy: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
+pub struct RetentionSummary {
+    pub action: RetentionAction,
+    pub resumes: i64,
+    pub users: i64,
+}
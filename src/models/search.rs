@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchMode {
+    /// Web-style syntax: `"quoted phrases"`, `or`, `-excluded`
+    #[default]
+    Web,
+    Phrase,
+    Prefix,
+}
+
+impl SearchMode {
+    pub fn to_str(self) -> &'static str {
+        match self {
+            SearchMode::Web => "web",
+            SearchMode::Phrase => "phrase",
+            SearchMode::Prefix => "prefix",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
+pub struct ResumeSearchHit {
+    pub id: Uuid,
+    pub rank: f32,
+    pub snippet: String,
+    pub total: i64,
+}
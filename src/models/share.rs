@@ -0,0 +1,58 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
+pub struct ShareLink {
+    pub id: Uuid,
+    #[serde(rename = "resumeId")]
+    pub resume_id: Uuid,
+    pub user_id: Uuid,
+    /// First characters of the token so owners can tell their links apart
+    #[serde(rename = "tokenPrefix")]
+    pub token_prefix: String,
+    #[serde(skip_serializing)]
+    pub password_hash: Option<String>,
+    pub redacted: bool,
+    #[serde(rename = "expiresAt")]
+    pub expires_at: DateTime<Utc>,
+    #[serde(rename = "maxViews")]
+    pub max_views: Option<i32>,
+    #[serde(rename = "viewCount")]
+    pub view_count: i32,
+    #[serde(rename = "lastViewedAt")]
+    pub last_viewed_at: Option<DateTime<Utc>>,
+    #[serde(rename = "revokedAt")]
+    pub revoked_at: Option<DateTime<Utc>>,
+    #[serde(rename = "createdAt")]
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl ShareLink {
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= Utc::now()
+    }
+
+    pub fn views_exhausted(&self) -> bool {
+        self.max_views.is_some_and(|max_views| self.view_count >= max_views)
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.revoked_at.is_none() && !self.is_expired() && !self.views_exhausted()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
+pub struct ShareAccess {
+    pub id: Uuid,
+    #[serde(rename = "shareLinkId")]
+    pub share_link_id: Uuid,
+    pub granted: bool,
+    /// Why access was refused, e.g. `expired` or `wrong_password`
+    pub reason: Option<String>,
+    pub ip: Option<String>,
+    #[serde(rename = "userAgent")]
+    pub user_agent: Option<String>,
+    #[serde(rename = "accessedAt")]
+    pub accessed_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,23 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
+pub struct Skill {
+    pub id: Uuid,
+    pub name: String,
+    pub category: String,
+    #[serde(rename = "parentId")]
+    pub parent_id: Option<Uuid>,
+    #[serde(rename = "createdAt")]
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
+pub struct SkillAlias {
+    pub alias: String,
+    #[serde(rename = "skillId")]
+    pub skill_id: Uuid,
+    #[serde(rename = "createdAt")]
+    pub created_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,49 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Colour given to tags created without one
+pub const DEFAULT_TAG_COLOUR: &str = "#6b7280";
+
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
+pub struct Tag {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    /// Hex colour such as `#22c55e`
+    pub colour: String,
+    #[serde(rename = "resumeCount")]
+    pub resume_count: i64,
+    #[serde(rename = "createdAt")]
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// A tag applied to one resume, fetched in bulk for listings.
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
+pub struct ResumeTag {
+    #[serde(rename = "resumeId")]
+    pub resume_id: Uuid,
+    pub id: Uuid,
+    pub name: String,
+    pub colour: String,
+    #[serde(rename = "taggedAt")]
+    pub tagged_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
+pub struct ResumeNote {
+    pub id: Uuid,
+    #[serde(rename = "resumeId")]
+    pub resume_id: Uuid,
+    #[serde(rename = "authorId")]
+    pub author_id: Uuid,
+    #[serde(rename = "authorName")]
+    pub author_name: String,
+    pub body: String,
+    #[serde(rename = "createdAt")]
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: Option<DateTime<Utc>>,
+}
@@ -7,13 +7,15 @@ use uuid::Uuid;
 pub enum UserRole {
     Admin,
     User,
+    Reviewer,
 }
 
 impl UserRole {
-    pub fn to_str(&self) -> &str {
+    pub fn to_str(self) -> &'static str {
         match self {
             UserRole::Admin => "admin",
-            UserRole::User => "user"
+            UserRole::User => "user",
+            UserRole::Reviewer => "reviewer",
         }
     }
 }
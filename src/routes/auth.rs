@@ -82,7 +82,7 @@ pub async fn login(
     if password_matched {
         let token = token::create_token(
             &user.id.to_string(),
-            &app_state.env.jwt_secret.as_bytes(),
+            app_state.env.jwt_secret.as_bytes(),
             app_state.env.jwt_expiration,
         )
         .map_err(|e| HttpError::server_error(e.to_string()))?;
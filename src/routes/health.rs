@@ -0,0 +1,26 @@
+use std::sync::Arc;
+
+use axum::{http::StatusCode, response::IntoResponse, routing::get, Extension, Json, Router};
+
+use crate::{services::circuit_breaker::CircuitState, utils::dtos::HealthResponseDto, AppState};
+
+pub fn health_routes() -> Router {
+    Router::new().route("/", get(get_health))
+}
+
+pub async fn get_health(Extension(app_state): Extension<Arc<AppState>>) -> impl IntoResponse {
+    let nlp = app_state.circuit_breaker.status();
+
+    // The API itself stays up while the NLP service is down, uploads are queued instead
+    let status = match nlp.state {
+        CircuitState::Closed => "ok",
+        CircuitState::HalfOpen | CircuitState::Open => "degraded",
+    };
+
+    let response = HealthResponseDto {
+        status: status.to_string(),
+        nlp,
+    };
+
+    (StatusCode::OK, Json(response))
+}
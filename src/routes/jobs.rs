@@ -0,0 +1,210 @@
+use std::{collections::HashMap, sync::Arc};
+
+use axum::{extract::{Path, Query}, http::StatusCode, response::IntoResponse, routing::get, Extension, Json, Router};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{services::{database::{JobActions, JobDescriptionParams, UserActions}, matching::refresh_job_scores, middleware::JWTAuthMiddleware, redaction::{is_blind_review, redact_resume}, taxonomy::SkillTaxonomy}, utils::{dtos::{FilterJobDto, JobData, JobDescriptionRequestDto, JobListResponseDto, JobRankingQueryDto, JobRankingResponseDto, JobResponseDto, RankedResumeDto, FilterResumeDto, RequestQueryDto, Response, ResumeViewQueryDto}, error::{ErrorMessage, HttpError}}, AppState};
+
+pub fn job_routes() -> Router {
+    Router::new()
+        .route("/", get(get_jobs).post(create_job))
+        .route("/{job_id}", get(get_job).put(update_job).delete(delete_job))
+        .route("/{job_id}/ranking", get(get_job_ranking))
+}
+
+fn job_params(body: &JobDescriptionRequestDto, taxonomy: &SkillTaxonomy) -> JobDescriptionParams {
+    JobDescriptionParams {
+        title: body.title.trim().to_string(),
+        description: body.description.clone(),
+        required_skills: taxonomy.normalize(&body.required_skills),
+        preferred_skills: taxonomy.normalize(&body.preferred_skills),
+        seniority: body.seniority,
+        min_years_experience: body.min_years_experience,
+    }
+}
+
+pub async fn create_job(
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+    Json(body): Json<JobDescriptionRequestDto>,
+) -> Result<impl IntoResponse, HttpError> {
+    body.validate()
+        .map_err(|e| HttpError::bad_request(e.to_string()))?;
+
+    let job = app_state
+        .db_client
+        .save_job(user.user.id, &job_params(&body, &app_state.taxonomy.get()))
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    let response = JobResponseDto {
+        status: "success".to_string(),
+        data: JobData { job: FilterJobDto::filter_job(&job) },
+    };
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+pub async fn get_jobs(
+    Query(query_params): Query<RequestQueryDto>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+) -> Result<impl IntoResponse, HttpError> {
+    query_params
+        .validate()
+        .map_err(|e| HttpError::bad_request(e.to_string()))?;
+
+    let page = query_params.page.unwrap_or(1);
+    let limit = query_params.limit.unwrap_or(10);
+
+    let jobs = app_state
+        .db_client
+        .get_jobs(user.user.id, page as u32, limit)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    let job_count = app_state
+        .db_client
+        .get_job_count(user.user.id)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    let response = JobListResponseDto {
+        status: "success".to_string(),
+        jobs: FilterJobDto::filter_jobs(&jobs),
+        results: job_count,
+    };
+    Ok(Json(response))
+}
+
+pub async fn get_job(
+    Path(job_id): Path<Uuid>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+) -> Result<impl IntoResponse, HttpError> {
+    let job = app_state
+        .db_client
+        .get_job(user.user.id, job_id)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?
+        .ok_or_else(|| HttpError::not_found(ErrorMessage::JobNotFound.to_string()))?;
+
+    let response = JobResponseDto {
+        status: "success".to_string(),
+        data: JobData { job: FilterJobDto::filter_job(&job) },
+    };
+    Ok(Json(response))
+}
+
+pub async fn update_job(
+    Path(job_id): Path<Uuid>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+    Json(body): Json<JobDescriptionRequestDto>,
+) -> Result<impl IntoResponse, HttpError> {
+    body.validate()
+        .map_err(|e| HttpError::bad_request(e.to_string()))?;
+
+    let job = app_state
+        .db_client
+        .update_job(user.user.id, job_id, &job_params(&body, &app_state.taxonomy.get()))
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?
+        .ok_or_else(|| HttpError::not_found(ErrorMessage::JobNotFound.to_string()))?;
+
+    let response = JobResponseDto {
+        status: "success".to_string(),
+        data: JobData { job: FilterJobDto::filter_job(&job) },
+    };
+    Ok(Json(response))
+}
+
+pub async fn delete_job(
+    Path(job_id): Path<Uuid>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+) -> Result<impl IntoResponse, HttpError> {
+    let deleted = app_state
+        .db_client
+        .delete_job(user.user.id, job_id)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    if !deleted {
+        return Err(HttpError::not_found(ErrorMessage::JobNotFound.to_string()));
+    }
+
+    Ok(Json(Response {
+        message: "Job description deleted successfully".to_string(),
+        status: "success",
+    }))
+}
+
+pub async fn get_job_ranking(
+    Path(job_id): Path<Uuid>,
+    Query(query_params): Query<JobRankingQueryDto>,
+    Query(view): Query<ResumeViewQueryDto>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+) -> Result<impl IntoResponse, HttpError> {
+    query_params
+        .validate()
+        .map_err(|e| HttpError::bad_request(e.to_string()))?;
+
+    let page = query_params.page.unwrap_or(1);
+    let limit = query_params.limit.unwrap_or(10);
+    let user_id = &user.user.id;
+
+    let job = app_state
+        .db_client
+        .get_job(*user_id, job_id)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?
+        .ok_or_else(|| HttpError::not_found(ErrorMessage::JobNotFound.to_string()))?;
+
+    refresh_job_scores(&app_state, &job).await?;
+
+    let scores = app_state
+        .db_client
+        .get_job_ranking(&job, query_params.min_score.unwrap_or(0.0), page as u32, limit)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    let resume_ids: Vec<Uuid> = scores.iter().map(|score| score.resume_id).collect();
+    let mut resumes: HashMap<Uuid, _> = app_state
+        .db_client
+        .get_resumes_by_ids(*user_id, &resume_ids)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?
+        .into_iter()
+        .map(|resume| (resume.id, resume))
+        .collect();
+
+    let blind = is_blind_review(&app_state, &user.user, view.blind);
+    let total = scores.first().map(|score| score.total).unwrap_or(0);
+    let offset = (page - 1) * limit;
+    let ranked = scores
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, score)| {
+            resumes.remove(&score.resume_id).map(|resume| RankedResumeDto {
+                rank: offset + index + 1,
+                resume: if blind {
+                    FilterResumeDto::filter_resume(&redact_resume(&resume))
+                } else {
+                    FilterResumeDto::filter_resume(&resume)
+                },
+                score: score.score,
+                report: serde_json::from_value(score.report).ok(),
+            })
+        })
+        .collect();
+
+    let response = JobRankingResponseDto {
+        status: "success".to_string(),
+        job: FilterJobDto::filter_job(&job),
+        resumes: ranked,
+        results: total,
+    };
+    Ok(Json(response))
+}
@@ -1,3 +1,10 @@
 pub mod users;
 pub mod auth;
-pub mod resumes;
\ No newline at end of file
+pub mod resumes;
+pub mod health;pub mod jobs;
+pub mod skills;
+pub mod tags;
+pub mod stages;
+pub mod share;
+pub mod retention;
+pub mod quotas;
\ No newline at end of file
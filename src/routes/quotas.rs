@@ -0,0 +1,147 @@
+use std::sync::Arc;
+
+use axum::{extract::Path, middleware::from_fn, response::IntoResponse, routing::{get, put}, Extension, Json, Router};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{models::{quota::Quota, users::UserRole}, services::{database::{QuotaActions, QuotaParams, UserActions}, middleware::{role_check, JWTAuthMiddleware}}, utils::{dtos::{FilterQuotaDto, QuotaData, QuotaDto, QuotaListResponseDto, QuotaResponseDto, Response}, error::{ErrorMessage, HttpError}}, AppState};
+
+pub fn quota_routes() -> Router {
+    Router::new()
+        .route("/", get(get_quotas))
+        .route("/roles/{role}", put(update_role_quota).delete(delete_role_quota))
+        .route("/users/{user_id}", put(update_user_quota).delete(delete_user_quota))
+        .layer(from_fn(|state, req, next| {
+            role_check(state, req, next, vec![UserRole::Admin])
+        }))
+}
+
+fn parse_role(role: &str) -> Result<UserRole, HttpError> {
+    match role.to_lowercase().as_str() {
+        "admin" => Ok(UserRole::Admin),
+        "user" => Ok(UserRole::User),
+        "reviewer" => Ok(UserRole::Reviewer),
+        _ => Err(HttpError::bad_request(format!("Unknown role {}", role))),
+    }
+}
+
+fn quota_params(body: &QuotaDto) -> Result<QuotaParams, HttpError> {
+    body.validate()
+        .map_err(|e| HttpError::bad_request(e.to_string()))?;
+
+    Ok(QuotaParams {
+        max_resumes: body.max_resumes,
+        max_storage_bytes: body.max_storage_bytes,
+        max_analyses_per_day: body.max_analyses_per_day,
+    })
+}
+
+fn quota_response(quota: &Quota) -> QuotaResponseDto {
+    QuotaResponseDto {
+        status: "success".to_string(),
+        data: QuotaData {
+            quota: FilterQuotaDto::filter_quota(quota),
+        },
+    }
+}
+
+pub async fn get_quotas(
+    Extension(app_state): Extension<Arc<AppState>>,
+) -> Result<impl IntoResponse, HttpError> {
+    let quotas = app_state
+        .db_client
+        .get_quotas()
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    let response = QuotaListResponseDto {
+        status: "success".to_string(),
+        quotas: FilterQuotaDto::filter_quotas(&quotas),
+        results: quotas.len() as i64,
+    };
+    Ok(Json(response))
+}
+
+pub async fn update_role_quota(
+    Path(role): Path<String>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+    Json(body): Json<QuotaDto>,
+) -> Result<impl IntoResponse, HttpError> {
+    let role = parse_role(&role)?;
+    let params = quota_params(&body)?;
+
+    let quota = app_state
+        .db_client
+        .save_role_quota(role, &params, user.user.id)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    Ok(Json(quota_response(&quota)))
+}
+
+pub async fn delete_role_quota(
+    Path(role): Path<String>,
+    Extension(app_state): Extension<Arc<AppState>>,
+) -> Result<impl IntoResponse, HttpError> {
+    let role = parse_role(&role)?;
+
+    let deleted = app_state
+        .db_client
+        .delete_role_quota(role)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    if !deleted {
+        return Err(HttpError::not_found(ErrorMessage::QuotaNotFound.to_string()));
+    }
+
+    Ok(Json(Response {
+        message: "Role quota removed, the role is unlimited".to_string(),
+        status: "success",
+    }))
+}
+
+pub async fn update_user_quota(
+    Path(user_id): Path<Uuid>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+    Json(body): Json<QuotaDto>,
+) -> Result<impl IntoResponse, HttpError> {
+    let params = quota_params(&body)?;
+
+    app_state
+        .db_client
+        .get_user(Some(user_id), None, None, None)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?
+        .ok_or_else(|| HttpError::not_found(ErrorMessage::UserNotFound.to_string()))?;
+
+    let quota = app_state
+        .db_client
+        .save_user_quota(user_id, &params, user.user.id)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    Ok(Json(quota_response(&quota)))
+}
+
+pub async fn delete_user_quota(
+    Path(user_id): Path<Uuid>,
+    Extension(app_state): Extension<Arc<AppState>>,
+) -> Result<impl IntoResponse, HttpError> {
+    let deleted = app_state
+        .db_client
+        .delete_user_quota(user_id)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    if !deleted {
+        return Err(HttpError::not_found(ErrorMessage::QuotaNotFound.to_string()));
+    }
+
+    Ok(Json(Response {
+        message: "User quota removed, the role's quota applies".to_string(),
+        status: "success",
+    }))
+}
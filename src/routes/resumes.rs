@@ -1,86 +1,565 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
-use axum::{extract::{Multipart, Path, Query}, response::IntoResponse, routing::{get, post}, Extension, Json, Router};
-use bytes::Bytes;
-use tokio::fs;
+use axum::{body::Body, extract::{DefaultBodyLimit, Multipart, Path, Query}, http::{header, StatusCode}, middleware::from_fn, response::IntoResponse, routing::{delete, get, post, put}, Extension, Json, Router};
+use bytes::{Bytes, BytesMut};
+use chrono::{Duration, Utc};
+use serde_json::Value;
 use uuid::Uuid;
 use validator::Validate;
 
-use crate::{services::{database::UserActions, middleware::JWTAuthMiddleware, nlp::call_nlp_service}, utils::{dtos::{FilterResumeDto, RequestQueryDto, Response, ResumeData, ResumeListResponseDto, ResumeResponseDto}, error::HttpError}, AppState};
+use crate::{models::{batch::{BatchFileResult, BatchFileStatus}, resume::{AnalysisStatus, Resume}, search::SearchMode, tag::ResumeTag, users::UserRole}, services::{analysis::{reanalyze_all, run_analysis}, importers::{import_linkedin, import_xml, IMPORT_MODEL_VERSION}, json_resume::JsonResume, export::{export_resumes, render_analysis_pdf, ExportFormat}, sharing::{generate_token, token_hash, TOKEN_PREFIX_LEN}, redaction::{is_blind_review, redact_document, redact_resume, redact_resume_analysis, Redactor}, bulk::{is_archive, unpack_archive, ArchiveLimits, BulkFile}, storage::{restore_if_missing, store_upload}, quota::{check_analysis_quota, check_storage_quota}, trash::{purge_at, purge_resume}, diff::diff_resumes, ats::{backfill_ats_report, record_ats_report}, matching::match_resume, embeddings::{embed_query, embed_resumes, find_similar, get_resume_embedding}, database::{AnalysisActions, BatchActions, DocumentActions, JobActions, NewResumeParams, NewShareLinkParams, PipelineActions, ShareActions, ResumeSearchParams, SearchActions, TagActions, TrashActions, UserActions}, extraction::{extract_document, DocumentFormat, ExtractedDocument}, middleware::{role_check, JWTAuthMiddleware}}, utils::{dtos::{AnalysisData, BatchData, BatchResponseDto, FilterBatchDto, AnalysisListResponseDto, AnalysisResponseDto, AnalyzeQueryDto, FilterAnalysisDto, CreateShareLinkDto, FilterShareAccessDto, FilterShareLinkDto, ShareAccessListResponseDto, ShareLinkCreatedData, ShareLinkCreatedResponseDto, ShareLinkData, ShareLinkListResponseDto, ShareLinkResponseDto, FilterNoteDto, FilterStageDto, FilterStageTransitionDto, MoveStageDto, PipelineCardDto, PipelineColumnDto, PipelineQueryDto, PipelineResponseDto, StageTimelineResponseDto, StageTransitionData, StageTransitionResponseDto, FilterResumeDocumentDto, FilterResumeDto, FilterResumeTagDto, NoteData, NoteListResponseDto, NoteRequestDto, NoteResponseDto, ResumeListQueryDto, ResumeTagListResponseDto, ResumeComparisonData, ResumeComparisonResponseDto, MatchData, MatchResponseDto, ReanalyzeRequestDto, RequestQueryDto, Response, ResumeData, ResumeDocumentData, ResumeDocumentListResponseDto, ResumeDocumentResponseDto, ResumeExportQueryDto, ResumeResponseDto, ResumeSearchHitDto, ResumeSearchQueryDto, ResumeSearchResponseDto, ResumeTextData, ResumeTextResponseDto, ResumeViewQueryDto, SimilarResumeDto, SimilarResumesQueryDto, SimilarResumesResponseDto, SetCurrentVersionDto, SimilarTextRequestDto, TrashListResponseDto, TrashedResumeDto, UploadResumeQueryDto}, error::{ErrorMessage, HttpError}, password}, AppState};
 
 pub fn resume_routes() -> Router {
     Router::new()
         .route("/resume", post(upload_resume))
+        .route("/json-resume", post(import_json_resume))
+        .route("/import", post(import_resume))
+        .route("/bulk", post(bulk_upload_resumes).layer(DefaultBodyLimit::disable()))
+        .route("/batches/{batch_id}", get(get_batch))
         .route("/resume/{resume_id}", get(get_resume).delete(delete_resume))
+        .route("/resume/{resume_id}/text", get(get_resume_text))
+        .route("/resume/{resume_id}/analyze", post(analyze_resume))
+        .route("/resume/{resume_id}/analyses", get(get_resume_analyses))
+        .route("/resume/{resume_id}/report", get(get_resume_report))
+        .route("/resume/{resume_id}/json-resume", get(export_json_resume))
+        .route("/resume/{resume_id}/tags/{tag_id}", put(tag_resume).delete(untag_resume))
+        .route("/resume/{resume_id}/notes", get(get_resume_notes).post(create_resume_note))
+        .route("/resume/{resume_id}/shares", get(get_share_links).post(create_share_link))
+        .route("/shares/{share_id}", delete(revoke_share_link))
+        .route("/shares/{share_id}/access", get(get_share_access))
+        .route("/resume/{resume_id}/notes/{note_id}", put(update_resume_note).delete(delete_resume_note))
         .route("/resumes", get(get_resumes))
+        .route("/resumes/export", get(export_resume_list))
+        .route("/trash", get(get_trash))
+        .route("/trash/{resume_id}", delete(purge_trashed_resume))
+        .route("/trash/{resume_id}/restore", post(restore_resume))
+        .route("/pipeline", get(get_pipeline))
+        .route("/resume/{resume_id}/stage", put(move_resume_stage))
+        .route("/resume/{resume_id}/timeline", get(get_resume_timeline))
+        .route("/documents/{document_id}", get(get_resume_document))
+        .route("/documents/{document_id}/current", put(set_current_version))
+        .route("/resume/{resume_id}/similar", get(get_similar_resumes))
+        .route("/resume/{resume_id}/match/{job_id}", post(match_resume_to_job))
+        .route("/resume/{resume_id}/compare/{other_id}", get(compare_resumes))
+        .route("/search", get(search_resumes))
+        .route("/similar", post(find_similar_resumes))
+        .route(
+            "/analyses/reanalyze",
+            post(reanalyze_resumes).layer(from_fn(|state, req, next| {
+                role_check(state, req, next, vec![UserRole::Admin])
+            })),
+        )
 }
 
 pub async fn upload_resume(
+    Query(query_params): Query<UploadResumeQueryDto>,
+    Query(view): Query<ResumeViewQueryDto>,
     Extension(app_state): Extension<Arc<AppState>>,
     Extension(user): Extension<JWTAuthMiddleware>,
     mut multipart: Multipart,
 ) -> Result<impl IntoResponse, HttpError> {
-    let upload_dir = "./uploads/temp";
-    fs::create_dir_all(upload_dir).await.map_err(|e| HttpError::server_error(e.to_string()))?;
-
     let user_id = &user.user.id;
-    let mut analysis_result = None;
-    let mut saved_resume = None;
+    let force_analysis = query_params.force_analysis.unwrap_or(false);
+
+    check_analysis_quota(&app_state, *user_id).await?;
+
+    if let Some(document_id) = query_params.document_id {
+        app_state
+            .db_client
+            .get_document(*user_id, document_id)
+            .await
+            .map_err(|e| HttpError::server_error(e.to_string()))?
+            .ok_or_else(|| HttpError::not_found(ErrorMessage::DocumentNotFound.to_string()))?;
+    }
 
-    while let Some(field) = multipart
+    let field = multipart
         .next_field()
         .await
         .map_err(|e| HttpError::bad_request(e.to_string()))?
-        {
-            let field_name = field.name().map(|s| s.to_string());
-            let file_name = field
-                .file_name()
-                .map(|s| s.to_string())
-                .unwrap_or_else(|| Uuid::new_v4().to_string());
-
-            let data: Bytes = field
-                .bytes()
-                .await
-                .map_err(|e| HttpError::bad_request(e.to_string()))?;
+        .ok_or_else(|| HttpError::bad_request("No file was uploaded".to_string()))?;
+    let field_name = field.name().map(|s| s.to_string());
+    let file_name = field
+        .file_name()
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+    let data: Bytes = field
+        .bytes()
+        .await
+        .map_err(|e| HttpError::bad_request(e.to_string()))?;
 
-            let file_path = format!("{}/{}", upload_dir, file_name);
-            fs::write(&file_path, &data).await.map_err(|e| HttpError::server_error(e.to_string()))?;
+    // Only one resume can be returned, several files go through the bulk endpoint
+    if multipart
+        .next_field()
+        .await
+        .map_err(|e| HttpError::bad_request(e.to_string()))?
+        .is_some()
+    {
+        return Err(HttpError::bad_request(
+            "Upload one file at a time, use /api/resumes/bulk for several".to_string(),
+        ));
+    }
 
-            println!(
-                "User {} uploaded file from field {:?} with filename {}, saved at {}",
-                user_id,
-                field_name,
-                file_name,
-                file_path
-            );
+    tracing::info!(
+        "User {} uploaded file from field {:?} with filename {}",
+        user_id,
+        field_name,
+        file_name
+    );
 
-            match call_nlp_service(&app_state.http_client, &file_path, &file_name).await{
-                Ok(result) => {
-                    analysis_result = Some(result);
-                    println!("Recieved analysis result: {:?}", analysis_result);
-                }
-                Err(e) => {
-                    println!("Error calling NLP service: {:?}", e);
-                }
+    let document = match extract_document(file_name.clone(), data.clone()).await {
+        Ok(document) => Some(document),
+        Err(e) => {
+            tracing::warn!("Could not extract text from {}: {}", file_name, e);
+            None
+        }
+    };
+
+    let mut resume = save_uploaded_resume(
+        &app_state,
+        *user_id,
+        &file_name,
+        &data,
+        document.as_ref(),
+        query_params.document_id,
+        None,
+    )
+    .await?;
+
+    analyze_upload(&app_state, &mut resume, force_analysis).await?;
+
+    let resume = if is_blind_review(&app_state, &user.user, view.blind) { redact_resume(&resume) } else { resume };
+    let tags = resume_tags(&app_state, *user_id, &[resume.id]).await?;
+
+    let response = ResumeResponseDto {
+        status: "success".to_string(),
+        data: ResumeData {
+            resume: FilterResumeDto::filter_resume(&resume),
+            tags: FilterResumeTagDto::filter_tags_for(&tags, resume.id),
+        }
+    };
+
+    Ok(Json(response))
+}
+
+/// Analyzes a freshly stored resume unless the NLP service is down or today's quota is used up,
+/// in which case it stays pending for the background worker. Embeds it either way.
+async fn analyze_upload(app_state: &AppState, resume: &mut Resume, force: bool) -> Result<(), HttpError> {
+    // Don't make the user wait on a service we already know is down,
+    // the pending analysis worker picks the resume up once it recovers
+    if app_state.circuit_breaker.is_open() {
+        tracing::info!("NLP service unavailable, resume {} queued for analysis", resume.id);
+    } else {
+        match run_analysis(
+            app_state,
+            resume,
+            &app_state.env.nlp_model,
+            &app_state.env.nlp_model_version,
+            force,
+            true,
+        )
+        .await
+        {
+            Ok(analysis) => {
+                tracing::debug!("Recieved analysis result: {:?}", analysis.analysis_result);
+                resume.latest_analysis_id = Some(analysis.id);
+                resume.analysis_result = Some(analysis.analysis_result);
+                resume.analysis_schema_version = analysis.schema_version;
+                resume.analysis_status = AnalysisStatus::Completed;
+            }
+            Err(e) if e.status == StatusCode::TOO_MANY_REQUESTS => {
+                // Used up by another upload in the meantime, the pending worker analyzes it once the quota resets
+                tracing::info!("Resume {} queued for analysis: {}", resume.id, e.message);
             }
+            Err(e) if app_state.circuit_breaker.is_open() => {
+                tracing::warn!("Error calling NLP service, resume {} queued for analysis: {}", resume.id, e);
+            }
+            Err(e) => {
+                tracing::warn!("Error calling NLP service: {}", e);
+                app_state
+                    .db_client
+                    .update_analysis_status(resume.id, AnalysisStatus::Failed)
+                    .await
+                    .map_err(|e| HttpError::server_error(e.to_string()))?;
+                resume.analysis_status = AnalysisStatus::Failed;
+            }
+        }
+    }
 
-            saved_resume = Some(app_state
-                .db_client
-                .save_resume(*user_id, &file_path, analysis_result.clone())
-                .await
-                .map_err(|e| HttpError::server_error(e.to_string()))?);
+    // Similarity search backfills missing embeddings, so a failure here isn't fatal
+    if let Err(e) = embed_resumes(app_state, std::slice::from_ref(resume)).await {
+        tracing::warn!("Could not embed resume {}: {}", resume.id, e);
+    }
+
+    Ok(())
+}
+
+/// Stores an uploaded file and creates its resume with an ATS report, analysis is up to the caller.
+async fn save_uploaded_resume(
+    app_state: &AppState,
+    user_id: Uuid,
+    file_name: &str,
+    data: &[u8],
+    document: Option<&ExtractedDocument>,
+    document_id: Option<Uuid>,
+    batch_id: Option<Uuid>,
+) -> Result<Resume, HttpError> {
+    check_storage_quota(app_state, user_id, data.len()).await?;
+
+    let stored = store_upload(file_name, data).await?;
+    if !stored.created {
+        tracing::debug!("{} has the same content as {}, reusing it", file_name, stored.file_path);
+    }
+
+    let mut resume = app_state
+        .db_client
+        .save_resume(
+            user_id,
+            &NewResumeParams {
+                file_path: stored.file_path.clone(),
+                file_name: file_name.to_string(),
+                content_hash: stored.content_hash,
+                file_size: data.len() as i64,
+                document_id,
+                batch_id,
+            },
+            document,
+        )
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+    restore_if_missing(&stored.file_path, data).await?;
+
+    if let Some(document) = document {
+        record_ats_report(app_state, &mut resume, document).await?;
+    }
+
+    Ok(resume)
+}
+
+/// Creates a resume from a JSON Resume document. The document is stored as submitted and its
+/// rendered text is analyzed like an uploaded file's.
+pub async fn import_json_resume(
+    Query(query_params): Query<UploadResumeQueryDto>,
+    Query(view): Query<ResumeViewQueryDto>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+    Json(body): Json<Value>,
+) -> Result<impl IntoResponse, HttpError> {
+    let user_id = &user.user.id;
+
+    let json_resume = JsonResume::parse(body.clone()).map_err(HttpError::bad_request)?;
+
+    check_analysis_quota(&app_state, *user_id).await?;
+
+    if let Some(document_id) = query_params.document_id {
+        app_state
+            .db_client
+            .get_document(*user_id, document_id)
+            .await
+            .map_err(|e| HttpError::server_error(e.to_string()))?
+            .ok_or_else(|| HttpError::not_found(ErrorMessage::DocumentNotFound.to_string()))?;
+    }
+
+    let file_name = json_resume.file_name();
+    let data = serde_json::to_vec_pretty(&body).map_err(|e| HttpError::server_error(e.to_string()))?;
+    let document = extract_document(file_name.clone(), Bytes::from(data.clone()))
+        .await
+        .map_err(|e| HttpError::bad_request(e.to_string()))?;
+
+    let mut resume = save_uploaded_resume(
+        &app_state,
+        *user_id,
+        &file_name,
+        &data,
+        Some(&document),
+        query_params.document_id,
+        None,
+    )
+    .await?;
+
+    analyze_upload(&app_state, &mut resume, query_params.force_analysis.unwrap_or(false)).await?;
+
+    let resume = if is_blind_review(&app_state, &user.user, view.blind) { redact_resume(&resume) } else { resume };
+    let tags = resume_tags(&app_state, *user_id, &[resume.id]).await?;
+
+    let response = ResumeResponseDto {
+        status: "success".to_string(),
+        data: ResumeData {
+            resume: FilterResumeDto::filter_resume(&resume),
+            tags: FilterResumeTagDto::filter_tags_for(&tags, resume.id),
+        }
+    };
+    Ok(Json(response))
+}
+
+/// Creates a resume from a LinkedIn data export or a Europass/HR-XML document. These are already
+/// structured, so they are read straight into an analysis without the NLP service and don't count
+/// against the daily analysis quota.
+pub async fn import_resume(
+    Query(query_params): Query<UploadResumeQueryDto>,
+    Query(view): Query<ResumeViewQueryDto>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+    mut multipart: Multipart,
+) -> Result<impl IntoResponse, HttpError> {
+    let user_id = &user.user.id;
+
+    if let Some(document_id) = query_params.document_id {
+        app_state
+            .db_client
+            .get_document(*user_id, document_id)
+            .await
+            .map_err(|e| HttpError::server_error(e.to_string()))?
+            .ok_or_else(|| HttpError::not_found(ErrorMessage::DocumentNotFound.to_string()))?;
+    }
+
+    let field = multipart
+        .next_field()
+        .await
+        .map_err(|e| HttpError::bad_request(e.to_string()))?
+        .ok_or_else(|| HttpError::bad_request("No file was uploaded".to_string()))?;
+    let file_name = field
+        .file_name()
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+    let data: Bytes = field
+        .bytes()
+        .await
+        .map_err(|e| HttpError::bad_request(e.to_string()))?;
+
+    let started_at = Utc::now();
+    let timer = std::time::Instant::now();
+
+    let imported = match DocumentFormat::detect(&file_name, &data) {
+        Some(DocumentFormat::LinkedIn) => import_linkedin(&data),
+        Some(DocumentFormat::Xml) => import_xml(&data),
+        _ => Err("Expected a LinkedIn data export ZIP or a Europass/HR-XML document".to_string()),
+    }
+    .map_err(HttpError::bad_request)?;
+
+    let document = extract_document(file_name.clone(), data.clone())
+        .await
+        .map_err(|e| HttpError::bad_request(e.to_string()))?;
+
+    let mut resume = save_uploaded_resume(
+        &app_state,
+        *user_id,
+        &file_name,
+        &data,
+        Some(&document),
+        query_params.document_id,
+        None,
+    )
+    .await?;
+
+    let mut result = imported.analysis;
+    result.skills = app_state.taxonomy.get().normalize(&result.skills);
+
+    let analysis = app_state
+        .db_client
+        .save_analysis(
+            resume.id,
+            &result,
+            imported.source.model(),
+            IMPORT_MODEL_VERSION,
+            started_at,
+            timer.elapsed().as_millis() as i64,
+        )
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+    resume.latest_analysis_id = Some(analysis.id);
+    resume.analysis_result = Some(analysis.analysis_result);
+    resume.analysis_schema_version = analysis.schema_version;
+    resume.analysis_status = AnalysisStatus::Completed;
+
+    // Similarity search backfills missing embeddings, so a failure here isn't fatal
+    if let Err(e) = embed_resumes(&app_state, std::slice::from_ref(&resume)).await {
+        tracing::warn!("Could not embed resume {}: {}", resume.id, e);
+    }
+
+    let resume = if is_blind_review(&app_state, &user.user, view.blind) { redact_resume(&resume) } else { resume };
+    let tags = resume_tags(&app_state, *user_id, &[resume.id]).await?;
+
+    let response = ResumeResponseDto {
+        status: "success".to_string(),
+        data: ResumeData {
+            resume: FilterResumeDto::filter_resume(&resume),
+            tags: FilterResumeTagDto::filter_tags_for(&tags, resume.id),
+        }
+    };
+    Ok(Json(response))
+}
+
+/// Creates one resume per file, unpacking ZIP archives, and leaves the analysis to the pending
+/// worker so the request returns once everything is stored. Files that can't be read as a
+/// resume fail individually instead of failing the batch.
+pub async fn bulk_upload_resumes(
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+    mut multipart: Multipart,
+) -> Result<impl IntoResponse, HttpError> {
+    let user_id = user.user.id;
+    let limits = ArchiveLimits::from_config(&app_state.env);
+
+    // Files are analyzed as the quota allows, but a batch isn't taken when none are left today
+    check_analysis_quota(&app_state, user_id).await?;
+
+    let mut uploads: Vec<(String, Bytes)> = Vec::new();
+    let mut received = 0;
+
+    while let Some(mut field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| HttpError::bad_request(e.to_string()))?
+    {
+        let file_name = field
+            .file_name()
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+        let mut data = BytesMut::new();
+        while let Some(chunk) = field
+            .chunk()
+            .await
+            .map_err(|e| HttpError::bad_request(e.to_string()))?
+        {
+            received += chunk.len();
+            if received > app_state.env.bulk_max_upload_bytes {
+                return Err(HttpError::new(
+                    format!("Upload is larger than {} bytes", app_state.env.bulk_max_upload_bytes),
+                    StatusCode::PAYLOAD_TOO_LARGE,
+                ));
+            }
+            data.extend_from_slice(&chunk);
         }
 
-        let resume = saved_resume.ok_or_else(|| HttpError::bad_request("No file was uploaded".to_string()))?;
-        
-        let response = ResumeResponseDto {
-            status: "success".to_string(),
-            data: ResumeData {
-                resume: FilterResumeDto::filter_resume(&resume)
+        uploads.push((file_name, data.freeze()));
+    }
+
+    if uploads.is_empty() {
+        return Err(HttpError::bad_request("No file was uploaded".to_string()));
+    }
+
+    let mut files = Vec::new();
+    let mut budget = limits.max_extracted_bytes;
+    for (file_name, data) in uploads {
+        if is_archive(&file_name, &data) {
+            match unpack_archive(&file_name, &data, &limits, &mut budget) {
+                Ok(entries) => files.extend(entries),
+                Err(e) => files.push(BulkFile { file_name, archive: None, data: Err(e) }),
             }
+        } else if data.len() as u64 > limits.max_file_bytes {
+            files.push(BulkFile {
+                file_name,
+                archive: None,
+                data: Err(format!("File is larger than {} bytes", limits.max_file_bytes)),
+            });
+        } else {
+            files.push(BulkFile { file_name, archive: None, data: Ok(data) });
+        }
+    }
+
+    let batch = app_state
+        .db_client
+        .save_batch(user_id)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    let mut results = Vec::with_capacity(files.len());
+    for (index, file) in files.into_iter().enumerate() {
+        let outcome = match file.data {
+            _ if index >= limits.max_files => Err(format!("Only {} files can be uploaded at once", limits.max_files)),
+            Err(e) => Err(e),
+            Ok(data) => match extract_document(file.file_name.clone(), data.clone()).await {
+                Err(e) => Err(e.to_string()),
+                Ok(document) => save_uploaded_resume(
+                    &app_state,
+                    user_id,
+                    &file.file_name,
+                    &data,
+                    Some(&document),
+                    None,
+                    Some(batch.id),
+                )
+                .await
+                .map(|resume| resume.id)
+                .map_err(|e| e.message),
+            },
         };
 
-        Ok(Json(response))
+        results.push(match outcome {
+            Ok(resume_id) => BatchFileResult {
+                file_name: file.file_name,
+                archive: file.archive,
+                status: BatchFileStatus::Created,
+                resume_id: Some(resume_id),
+                error: None,
+            },
+            Err(error) => BatchFileResult {
+                file_name: file.file_name,
+                archive: file.archive,
+                status: BatchFileStatus::Failed,
+                resume_id: None,
+                error: Some(error),
+            },
+        });
+    }
+
+    let batch = app_state
+        .db_client
+        .finish_batch(batch.id, &results)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    tracing::info!(
+        "User {} uploaded batch {}: {} files, {} failed",
+        user_id,
+        batch.id,
+        batch.file_count,
+        batch.failed_count
+    );
+    app_state.pending_analyses.notify_one();
+
+    let progress = app_state
+        .db_client
+        .get_batch_progress(batch.id)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    let response = BatchResponseDto {
+        status: "success".to_string(),
+        data: BatchData {
+            batch: FilterBatchDto::filter_batch(&batch, progress),
+        },
+    };
+
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+pub async fn get_batch(
+    Path(batch_id): Path<Uuid>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+) -> Result<impl IntoResponse, HttpError> {
+    let batch = app_state
+        .db_client
+        .get_batch(user.user.id, batch_id)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?
+        .ok_or_else(|| HttpError::not_found(ErrorMessage::BatchNotFound.to_string()))?;
+
+    let progress = app_state
+        .db_client
+        .get_batch_progress(batch.id)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    let response = BatchResponseDto {
+        status: "success".to_string(),
+        data: BatchData {
+            batch: FilterBatchDto::filter_batch(&batch, progress),
+        },
+    };
+    Ok(Json(response))
 }
 
 pub async fn delete_resume(
@@ -88,34 +567,120 @@ pub async fn delete_resume(
     Extension(app_state): Extension<Arc<AppState>>,
     Extension(user): Extension<JWTAuthMiddleware>,
 ) -> Result<impl IntoResponse, HttpError> {
-    let user_id = &user.user.id;
+    // Deleting only moves the resume to the trash, the file stays until it's purged
+    let trashed = app_state
+        .db_client
+        .trash_resume(user.user.id, resume_id)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
 
-    let resume = app_state
+    if !trashed {
+        return Err(HttpError::not_found(ErrorMessage::ResumeNotFound.to_string()));
+    }
+
+    Ok(Json(Response {
+        message: "Resume moved to the trash".to_string(),
+        status: "success",
+    }))
+}
+
+pub async fn get_trash(
+    Query(query_params): Query<RequestQueryDto>,
+    Query(view): Query<ResumeViewQueryDto>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+) -> Result<impl IntoResponse, HttpError> {
+    query_params
+        .validate()
+        .map_err(|e| HttpError::bad_request(e.to_string()))?;
+
+    let page = query_params.page.unwrap_or(1);
+    let limit = query_params.limit.unwrap_or(10);
+    let user_id = user.user.id;
+
+    let mut resumes = app_state
         .db_client
-        .get_resume(Some(*user_id), Some(resume_id))
+        .get_trash(user_id, page as u32, limit)
         .await
         .map_err(|e| HttpError::server_error(e.to_string()))?;
+    if is_blind_review(&app_state, &user.user, view.blind) {
+        resumes = resumes.iter().map(redact_resume).collect();
+    }
 
-    let _result = app_state
+    let total = app_state
         .db_client
-        .delete_resume(Some(*user_id), Some(resume_id))
+        .get_trash_count(user_id)
         .await
         .map_err(|e| HttpError::server_error(e.to_string()))?;
 
-    let file_path = resume.as_ref().unwrap().file_path.clone();
+    let response = TrashListResponseDto {
+        status: "success".to_string(),
+        resumes: resumes
+            .iter()
+            .map(|resume| TrashedResumeDto {
+                resume: FilterResumeDto::filter_resume(resume),
+                purge_at: purge_at(&app_state, resume),
+            })
+            .collect(),
+        results: resumes.len() as i64,
+        total,
+    };
+    Ok(Json(response))
+}
+
+pub async fn restore_resume(
+    Path(resume_id): Path<Uuid>,
+    Query(view): Query<ResumeViewQueryDto>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+) -> Result<impl IntoResponse, HttpError> {
+    let user_id = user.user.id;
+
+    let mut resume = app_state
+        .db_client
+        .restore_resume(user_id, resume_id)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?
+        .ok_or_else(|| HttpError::not_found(ErrorMessage::ResumeNotFound.to_string()))?;
+
+    if is_blind_review(&app_state, &user.user, view.blind) {
+        resume = redact_resume(&resume);
+    }
+
+    let tags = resume_tags(&app_state, user_id, &[resume.id]).await?;
+
+    let response = ResumeResponseDto {
+        status: "success".to_string(),
+        data: ResumeData {
+            resume: FilterResumeDto::filter_resume(&resume),
+            tags: FilterResumeTagDto::filter_tags_for(&tags, resume.id),
+        },
+    };
+    Ok(Json(response))
+}
+
+pub async fn purge_trashed_resume(
+    Path(resume_id): Path<Uuid>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+) -> Result<impl IntoResponse, HttpError> {
+    let purged = purge_resume(&app_state, Some(user.user.id), resume_id)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
 
-    if let Err(e) = fs::remove_file(&file_path).await {
-        println!("Warning: Could not delete file {}: {}", &file_path, e);
+    if !purged {
+        return Err(HttpError::not_found(ErrorMessage::ResumeNotFound.to_string()));
     }
 
     Ok(Json(Response {
-        message: "Resume deleted successfully".to_string(),
+        message: "Resume deleted permanently".to_string(),
         status: "success",
     }))
 }
 
 pub async fn get_resume(
     Path(resume_id): Path<Uuid>,
+    Query(view): Query<ResumeViewQueryDto>,
     Extension(app_state): Extension<Arc<AppState>>,
     Extension(user): Extension<JWTAuthMiddleware>,
 ) -> Result<impl IntoResponse, HttpError> {
@@ -127,17 +692,60 @@ pub async fn get_resume(
         .await
         .map_err(|e| HttpError::server_error(e.to_string()))?;
 
-    let filtered_resume = FilterResumeDto::filter_resume(resume.as_ref().unwrap());
+    let mut resume = resume.ok_or_else(|| HttpError::not_found(ErrorMessage::ResumeNotFound.to_string()))?;
+
+    backfill_ats_report(&app_state, &mut resume).await;
+
+    if is_blind_review(&app_state, &user.user, view.blind) {
+        resume = redact_resume(&resume);
+    }
+
+    let filtered_resume = FilterResumeDto::filter_resume(&resume);
+    let tags = resume_tags(&app_state, *user_id, &[resume.id]).await?;
 
     let response = ResumeResponseDto {
         status: "success".to_string(),
-        data: ResumeData {resume: filtered_resume}
+        data: ResumeData {
+            resume: filtered_resume,
+            tags: FilterResumeTagDto::filter_tags_for(&tags, resume.id),
+        }
+    };
+    Ok(Json(response))
+}
+
+pub async fn get_resume_text(
+    Path(resume_id): Path<Uuid>,
+    Query(view): Query<ResumeViewQueryDto>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+) -> Result<impl IntoResponse, HttpError> {
+    let user_id = &user.user.id;
+
+    let resume = app_state
+        .db_client
+        .get_resume(Some(*user_id), Some(resume_id))
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?
+        .ok_or_else(|| HttpError::not_found(ErrorMessage::ResumeNotFound.to_string()))?;
+    let resume = if is_blind_review(&app_state, &user.user, view.blind) { redact_resume(&resume) } else { resume };
+
+    let response = ResumeTextResponseDto {
+        status: "success".to_string(),
+        data: ResumeTextData {
+            id: resume.id.to_string(),
+            document_format: resume.document_format,
+            text: resume.extracted_text,
+            page_count: resume.page_count,
+            word_count: resume.word_count,
+            language: resume.language,
+        },
     };
     Ok(Json(response))
 }
 
 pub async fn get_resumes(
-    Query(query_params): Query<RequestQueryDto>,
+    Query(query_params): Query<ResumeListQueryDto>,
+    Query(view): Query<ResumeViewQueryDto>,
     Extension(app_state): Extension<Arc<AppState>>,
     Extension(user): Extension<JWTAuthMiddleware>,
 ) -> Result<impl IntoResponse, HttpError> {
@@ -149,17 +757,1081 @@ pub async fn get_resumes(
     let limit = query_params.limit.unwrap_or(10);
 
     let user_id = &user.user.id;
+    let blind = is_blind_review(&app_state, &user.user, view.blind);
+
+    let tag_names = tag_filter(query_params.tags.as_deref());
 
-    let resumes = app_state
+    let documents = app_state
         .db_client
-        .get_resumes(*user_id, page as u32, limit)
+        .get_documents(*user_id, page as u32, limit, &tag_names)
         .await
         .map_err(|e| HttpError::server_error(e.to_string()))?;
 
-    let response = ResumeListResponseDto {
+    let current_ids: Vec<Uuid> = documents.iter().filter_map(|document| document.current_resume_id).collect();
+    let tags = resume_tags(&app_state, *user_id, &current_ids).await?;
+    let current: HashMap<Uuid, Resume> = app_state
+        .db_client
+        .get_resumes_by_ids(*user_id, &current_ids)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?
+        .into_iter()
+        .map(|resume| (resume.id, if blind { redact_resume(&resume) } else { resume }))
+        .collect();
+
+    let response = ResumeDocumentListResponseDto {
         status: "success".to_string(),
-        resumes: FilterResumeDto::filter_resumes(&resumes),
-        results: resumes.len() as i64,
+        documents: documents
+            .iter()
+            .map(|document| {
+                let resume = document.current_resume_id.and_then(|id| current.get(&id));
+                if blind {
+                    FilterResumeDocumentDto::filter_document(&redact_document(document), resume, &tags)
+                } else {
+                    FilterResumeDocumentDto::filter_document(document, resume, &tags)
+                }
+            })
+            .collect(),
+        results: documents.len() as i64,
     };
     Ok(Json(response))
-} 
\ No newline at end of file
+}
+
+/// The resume's analysis as a downloadable PDF report.
+pub async fn get_resume_report(
+    Path(resume_id): Path<Uuid>,
+    Query(view): Query<ResumeViewQueryDto>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+) -> Result<impl IntoResponse, HttpError> {
+    let user_id = &user.user.id;
+    let mut resume = owned_resume(&app_state, *user_id, resume_id).await?;
+
+    backfill_ats_report(&app_state, &mut resume).await;
+
+    if is_blind_review(&app_state, &user.user, view.blind) {
+        resume = redact_resume(&resume);
+    }
+
+    let tags = resume_tags(&app_state, *user_id, &[resume.id]).await?;
+    let pdf = render_analysis_pdf(&resume, &tags)
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    let headers = [
+        (header::CONTENT_TYPE, "application/pdf".to_string()),
+        (header::CONTENT_DISPOSITION, format!("attachment; filename=\"resume-{}.pdf\"", resume.id)),
+    ];
+    Ok((headers, pdf))
+}
+
+/// The resume's analysis mapped onto JSON Resume, so users can take their parsed data elsewhere.
+pub async fn export_json_resume(
+    Path(resume_id): Path<Uuid>,
+    Query(view): Query<ResumeViewQueryDto>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+) -> Result<impl IntoResponse, HttpError> {
+    let mut resume = owned_resume(&app_state, user.user.id, resume_id).await?;
+
+    if is_blind_review(&app_state, &user.user, view.blind) {
+        resume = redact_resume(&resume);
+    }
+
+    let analysis = resume
+        .analysis()
+        .ok_or_else(|| HttpError::new(ErrorMessage::ResumeNotAnalyzed.to_string(), StatusCode::CONFLICT))?;
+
+    let mut json_resume = JsonResume::from_analysis(&analysis);
+    if let Some(meta) = json_resume.meta.as_mut() {
+        meta.last_modified = resume.uploaded_at.map(|at| at.to_rfc3339());
+    }
+
+    let headers = [(header::CONTENT_DISPOSITION, format!("attachment; filename=\"resume-{}.json\"", resume.id))];
+    Ok((headers, Json(json_resume.to_value())))
+}
+
+/// Streams the current version of every document as CSV or NDJSON, filtered by tag like the list.
+pub async fn export_resume_list(
+    Query(query_params): Query<ResumeExportQueryDto>,
+    Query(view): Query<ResumeViewQueryDto>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+) -> Result<impl IntoResponse, HttpError> {
+    let format = query_params.format.unwrap_or(ExportFormat::Csv);
+    let tag_names = tag_filter(query_params.tags.as_deref());
+    let blind = is_blind_review(&app_state, &user.user, view.blind);
+
+    let stages: HashMap<Uuid, String> = app_state
+        .db_client
+        .get_stages()
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?
+        .into_iter()
+        .map(|stage| (stage.id, stage.name))
+        .collect();
+
+    let headers = [
+        (header::CONTENT_TYPE, format.content_type().to_string()),
+        (
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"resumes-{}.{}\"", Utc::now().format("%Y%m%d"), format.extension()),
+        ),
+    ];
+    let body = Body::from_stream(export_resumes(app_state.clone(), user.user.id, tag_names, stages, blind, format));
+    Ok((headers, body))
+}
+
+pub async fn analyze_resume(
+    Path(resume_id): Path<Uuid>,
+    Query(query_params): Query<AnalyzeQueryDto>,
+    Query(view): Query<ResumeViewQueryDto>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+) -> Result<impl IntoResponse, HttpError> {
+    query_params
+        .validate()
+        .map_err(|e| HttpError::bad_request(e.to_string()))?;
+
+    let user_id = &user.user.id;
+
+    let resume = app_state
+        .db_client
+        .get_resume(Some(*user_id), Some(resume_id))
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?
+        .ok_or_else(|| HttpError::not_found(ErrorMessage::ResumeNotFound.to_string()))?;
+
+    let model = query_params.model.unwrap_or(app_state.env.nlp_model.clone());
+    let model_version = query_params
+        .model_version
+        .unwrap_or(app_state.env.nlp_model_version.clone());
+
+    let force = query_params.force.unwrap_or(false);
+
+    let mut analysis = run_analysis(&app_state, &resume, &model, &model_version, force, true).await?;
+    if is_blind_review(&app_state, &user.user, view.blind) {
+        analysis = redact_resume_analysis(&resume, &analysis);
+    }
+
+    let response = AnalysisResponseDto {
+        status: "success".to_string(),
+        data: AnalysisData {
+            analysis: FilterAnalysisDto::filter_analysis(&analysis),
+        },
+    };
+
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+pub async fn get_resume_analyses(
+    Path(resume_id): Path<Uuid>,
+    Query(query_params): Query<RequestQueryDto>,
+    Query(view): Query<ResumeViewQueryDto>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+) -> Result<impl IntoResponse, HttpError> {
+    query_params
+        .validate()
+        .map_err(|e| HttpError::bad_request(e.to_string()))?;
+
+    let page = query_params.page.unwrap_or(1);
+    let limit = query_params.limit.unwrap_or(10);
+
+    let user_id = &user.user.id;
+
+    let resume = app_state
+        .db_client
+        .get_resume(Some(*user_id), Some(resume_id))
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?
+        .ok_or_else(|| HttpError::not_found(ErrorMessage::ResumeNotFound.to_string()))?;
+
+    let mut analyses = app_state
+        .db_client
+        .get_analyses(resume_id, page as u32, limit)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+    if is_blind_review(&app_state, &user.user, view.blind) {
+        analyses = analyses
+            .iter()
+            .map(|analysis| redact_resume_analysis(&resume, analysis))
+            .collect();
+    }
+
+    let analysis_count = app_state
+        .db_client
+        .get_analysis_count(resume_id)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    let response = AnalysisListResponseDto {
+        status: "success".to_string(),
+        analyses: FilterAnalysisDto::filter_analyses(&analyses),
+        results: analysis_count,
+    };
+    Ok(Json(response))
+}
+
+pub async fn reanalyze_resumes(
+    Extension(app_state): Extension<Arc<AppState>>,
+    Json(body): Json<ReanalyzeRequestDto>,
+) -> Result<impl IntoResponse, HttpError> {
+    body.validate()
+        .map_err(|e| HttpError::bad_request(e.to_string()))?;
+
+    let model = body.model;
+    let model_version = body
+        .model_version
+        .unwrap_or(app_state.env.nlp_model_version.clone());
+    let force = body.force.unwrap_or(false);
+
+    let message = format!(
+        "Re-analysis of all resumes started with model {} ({})",
+        model, model_version
+    );
+
+    tokio::spawn(reanalyze_all(app_state, model, model_version, force));
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(Response {
+            status: "success",
+            message,
+        }),
+    ))
+}
+
+/// Turns free text into a `to_tsquery` expression matching every word as a prefix.
+fn prefix_tsquery(query: &str) -> String {
+    query
+        .split_whitespace()
+        .map(|word| word.chars().filter(|c| c.is_alphanumeric()).collect::<String>())
+        .filter(|word| !word.is_empty())
+        .map(|word| format!("{}:*", word))
+        .collect::<Vec<_>>()
+        .join(" & ")
+}
+
+pub async fn search_resumes(
+    Query(query_params): Query<ResumeSearchQueryDto>,
+    Query(view): Query<ResumeViewQueryDto>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+) -> Result<impl IntoResponse, HttpError> {
+    query_params
+        .validate()
+        .map_err(|e| HttpError::bad_request(e.to_string()))?;
+
+    let mode = query_params.mode.unwrap_or_default();
+    let query = match mode {
+        SearchMode::Prefix => prefix_tsquery(&query_params.q),
+        SearchMode::Web | SearchMode::Phrase => query_params.q.clone(),
+    };
+    if query.trim().is_empty() {
+        return Err(HttpError::bad_request("Search query has no searchable words"));
+    }
+
+    let skills = query_params
+        .skills
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .map(|skill| skill.trim().to_string())
+        .filter(|skill| !skill.is_empty())
+        .collect();
+
+    // `to` is inclusive, so search up to the start of the following day
+    let params = ResumeSearchParams {
+        mode,
+        query,
+        skills,
+        uploaded_from: query_params
+            .from
+            .map(|date| date.and_hms_opt(0, 0, 0).unwrap().and_utc()),
+        uploaded_to: query_params
+            .to
+            .and_then(|date| date.succ_opt())
+            .map(|date| date.and_hms_opt(0, 0, 0).unwrap().and_utc()),
+        page: query_params.page.unwrap_or(1) as u32,
+        limit: query_params.limit.unwrap_or(10),
+    };
+
+    let user_id = &user.user.id;
+
+    let hits = app_state
+        .db_client
+        .search_resumes(*user_id, &params)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    let resume_ids: Vec<Uuid> = hits.iter().map(|hit| hit.id).collect();
+    let mut resumes: HashMap<Uuid, _> = app_state
+        .db_client
+        .get_resumes_by_ids(*user_id, &resume_ids)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?
+        .into_iter()
+        .map(|resume| (resume.id, resume))
+        .collect();
+
+    let blind = is_blind_review(&app_state, &user.user, view.blind);
+    let total = hits.first().map(|hit| hit.total).unwrap_or(0);
+    let hits = hits
+        .into_iter()
+        .filter_map(|hit| {
+            resumes.remove(&hit.id).map(|resume| {
+                if blind {
+                    ResumeSearchHitDto {
+                        snippet: Redactor::from_resume(&resume).redact(&hit.snippet),
+                        resume: FilterResumeDto::filter_resume(&redact_resume(&resume)),
+                        rank: hit.rank,
+                    }
+                } else {
+                    ResumeSearchHitDto {
+                        resume: FilterResumeDto::filter_resume(&resume),
+                        rank: hit.rank,
+                        snippet: hit.snippet,
+                    }
+                }
+            })
+        })
+        .collect();
+
+    let response = ResumeSearchResponseDto {
+        status: "success".to_string(),
+        hits,
+        results: total,
+    };
+    Ok(Json(response))
+}
+
+async fn similar_resumes_response(
+    app_state: &AppState,
+    user_id: Uuid,
+    scored: Vec<(Uuid, f32)>,
+    blind: bool,
+) -> Result<SimilarResumesResponseDto, HttpError> {
+    let resume_ids: Vec<Uuid> = scored.iter().map(|(id, _)| *id).collect();
+    let mut resumes: HashMap<Uuid, _> = app_state
+        .db_client
+        .get_resumes_by_ids(user_id, &resume_ids)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?
+        .into_iter()
+        .map(|resume| (resume.id, resume))
+        .collect();
+
+    let resumes: Vec<SimilarResumeDto> = scored
+        .into_iter()
+        .filter_map(|(id, score)| {
+            resumes.remove(&id).map(|resume| SimilarResumeDto {
+                resume: if blind {
+                    FilterResumeDto::filter_resume(&redact_resume(&resume))
+                } else {
+                    FilterResumeDto::filter_resume(&resume)
+                },
+                score,
+            })
+        })
+        .collect();
+
+    Ok(SimilarResumesResponseDto {
+        status: "success".to_string(),
+        results: resumes.len() as i64,
+        resumes,
+    })
+}
+
+pub async fn get_similar_resumes(
+    Path(resume_id): Path<Uuid>,
+    Query(query_params): Query<SimilarResumesQueryDto>,
+    Query(view): Query<ResumeViewQueryDto>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+) -> Result<impl IntoResponse, HttpError> {
+    query_params
+        .validate()
+        .map_err(|e| HttpError::bad_request(e.to_string()))?;
+
+    let user_id = &user.user.id;
+
+    let resume = app_state
+        .db_client
+        .get_resume(Some(*user_id), Some(resume_id))
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?
+        .ok_or_else(|| HttpError::not_found(ErrorMessage::ResumeNotFound.to_string()))?;
+
+    let embedding = get_resume_embedding(&app_state, &resume).await?;
+
+    let scored = find_similar(
+        &app_state,
+        *user_id,
+        &embedding,
+        Some(resume.id),
+        query_params.limit.unwrap_or(10),
+        query_params.min_score.unwrap_or(0.0),
+    )
+    .await?;
+
+    let blind = is_blind_review(&app_state, &user.user, view.blind);
+    let response = similar_resumes_response(&app_state, *user_id, scored, blind).await?;
+    Ok(Json(response))
+}
+
+pub async fn find_similar_resumes(
+    Query(view): Query<ResumeViewQueryDto>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+    Json(body): Json<SimilarTextRequestDto>,
+) -> Result<impl IntoResponse, HttpError> {
+    body.validate()
+        .map_err(|e| HttpError::bad_request(e.to_string()))?;
+
+    let user_id = &user.user.id;
+
+    let embedding = embed_query(&app_state, &body.text).await?;
+
+    let scored = find_similar(
+        &app_state,
+        *user_id,
+        &embedding,
+        None,
+        body.limit.unwrap_or(10),
+        body.min_score.unwrap_or(0.0),
+    )
+    .await?;
+
+    let blind = is_blind_review(&app_state, &user.user, view.blind);
+    let response = similar_resumes_response(&app_state, *user_id, scored, blind).await?;
+    Ok(Json(response))
+}
+
+pub async fn match_resume_to_job(
+    Path((resume_id, job_id)): Path<(Uuid, Uuid)>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+) -> Result<impl IntoResponse, HttpError> {
+    let user_id = &user.user.id;
+
+    let resume = app_state
+        .db_client
+        .get_resume(Some(*user_id), Some(resume_id))
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?
+        .ok_or_else(|| HttpError::not_found(ErrorMessage::ResumeNotFound.to_string()))?;
+
+    let job = app_state
+        .db_client
+        .get_job(*user_id, job_id)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?
+        .ok_or_else(|| HttpError::not_found(ErrorMessage::JobNotFound.to_string()))?;
+
+    let response = MatchResponseDto {
+        status: "success".to_string(),
+        data: MatchData {
+            resume_id: resume.id.to_string(),
+            job_id: job.id.to_string(),
+            report: match_resume(&resume, &job, app_state.taxonomy.get()),
+        },
+    };
+    Ok(Json(response))
+}
+
+/// Diffs `other_id` against `resume_id`, treating the first as the earlier version.
+pub async fn compare_resumes(
+    Path((resume_id, other_id)): Path<(Uuid, Uuid)>,
+    Query(view): Query<ResumeViewQueryDto>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+) -> Result<impl IntoResponse, HttpError> {
+    if resume_id == other_id {
+        return Err(HttpError::bad_request("Choose two different resumes to compare"));
+    }
+
+    let user_id = &user.user.id;
+
+    let mut resumes = Vec::with_capacity(2);
+    for id in [resume_id, other_id] {
+        let resume = app_state
+            .db_client
+            .get_resume(Some(*user_id), Some(id))
+            .await
+            .map_err(|e| HttpError::server_error(e.to_string()))?
+            .ok_or_else(|| HttpError::not_found(ErrorMessage::ResumeNotFound.to_string()))?;
+        resumes.push(resume);
+    }
+    // Diffing the anonymized texts keeps personal details out of the changed lines too
+    if is_blind_review(&app_state, &user.user, view.blind) {
+        resumes = resumes.iter().map(redact_resume).collect();
+    }
+    let target = resumes.pop().unwrap();
+    let base = resumes.pop().unwrap();
+
+    let diff = diff_resumes(&base, &target, &app_state.taxonomy.get());
+
+    let response = ResumeComparisonResponseDto {
+        status: "success".to_string(),
+        data: ResumeComparisonData {
+            base: FilterResumeDto::filter_resume(&base),
+            target: FilterResumeDto::filter_resume(&target),
+            diff,
+        },
+    };
+    Ok(Json(response))
+}
+
+async fn resume_document_response(
+    app_state: &AppState,
+    user_id: Uuid,
+    document_id: Uuid,
+    blind: bool,
+) -> Result<ResumeDocumentResponseDto, HttpError> {
+    let document = app_state
+        .db_client
+        .get_document(user_id, document_id)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?
+        .ok_or_else(|| HttpError::not_found(ErrorMessage::DocumentNotFound.to_string()))?;
+
+    let versions = app_state
+        .db_client
+        .get_document_versions(user_id, document_id)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    let (document, versions) = if blind {
+        (redact_document(&document), versions.iter().map(redact_resume).collect())
+    } else {
+        (document, versions)
+    };
+
+    let current = versions.iter().find(|resume| Some(resume.id) == document.current_resume_id);
+    let tags = resume_tags(app_state, user_id, &current.map(|resume| resume.id).into_iter().collect::<Vec<_>>()).await?;
+
+    Ok(ResumeDocumentResponseDto {
+        status: "success".to_string(),
+        data: ResumeDocumentData {
+            document: FilterResumeDocumentDto::filter_document(&document, current, &tags),
+            versions: FilterResumeDto::filter_resumes(&versions),
+        },
+    })
+}
+
+pub async fn get_resume_document(
+    Path(document_id): Path<Uuid>,
+    Query(view): Query<ResumeViewQueryDto>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+) -> Result<impl IntoResponse, HttpError> {
+    let blind = is_blind_review(&app_state, &user.user, view.blind);
+    let response = resume_document_response(&app_state, user.user.id, document_id, blind).await?;
+    Ok(Json(response))
+}
+
+pub async fn set_current_version(
+    Path(document_id): Path<Uuid>,
+    Query(view): Query<ResumeViewQueryDto>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+    Json(body): Json<SetCurrentVersionDto>,
+) -> Result<impl IntoResponse, HttpError> {
+    let user_id = user.user.id;
+
+    let updated = app_state
+        .db_client
+        .set_current_version(user_id, document_id, body.resume_id)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    if !updated {
+        return Err(HttpError::not_found(ErrorMessage::ResumeNotFound.to_string()));
+    }
+
+    let blind = is_blind_review(&app_state, &user.user, view.blind);
+    let response = resume_document_response(&app_state, user_id, document_id, blind).await?;
+    Ok(Json(response))
+}
+
+/// Lowercased, distinct tag names from a comma separated `tags` filter.
+fn tag_filter(tags: Option<&str>) -> Vec<String> {
+    let mut names: Vec<String> = tags
+        .unwrap_or_default()
+        .split(',')
+        .map(|name| name.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase())
+        .filter(|name| !name.is_empty())
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+async fn resume_tags(app_state: &AppState, user_id: Uuid, resume_ids: &[Uuid]) -> Result<Vec<ResumeTag>, HttpError> {
+    if resume_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    app_state
+        .db_client
+        .get_tags_for_resumes(user_id, resume_ids)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))
+}
+
+async fn owned_resume(app_state: &AppState, user_id: Uuid, resume_id: Uuid) -> Result<Resume, HttpError> {
+    app_state
+        .db_client
+        .get_resume(Some(user_id), Some(resume_id))
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?
+        .ok_or_else(|| HttpError::not_found(ErrorMessage::ResumeNotFound.to_string()))
+}
+
+async fn resume_tag_list(app_state: &AppState, user_id: Uuid, resume_id: Uuid) -> Result<ResumeTagListResponseDto, HttpError> {
+    let tags = resume_tags(app_state, user_id, &[resume_id]).await?;
+
+    Ok(ResumeTagListResponseDto {
+        status: "success".to_string(),
+        results: tags.len() as i64,
+        tags: FilterResumeTagDto::filter_tags_for(&tags, resume_id),
+    })
+}
+
+pub async fn tag_resume(
+    Path((resume_id, tag_id)): Path<(Uuid, Uuid)>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+) -> Result<impl IntoResponse, HttpError> {
+    let user_id = user.user.id;
+
+    owned_resume(&app_state, user_id, resume_id).await?;
+    app_state
+        .db_client
+        .get_tag(user_id, tag_id)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?
+        .ok_or_else(|| HttpError::not_found(ErrorMessage::TagNotFound.to_string()))?;
+
+    app_state
+        .db_client
+        .tag_resumes(user_id, &[resume_id], &[tag_id])
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    Ok(Json(resume_tag_list(&app_state, user_id, resume_id).await?))
+}
+
+pub async fn untag_resume(
+    Path((resume_id, tag_id)): Path<(Uuid, Uuid)>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+) -> Result<impl IntoResponse, HttpError> {
+    let user_id = user.user.id;
+
+    owned_resume(&app_state, user_id, resume_id).await?;
+
+    let removed = app_state
+        .db_client
+        .untag_resumes(user_id, &[resume_id], &[tag_id])
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    if removed == 0 {
+        return Err(HttpError::not_found(ErrorMessage::TagNotFound.to_string()));
+    }
+
+    Ok(Json(resume_tag_list(&app_state, user_id, resume_id).await?))
+}
+
+pub async fn get_resume_notes(
+    Path(resume_id): Path<Uuid>,
+    Query(view): Query<ResumeViewQueryDto>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+) -> Result<impl IntoResponse, HttpError> {
+    let resume = owned_resume(&app_state, user.user.id, resume_id).await?;
+
+    let mut notes = app_state
+        .db_client
+        .get_notes(resume_id)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    // Notes tend to repeat the candidate's name, so blind reviewers get them redacted too
+    if is_blind_review(&app_state, &user.user, view.blind) {
+        let redactor = Redactor::from_resume(&resume);
+        for note in &mut notes {
+            note.body = redactor.redact(&note.body);
+        }
+    }
+
+    let response = NoteListResponseDto {
+        status: "success".to_string(),
+        notes: FilterNoteDto::filter_notes(&notes),
+        results: notes.len() as i64,
+    };
+    Ok(Json(response))
+}
+
+pub async fn create_resume_note(
+    Path(resume_id): Path<Uuid>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+    Json(body): Json<NoteRequestDto>,
+) -> Result<impl IntoResponse, HttpError> {
+    body.validate()
+        .map_err(|e| HttpError::bad_request(e.to_string()))?;
+
+    owned_resume(&app_state, user.user.id, resume_id).await?;
+
+    let note = app_state
+        .db_client
+        .save_note(resume_id, user.user.id, body.body.trim())
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    let response = NoteResponseDto {
+        status: "success".to_string(),
+        data: NoteData { note: FilterNoteDto::filter_note(&note) },
+    };
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+pub async fn update_resume_note(
+    Path((resume_id, note_id)): Path<(Uuid, Uuid)>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+    Json(body): Json<NoteRequestDto>,
+) -> Result<impl IntoResponse, HttpError> {
+    body.validate()
+        .map_err(|e| HttpError::bad_request(e.to_string()))?;
+
+    owned_resume(&app_state, user.user.id, resume_id).await?;
+
+    // Only the author can edit a note
+    let note = app_state
+        .db_client
+        .update_note(resume_id, note_id, user.user.id, body.body.trim())
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?
+        .ok_or_else(|| HttpError::not_found(ErrorMessage::NoteNotFound.to_string()))?;
+
+    let response = NoteResponseDto {
+        status: "success".to_string(),
+        data: NoteData { note: FilterNoteDto::filter_note(&note) },
+    };
+    Ok(Json(response))
+}
+
+pub async fn delete_resume_note(
+    Path((resume_id, note_id)): Path<(Uuid, Uuid)>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+) -> Result<impl IntoResponse, HttpError> {
+    owned_resume(&app_state, user.user.id, resume_id).await?;
+
+    let deleted = app_state
+        .db_client
+        .delete_note(resume_id, note_id, user.user.id)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    if !deleted {
+        return Err(HttpError::not_found(ErrorMessage::NoteNotFound.to_string()));
+    }
+
+    Ok(Json(Response {
+        message: "Note deleted successfully".to_string(),
+        status: "success",
+    }))
+}
+
+pub async fn move_resume_stage(
+    Path(resume_id): Path<Uuid>,
+    Query(view): Query<ResumeViewQueryDto>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+    Json(body): Json<MoveStageDto>,
+) -> Result<impl IntoResponse, HttpError> {
+    body.validate()
+        .map_err(|e| HttpError::bad_request(e.to_string()))?;
+
+    let user_id = user.user.id;
+
+    let resume = owned_resume(&app_state, user_id, resume_id).await?;
+    if resume.stage_id == body.stage_id {
+        return Err(HttpError::bad_request("Resume is already in this stage"));
+    }
+
+    app_state
+        .db_client
+        .get_stage(body.stage_id)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?
+        .ok_or_else(|| HttpError::not_found(ErrorMessage::StageNotFound.to_string()))?;
+
+    let note = body.note.as_deref().map(str::trim).filter(|note| !note.is_empty());
+    let mut transition = app_state
+        .db_client
+        .move_resume_stage(user_id, resume_id, body.stage_id, note)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?
+        .ok_or_else(|| HttpError::not_found(ErrorMessage::ResumeNotFound.to_string()))?;
+
+    let mut resume = owned_resume(&app_state, user_id, resume_id).await?;
+    if is_blind_review(&app_state, &user.user, view.blind) {
+        let redactor = Redactor::from_resume(&resume);
+        transition.note = transition.note.as_deref().map(|note| redactor.redact(note));
+        resume = redact_resume(&resume);
+    }
+
+    let response = StageTransitionResponseDto {
+        status: "success".to_string(),
+        data: StageTransitionData {
+            transition: FilterStageTransitionDto::filter_transition(&transition),
+            resume: FilterResumeDto::filter_resume(&resume),
+        },
+    };
+    Ok(Json(response))
+}
+
+pub async fn get_resume_timeline(
+    Path(resume_id): Path<Uuid>,
+    Query(view): Query<ResumeViewQueryDto>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+) -> Result<impl IntoResponse, HttpError> {
+    let resume = owned_resume(&app_state, user.user.id, resume_id).await?;
+
+    let mut timeline = app_state
+        .db_client
+        .get_stage_timeline(user.user.id, resume_id)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    if is_blind_review(&app_state, &user.user, view.blind) {
+        let redactor = Redactor::from_resume(&resume);
+        for transition in &mut timeline {
+            transition.note = transition.note.as_deref().map(|note| redactor.redact(note));
+        }
+    }
+
+    let response = StageTimelineResponseDto {
+        status: "success".to_string(),
+        timeline: FilterStageTransitionDto::filter_transitions(&timeline),
+        results: timeline.len() as i64,
+    };
+    Ok(Json(response))
+}
+
+pub async fn get_pipeline(
+    Query(query_params): Query<PipelineQueryDto>,
+    Query(view): Query<ResumeViewQueryDto>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+) -> Result<impl IntoResponse, HttpError> {
+    query_params
+        .validate()
+        .map_err(|e| HttpError::bad_request(e.to_string()))?;
+
+    let user_id = user.user.id;
+    let limit = query_params.limit.unwrap_or(20);
+    let tag_names = tag_filter(query_params.tags.as_deref());
+    let blind = is_blind_review(&app_state, &user.user, view.blind);
+
+    let stages = app_state
+        .db_client
+        .get_stages()
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    let counts: HashMap<Uuid, i64> = app_state
+        .db_client
+        .get_pipeline_counts(user_id, &tag_names)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?
+        .into_iter()
+        .map(|count| (count.stage_id, count.count))
+        .collect();
+
+    let resume_ids = app_state
+        .db_client
+        .get_pipeline_resume_ids(user_id, limit as i64, &tag_names)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    let mut resumes = if resume_ids.is_empty() {
+        Vec::new()
+    } else {
+        app_state
+            .db_client
+            .get_resumes_by_ids(user_id, &resume_ids)
+            .await
+            .map_err(|e| HttpError::server_error(e.to_string()))?
+    };
+    // Longest waiting first within each stage
+    resumes.sort_by_key(|resume| resume.stage_changed_at);
+
+    let tags = resume_tags(&app_state, user_id, &resume_ids).await?;
+
+    let columns = stages
+        .iter()
+        .map(|stage| PipelineColumnDto {
+            stage: FilterStageDto::filter_stage(stage),
+            count: counts.get(&stage.id).copied().unwrap_or(0),
+            resumes: resumes
+                .iter()
+                .filter(|resume| resume.stage_id == stage.id)
+                .map(|resume| PipelineCardDto {
+                    resume: if blind {
+                        FilterResumeDto::filter_resume(&redact_resume(resume))
+                    } else {
+                        FilterResumeDto::filter_resume(resume)
+                    },
+                    tags: FilterResumeTagDto::filter_tags_for(&tags, resume.id),
+                })
+                .collect(),
+        })
+        .collect();
+
+    let response = PipelineResponseDto {
+        status: "success".to_string(),
+        stages: columns,
+        results: counts.values().sum(),
+    };
+    Ok(Json(response))
+}
+
+pub async fn create_share_link(
+    Path(resume_id): Path<Uuid>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+    Json(body): Json<CreateShareLinkDto>,
+) -> Result<impl IntoResponse, HttpError> {
+    body.validate()
+        .map_err(|e| HttpError::bad_request(e.to_string()))?;
+
+    let hours = body.expires_in_hours.unwrap_or(app_state.env.share_link_default_hours);
+    if hours > app_state.env.share_link_max_hours {
+        return Err(HttpError::bad_request(format!(
+            "Share links can last at most {} hours",
+            app_state.env.share_link_max_hours
+        )));
+    }
+
+    owned_resume(&app_state, user.user.id, resume_id).await?;
+
+    let password_hash = match body.password.as_deref() {
+        Some(password) => Some(password::hash(password).map_err(|e| HttpError::bad_request(e.to_string()))?),
+        None => None,
+    };
+
+    let token = generate_token();
+    let link = app_state
+        .db_client
+        .save_share_link(
+            user.user.id,
+            resume_id,
+            &NewShareLinkParams {
+                token_hash: token_hash(&token),
+                token_prefix: token[..TOKEN_PREFIX_LEN].to_string(),
+                password_hash,
+                // Roles with enforced blind review can't hand out the full resume
+                redacted: is_blind_review(&app_state, &user.user, body.redacted),
+                expires_at: Utc::now() + Duration::hours(hours),
+                max_views: body.max_views,
+            },
+        )
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    let response = ShareLinkCreatedResponseDto {
+        status: "success".to_string(),
+        data: ShareLinkCreatedData {
+            share: FilterShareLinkDto::filter_share_link(&link),
+            path: format!("/api/share/{}", token),
+            token,
+        },
+    };
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+pub async fn get_share_links(
+    Path(resume_id): Path<Uuid>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+) -> Result<impl IntoResponse, HttpError> {
+    owned_resume(&app_state, user.user.id, resume_id).await?;
+
+    let links = app_state
+        .db_client
+        .get_share_links(user.user.id, resume_id)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    let response = ShareLinkListResponseDto {
+        status: "success".to_string(),
+        shares: FilterShareLinkDto::filter_share_links(&links),
+        results: links.len() as i64,
+    };
+    Ok(Json(response))
+}
+
+pub async fn revoke_share_link(
+    Path(share_id): Path<Uuid>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+) -> Result<impl IntoResponse, HttpError> {
+    let link = app_state
+        .db_client
+        .revoke_share_link(user.user.id, share_id)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?
+        .ok_or_else(|| HttpError::not_found(ErrorMessage::ShareLinkNotFound.to_string()))?;
+
+    let response = ShareLinkResponseDto {
+        status: "success".to_string(),
+        data: ShareLinkData { share: FilterShareLinkDto::filter_share_link(&link) },
+    };
+    Ok(Json(response))
+}
+
+pub async fn get_share_access(
+    Path(share_id): Path<Uuid>,
+    Query(query_params): Query<RequestQueryDto>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+) -> Result<impl IntoResponse, HttpError> {
+    query_params
+        .validate()
+        .map_err(|e| HttpError::bad_request(e.to_string()))?;
+
+    let page = query_params.page.unwrap_or(1);
+    let limit = query_params.limit.unwrap_or(10);
+
+    app_state
+        .db_client
+        .get_share_link(user.user.id, share_id)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?
+        .ok_or_else(|| HttpError::not_found(ErrorMessage::ShareLinkNotFound.to_string()))?;
+
+    let access = app_state
+        .db_client
+        .get_share_access(share_id, page as u32, limit)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    let access_count = app_state
+        .db_client
+        .get_share_access_count(share_id)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    let response = ShareAccessListResponseDto {
+        status: "success".to_string(),
+        access: FilterShareAccessDto::filter_access_log(&access),
+        results: access_count,
+    };
+    Ok(Json(response))
+}
@@ -0,0 +1,152 @@
+use std::sync::Arc;
+
+use axum::{extract::{Path, Query}, middleware::from_fn, response::IntoResponse, routing::{get, post, put}, Extension, Json, Router};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{models::users::UserRole, services::{database::{RetentionActions, UserActions}, middleware::{role_check, JWTAuthMiddleware}, retention::apply_retention}, utils::{dtos::{FilterRetentionCandidateDto, FilterRetentionPolicyDto, Response, RetentionPolicyData, RetentionPolicyDto, RetentionPolicyListResponseDto, RetentionPolicyResponseDto, RetentionReportData, RetentionReportQueryDto, RetentionReportResponseDto, RetentionRunData, RetentionRunResponseDto, RetentionSummaryDto}, error::{ErrorMessage, HttpError}}, AppState};
+
+pub fn retention_routes() -> Router {
+    Router::new()
+        .route("/policies", get(get_policies))
+        .route("/policies/global", put(update_global_policy))
+        .route("/policies/users/{user_id}", put(update_user_policy).delete(delete_user_policy))
+        .route("/report", get(get_report))
+        .route("/run", post(run_retention))
+        .layer(from_fn(|state, req, next| {
+            role_check(state, req, next, vec![UserRole::Admin])
+        }))
+}
+
+pub async fn get_policies(
+    Extension(app_state): Extension<Arc<AppState>>,
+) -> Result<impl IntoResponse, HttpError> {
+    let policies = app_state
+        .db_client
+        .get_retention_policies()
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    let response = RetentionPolicyListResponseDto {
+        status: "success".to_string(),
+        policies: FilterRetentionPolicyDto::filter_policies(&policies),
+        results: policies.len() as i64,
+    };
+    Ok(Json(response))
+}
+
+async fn save_policy(
+    app_state: &AppState,
+    user_id: Option<Uuid>,
+    body: &RetentionPolicyDto,
+    updated_by: Uuid,
+) -> Result<RetentionPolicyResponseDto, HttpError> {
+    body.validate()
+        .map_err(|e| HttpError::bad_request(e.to_string()))?;
+
+    let policy = app_state
+        .db_client
+        .save_retention_policy(user_id, body.retention_months, body.action, updated_by)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    Ok(RetentionPolicyResponseDto {
+        status: "success".to_string(),
+        data: RetentionPolicyData {
+            policy: FilterRetentionPolicyDto::filter_policy(&policy),
+        },
+    })
+}
+
+pub async fn update_global_policy(
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+    Json(body): Json<RetentionPolicyDto>,
+) -> Result<impl IntoResponse, HttpError> {
+    let response = save_policy(&app_state, None, &body, user.user.id).await?;
+    Ok(Json(response))
+}
+
+pub async fn update_user_policy(
+    Path(user_id): Path<Uuid>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+    Json(body): Json<RetentionPolicyDto>,
+) -> Result<impl IntoResponse, HttpError> {
+    app_state
+        .db_client
+        .get_user(Some(user_id), None, None, None)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?
+        .ok_or_else(|| HttpError::not_found(ErrorMessage::UserNotFound.to_string()))?;
+
+    let response = save_policy(&app_state, Some(user_id), &body, user.user.id).await?;
+    Ok(Json(response))
+}
+
+pub async fn delete_user_policy(
+    Path(user_id): Path<Uuid>,
+    Extension(app_state): Extension<Arc<AppState>>,
+) -> Result<impl IntoResponse, HttpError> {
+    let deleted = app_state
+        .db_client
+        .delete_retention_policy(user_id)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    if !deleted {
+        return Err(HttpError::not_found(ErrorMessage::RetentionPolicyNotFound.to_string()));
+    }
+
+    Ok(Json(Response {
+        message: "User retention policy removed, the global policy applies".to_string(),
+        status: "success",
+    }))
+}
+
+/// What the next retention run would delete or anonymize, without changing anything.
+pub async fn get_report(
+    Query(query_params): Query<RetentionReportQueryDto>,
+    Extension(app_state): Extension<Arc<AppState>>,
+) -> Result<impl IntoResponse, HttpError> {
+    query_params
+        .validate()
+        .map_err(|e| HttpError::bad_request(e.to_string()))?;
+
+    let summary = app_state
+        .db_client
+        .get_retention_summary()
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    let candidates = app_state
+        .db_client
+        .get_retention_candidates(query_params.limit.unwrap_or(50))
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    let response = RetentionReportResponseDto {
+        status: "success".to_string(),
+        dry_run: true,
+        data: RetentionReportData {
+            summary: RetentionSummaryDto::filter_summary(&summary),
+            resumes: candidates.iter().map(FilterRetentionCandidateDto::filter_candidate).collect(),
+        },
+    };
+    Ok(Json(response))
+}
+
+/// Applies retention now instead of waiting for the scheduled run.
+pub async fn run_retention(
+    Extension(app_state): Extension<Arc<AppState>>,
+) -> Result<impl IntoResponse, HttpError> {
+    let handled = apply_retention(app_state)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    let response = RetentionRunResponseDto {
+        status: "success".to_string(),
+        data: RetentionRunData { handled },
+    };
+    Ok(Json(response))
+}
@@ -0,0 +1,72 @@
+use std::{net::SocketAddr, sync::Arc};
+
+use axum::{extract::{ConnectInfo, Path}, http::{header::USER_AGENT, HeaderMap}, response::IntoResponse, routing::get, Extension, Json, Router};
+
+use crate::{services::sharing::{open_share_link, ShareClient}, utils::{dtos::{SharePasswordDto, SharedResumeData, SharedResumeDto, SharedResumeResponseDto}, error::HttpError}, AppState};
+
+/// Public, read-only routes for share links, mounted outside the `auth` layer.
+pub fn share_routes() -> Router {
+    Router::new()
+        .route("/{token}", get(get_shared_resume).post(unlock_shared_resume))
+}
+
+/// Behind a proxy the peer is the proxy, so the forwarded address is preferred. It is only
+/// used for the owner's access log.
+fn share_client(headers: &HeaderMap, peer: SocketAddr) -> ShareClient {
+    let forwarded = headers
+        .get("x-forwarded-for")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(',').next())
+        .map(|ip| ip.trim().to_string())
+        .filter(|ip| !ip.is_empty());
+
+    ShareClient {
+        ip: Some(forwarded.unwrap_or_else(|| peer.ip().to_string())),
+        user_agent: headers
+            .get(USER_AGENT)
+            .and_then(|value| value.to_str().ok())
+            .map(|agent| agent.chars().take(512).collect()),
+    }
+}
+
+async fn shared_resume_response(
+    app_state: &AppState,
+    token: &str,
+    password: Option<&str>,
+    client: &ShareClient,
+) -> Result<SharedResumeResponseDto, HttpError> {
+    let (link, resume) = open_share_link(app_state, token, password, client).await?;
+
+    Ok(SharedResumeResponseDto {
+        status: "success".to_string(),
+        data: SharedResumeData {
+            resume: SharedResumeDto::filter_shared_resume(&resume),
+            expires_at: link.expires_at,
+            views_remaining: link.max_views.map(|max_views| (max_views - link.view_count).max(0)),
+        },
+    })
+}
+
+pub async fn get_shared_resume(
+    Path(token): Path<String>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Extension(app_state): Extension<Arc<AppState>>,
+) -> Result<impl IntoResponse, HttpError> {
+    let client = share_client(&headers, peer);
+    let response = shared_resume_response(&app_state, &token, None, &client).await?;
+    Ok(Json(response))
+}
+
+/// Password protected links are opened with a POST so the password stays out of URLs and logs.
+pub async fn unlock_shared_resume(
+    Path(token): Path<String>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Json(body): Json<SharePasswordDto>,
+) -> Result<impl IntoResponse, HttpError> {
+    let client = share_client(&headers, peer);
+    let response = shared_resume_response(&app_state, &token, body.password.as_deref(), &client).await?;
+    Ok(Json(response))
+}
@@ -0,0 +1,216 @@
+use std::sync::Arc;
+
+use axum::{extract::{Path, Query}, http::StatusCode, middleware::from_fn, response::IntoResponse, routing::{delete, get, post}, Extension, Json, Router};
+use validator::Validate;
+
+use crate::{models::users::UserRole, services::{database::{JobActions, TaxonomyActions}, middleware::{auth, role_check}, taxonomy::normalize_key}, utils::{dtos::{CreateSkillAliasDto, CreateSkillDto, FilterSkillDto, Response, SkillAutocompleteQueryDto, SkillData, SkillListQueryDto, SkillListResponseDto, SkillResponseDto}, error::{ErrorMessage, HttpError}}, AppState};
+
+pub fn skill_routes() -> Router {
+    Router::new()
+        .route(
+            "/",
+            get(get_skills).merge(post(create_skill).layer(from_fn(|state, req, next| {
+                role_check(state, req, next, vec![UserRole::Admin])
+            })).layer(from_fn(auth))),
+        )
+        .route("/autocomplete", get(autocomplete_skills))
+        .route(
+            "/aliases",
+            post(create_skill_alias).layer(from_fn(|state, req, next| {
+                role_check(state, req, next, vec![UserRole::Admin])
+            })).layer(from_fn(auth)),
+        )
+        .route(
+            "/aliases/{alias}",
+            delete(delete_skill_alias).layer(from_fn(|state, req, next| {
+                role_check(state, req, next, vec![UserRole::Admin])
+            })).layer(from_fn(auth)),
+        )
+}
+
+pub async fn get_skills(
+    Query(query_params): Query<SkillListQueryDto>,
+    Extension(app_state): Extension<Arc<AppState>>,
+) -> Result<impl IntoResponse, HttpError> {
+    let taxonomy = app_state.taxonomy.get();
+    let category = query_params.category.as_deref().map(normalize_key);
+
+    let skills: Vec<_> = taxonomy
+        .skills()
+        .iter()
+        .filter(|skill| category.as_ref().is_none_or(|category| normalize_key(&skill.category) == *category))
+        .collect();
+
+    let response = SkillListResponseDto {
+        status: "success".to_string(),
+        results: skills.len() as i64,
+        skills: FilterSkillDto::filter_skills(&skills),
+    };
+    Ok(Json(response))
+}
+
+pub async fn autocomplete_skills(
+    Query(query_params): Query<SkillAutocompleteQueryDto>,
+    Extension(app_state): Extension<Arc<AppState>>,
+) -> Result<impl IntoResponse, HttpError> {
+    query_params
+        .validate()
+        .map_err(|e| HttpError::bad_request(e.to_string()))?;
+
+    let taxonomy = app_state.taxonomy.get();
+    let skills = taxonomy.autocomplete(&query_params.q, query_params.limit.unwrap_or(10));
+
+    let response = SkillListResponseDto {
+        status: "success".to_string(),
+        results: skills.len() as i64,
+        skills: FilterSkillDto::filter_skills(&skills),
+    };
+    Ok(Json(response))
+}
+
+async fn reload_taxonomy(app_state: &AppState) -> Result<(), HttpError> {
+    app_state
+        .taxonomy
+        .reload(&app_state.db_client)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    // Skills are matched through the taxonomy, rankings scored with the old one are out of date
+    app_state
+        .db_client
+        .invalidate_match_scores()
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))
+}
+
+pub async fn create_skill(
+    Extension(app_state): Extension<Arc<AppState>>,
+    Json(body): Json<CreateSkillDto>,
+) -> Result<impl IntoResponse, HttpError> {
+    body.validate()
+        .map_err(|e| HttpError::bad_request(e.to_string()))?;
+
+    let name = body.name.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    let existing = app_state
+        .db_client
+        .get_skill_by_name(&name)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+    if existing.is_some() {
+        return Err(HttpError::unique_constraint_violation(format!("Skill {} already exists", name)));
+    }
+
+    let parent_id = match body.parent.as_deref() {
+        Some(parent) => Some(
+            app_state
+                .taxonomy
+                .get()
+                .lookup(parent)
+                .map(|parent| parent.id)
+                .ok_or_else(|| HttpError::bad_request(format!("Unknown parent skill {}", parent)))?,
+        ),
+        None => None,
+    };
+
+    let skill = app_state
+        .db_client
+        .save_skill(&name, &normalize_key(&body.category), parent_id)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    for alias in &body.aliases {
+        let alias = normalize_key(alias);
+        if !alias.is_empty() && alias != normalize_key(&skill.name) {
+            app_state
+                .db_client
+                .save_skill_alias(&alias, skill.id)
+                .await
+                .map_err(|e| HttpError::server_error(e.to_string()))?;
+        }
+    }
+
+    reload_taxonomy(&app_state).await?;
+
+    let taxonomy = app_state.taxonomy.get();
+    let skill = taxonomy
+        .lookup(&skill.name)
+        .ok_or_else(|| HttpError::server_error(ErrorMessage::ServerError.to_string()))?;
+
+    let response = SkillResponseDto {
+        status: "success".to_string(),
+        data: SkillData { skill: FilterSkillDto::filter_skill(skill) },
+    };
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+pub async fn create_skill_alias(
+    Extension(app_state): Extension<Arc<AppState>>,
+    Json(body): Json<CreateSkillAliasDto>,
+) -> Result<impl IntoResponse, HttpError> {
+    body.validate()
+        .map_err(|e| HttpError::bad_request(e.to_string()))?;
+
+    let alias = normalize_key(&body.alias);
+    if alias.is_empty() {
+        return Err(HttpError::bad_request("Alias must not be blank"));
+    }
+
+    let skill = app_state
+        .db_client
+        .get_skill_by_name(body.skill.trim())
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?
+        .ok_or_else(|| HttpError::not_found(ErrorMessage::SkillNotFound.to_string()))?;
+
+    // An alias must never shadow another canonical skill
+    let canonical = app_state
+        .db_client
+        .get_skill_by_name(&alias)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+    if canonical.is_some_and(|canonical| canonical.id != skill.id) {
+        return Err(HttpError::unique_constraint_violation(format!("{} is already a skill", alias)));
+    }
+
+    app_state
+        .db_client
+        .save_skill_alias(&alias, skill.id)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    reload_taxonomy(&app_state).await?;
+
+    let taxonomy = app_state.taxonomy.get();
+    let skill = taxonomy
+        .lookup(&skill.name)
+        .ok_or_else(|| HttpError::server_error(ErrorMessage::ServerError.to_string()))?;
+
+    let response = SkillResponseDto {
+        status: "success".to_string(),
+        data: SkillData { skill: FilterSkillDto::filter_skill(skill) },
+    };
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+pub async fn delete_skill_alias(
+    Path(alias): Path<String>,
+    Extension(app_state): Extension<Arc<AppState>>,
+) -> Result<impl IntoResponse, HttpError> {
+    let deleted = app_state
+        .db_client
+        .delete_skill_alias(&normalize_key(&alias))
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    if !deleted {
+        return Err(HttpError::not_found(format!("Alias {} not found", alias)));
+    }
+
+    reload_taxonomy(&app_state).await?;
+
+    Ok(Json(Response {
+        message: "Alias deleted successfully".to_string(),
+        status: "success",
+    }))
+}
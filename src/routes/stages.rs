@@ -0,0 +1,130 @@
+use std::sync::Arc;
+
+use axum::{extract::Path, http::StatusCode, middleware::from_fn, response::IntoResponse, routing::{get, post, put}, Extension, Json, Router};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{models::users::UserRole, services::{database::PipelineActions, middleware::role_check}, utils::{dtos::{CreateStageDto, FilterStageDto, Response, StageData, StageListResponseDto, StageResponseDto, UpdateStageDto}, error::{ErrorMessage, HttpError}}, AppState};
+
+pub fn stage_routes() -> Router {
+    Router::new()
+        .route(
+            "/",
+            get(get_stages).merge(post(create_stage).layer(from_fn(|state, req, next| {
+                role_check(state, req, next, vec![UserRole::Admin])
+            }))),
+        )
+        .route(
+            "/{stage_id}",
+            put(update_stage).delete(delete_stage).layer(from_fn(|state, req, next| {
+                role_check(state, req, next, vec![UserRole::Admin])
+            })),
+        )
+}
+
+fn stage_name(name: &str) -> String {
+    name.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn stage_error(e: sqlx::Error, name: Option<&str>) -> HttpError {
+    match (&e, name) {
+        (sqlx::Error::Database(db_err), Some(name)) if db_err.is_unique_violation() => {
+            HttpError::unique_constraint_violation(format!("Stage {} already exists", name))
+        }
+        _ => HttpError::server_error(e.to_string()),
+    }
+}
+
+pub async fn get_stages(
+    Extension(app_state): Extension<Arc<AppState>>,
+) -> Result<impl IntoResponse, HttpError> {
+    let stages = app_state
+        .db_client
+        .get_stages()
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    let response = StageListResponseDto {
+        status: "success".to_string(),
+        stages: FilterStageDto::filter_stages(&stages),
+        results: stages.len() as i64,
+    };
+    Ok(Json(response))
+}
+
+pub async fn create_stage(
+    Extension(app_state): Extension<Arc<AppState>>,
+    Json(body): Json<CreateStageDto>,
+) -> Result<impl IntoResponse, HttpError> {
+    body.validate()
+        .map_err(|e| HttpError::bad_request(e.to_string()))?;
+
+    let name = stage_name(&body.name);
+
+    let stage = app_state
+        .db_client
+        .save_stage(&name, body.position, body.terminal.unwrap_or(false))
+        .await
+        .map_err(|e| stage_error(e, Some(&name)))?;
+
+    let response = StageResponseDto {
+        status: "success".to_string(),
+        data: StageData { stage: FilterStageDto::filter_stage(&stage) },
+    };
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+pub async fn update_stage(
+    Path(stage_id): Path<Uuid>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Json(body): Json<UpdateStageDto>,
+) -> Result<impl IntoResponse, HttpError> {
+    body.validate()
+        .map_err(|e| HttpError::bad_request(e.to_string()))?;
+
+    let name = body.name.as_deref().map(stage_name);
+
+    let stage = app_state
+        .db_client
+        .update_stage(stage_id, name.as_deref(), body.position, body.terminal)
+        .await
+        .map_err(|e| stage_error(e, name.as_deref()))?
+        .ok_or_else(|| HttpError::not_found(ErrorMessage::StageNotFound.to_string()))?;
+
+    let response = StageResponseDto {
+        status: "success".to_string(),
+        data: StageData { stage: FilterStageDto::filter_stage(&stage) },
+    };
+    Ok(Json(response))
+}
+
+pub async fn delete_stage(
+    Path(stage_id): Path<Uuid>,
+    Extension(app_state): Extension<Arc<AppState>>,
+) -> Result<impl IntoResponse, HttpError> {
+    let stages = app_state
+        .db_client
+        .get_stages()
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    if !stages.iter().any(|stage| stage.id == stage_id) {
+        return Err(HttpError::not_found(ErrorMessage::StageNotFound.to_string()));
+    }
+    // New uploads are placed in the first stage, so there must always be one
+    if stages.len() == 1 {
+        return Err(HttpError::new("The last pipeline stage cannot be deleted", StatusCode::CONFLICT));
+    }
+
+    match app_state.db_client.delete_stage(stage_id).await {
+        Ok(_) => Ok(Json(Response {
+            message: "Pipeline stage deleted successfully".to_string(),
+            status: "success",
+        })),
+        Err(sqlx::Error::Database(db_err)) if db_err.is_foreign_key_violation() => Err(HttpError::new(
+            "Resumes are still in this stage, move them to another stage first",
+            StatusCode::CONFLICT,
+        )),
+        Err(e) => Err(HttpError::server_error(e.to_string())),
+    }
+}
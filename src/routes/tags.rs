@@ -0,0 +1,204 @@
+use std::{collections::HashSet, sync::Arc};
+
+use axum::{extract::Path, http::StatusCode, response::IntoResponse, routing::{get, post}, Extension, Json, Router};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{models::tag::DEFAULT_TAG_COLOUR, services::{database::{TagActions, UserActions}, middleware::JWTAuthMiddleware}, utils::{dtos::{BulkTagData, BulkTagRequestDto, BulkTagResponseDto, CreateTagDto, FilterTagDto, Response, TagData, TagListResponseDto, TagResponseDto, UpdateTagDto}, error::{ErrorMessage, HttpError}}, AppState};
+
+pub fn tag_routes() -> Router {
+    Router::new()
+        .route("/", get(get_tags).post(create_tag))
+        .route("/bulk", post(bulk_tag_resumes))
+        .route("/{tag_id}", get(get_tag).put(update_tag).delete(delete_tag))
+}
+
+fn tag_name(name: &str) -> String {
+    name.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn tag_error(e: sqlx::Error, name: Option<&str>) -> HttpError {
+    match (&e, name) {
+        (sqlx::Error::Database(db_err), Some(name)) if db_err.is_unique_violation() => {
+            HttpError::unique_constraint_violation(format!("Tag {} already exists", name))
+        }
+        _ => HttpError::server_error(e.to_string()),
+    }
+}
+
+pub async fn get_tags(
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+) -> Result<impl IntoResponse, HttpError> {
+    let tags = app_state
+        .db_client
+        .get_tags(user.user.id)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    let response = TagListResponseDto {
+        status: "success".to_string(),
+        tags: FilterTagDto::filter_tags(&tags),
+        results: tags.len() as i64,
+    };
+    Ok(Json(response))
+}
+
+pub async fn create_tag(
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+    Json(body): Json<CreateTagDto>,
+) -> Result<impl IntoResponse, HttpError> {
+    body.validate()
+        .map_err(|e| HttpError::bad_request(e.to_string()))?;
+
+    let name = tag_name(&body.name);
+    let colour = body.colour.as_deref().unwrap_or(DEFAULT_TAG_COLOUR).to_lowercase();
+
+    let tag = app_state
+        .db_client
+        .save_tag(user.user.id, &name, &colour)
+        .await
+        .map_err(|e| tag_error(e, Some(&name)))?;
+
+    let response = TagResponseDto {
+        status: "success".to_string(),
+        data: TagData { tag: FilterTagDto::filter_tag(&tag) },
+    };
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+pub async fn get_tag(
+    Path(tag_id): Path<Uuid>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+) -> Result<impl IntoResponse, HttpError> {
+    let tag = app_state
+        .db_client
+        .get_tag(user.user.id, tag_id)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?
+        .ok_or_else(|| HttpError::not_found(ErrorMessage::TagNotFound.to_string()))?;
+
+    let response = TagResponseDto {
+        status: "success".to_string(),
+        data: TagData { tag: FilterTagDto::filter_tag(&tag) },
+    };
+    Ok(Json(response))
+}
+
+pub async fn update_tag(
+    Path(tag_id): Path<Uuid>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+    Json(body): Json<UpdateTagDto>,
+) -> Result<impl IntoResponse, HttpError> {
+    body.validate()
+        .map_err(|e| HttpError::bad_request(e.to_string()))?;
+
+    let name = body.name.as_deref().map(tag_name);
+    let colour = body.colour.as_deref().map(str::to_lowercase);
+
+    let tag = app_state
+        .db_client
+        .update_tag(user.user.id, tag_id, name.as_deref(), colour.as_deref())
+        .await
+        .map_err(|e| tag_error(e, name.as_deref()))?
+        .ok_or_else(|| HttpError::not_found(ErrorMessage::TagNotFound.to_string()))?;
+
+    let response = TagResponseDto {
+        status: "success".to_string(),
+        data: TagData { tag: FilterTagDto::filter_tag(&tag) },
+    };
+    Ok(Json(response))
+}
+
+pub async fn delete_tag(
+    Path(tag_id): Path<Uuid>,
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+) -> Result<impl IntoResponse, HttpError> {
+    let deleted = app_state
+        .db_client
+        .delete_tag(user.user.id, tag_id)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    if !deleted {
+        return Err(HttpError::not_found(ErrorMessage::TagNotFound.to_string()));
+    }
+
+    Ok(Json(Response {
+        message: "Tag deleted successfully".to_string(),
+        status: "success",
+    }))
+}
+
+pub async fn bulk_tag_resumes(
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>,
+    Json(body): Json<BulkTagRequestDto>,
+) -> Result<impl IntoResponse, HttpError> {
+    body.validate()
+        .map_err(|e| HttpError::bad_request(e.to_string()))?;
+
+    if body.add.is_empty() && body.remove.is_empty() {
+        return Err(HttpError::bad_request("Nothing to add or remove"));
+    }
+    if body.add.iter().any(|tag_id| body.remove.contains(tag_id)) {
+        return Err(HttpError::bad_request("A tag cannot be both added and removed"));
+    }
+
+    let user_id = user.user.id;
+
+    // Unknown tags are an error rather than silently skipped, unlike resumes
+    let tags: HashSet<Uuid> = app_state
+        .db_client
+        .get_tags(user_id)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?
+        .iter()
+        .map(|tag| tag.id)
+        .collect();
+    if body.add.iter().chain(&body.remove).any(|tag_id| !tags.contains(tag_id)) {
+        return Err(HttpError::not_found(ErrorMessage::TagNotFound.to_string()));
+    }
+
+    let found: HashSet<Uuid> = app_state
+        .db_client
+        .get_resumes_by_ids(user_id, &body.resume_ids)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?
+        .iter()
+        .map(|resume| resume.id)
+        .collect();
+    let missing_resume_ids = body
+        .resume_ids
+        .iter()
+        .filter(|id| !found.contains(id))
+        .map(|id| id.to_string())
+        .collect();
+    let resume_ids: Vec<Uuid> = found.into_iter().collect();
+
+    let added = app_state
+        .db_client
+        .tag_resumes(user_id, &resume_ids, &body.add)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    let removed = app_state
+        .db_client
+        .untag_resumes(user_id, &resume_ids, &body.remove)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    let response = BulkTagResponseDto {
+        status: "success".to_string(),
+        data: BulkTagData {
+            added,
+            removed,
+            missing_resume_ids,
+        },
+    };
+    Ok(Json(response))
+}
@@ -4,14 +4,20 @@ use axum::{extract::Query, middleware::from_fn, response::IntoResponse, routing:
 use uuid::Uuid;
 use validator::Validate;
 
-use crate::{models::users::UserRole, services::{database::UserActions, middleware::{role_check, JWTAuthMiddleware}}, utils::{dtos::{FilterUserDto, RequestQueryDto, Response, UserData, UserListResponseDto, RoleUpdateDto, UserResponseDto, UserPassUpdateDto}, error::{ErrorMessage, HttpError}, password}, AppState};
+use crate::{models::users::UserRole, services::{database::UserActions, middleware::{role_check, JWTAuthMiddleware}, quota::{analyses_reset_at, get_usage}}, utils::{dtos::{FilterUsageDto, FilterUserDto, UsageData, UsageResponseDto, RequestQueryDto, Response, UserData, UserListResponseDto, RoleUpdateDto, UserResponseDto, UserPassUpdateDto}, error::{ErrorMessage, HttpError}, password}, AppState};
 
 pub fn user_routes() -> Router {
     Router::new()
         .route(
             "/me",
             get(get_me).layer(from_fn(|state, req, next| {
-                role_check(state, req, next, vec![UserRole::Admin, UserRole::User])
+                role_check(state, req, next, vec![UserRole::Admin, UserRole::User, UserRole::Reviewer])
+            })),
+        )
+        .route(
+            "/me/usage",
+            get(get_my_usage).layer(from_fn(|state, req, next| {
+                role_check(state, req, next, vec![UserRole::Admin, UserRole::User, UserRole::Reviewer])
             })),
         )
         .route(
@@ -40,6 +46,22 @@ pub async fn get_me(
     Ok(Json(response_data))
 }
 
+pub async fn get_my_usage(
+    Extension(app_state): Extension<Arc<AppState>>,
+    Extension(user): Extension<JWTAuthMiddleware>
+) -> Result<impl IntoResponse, HttpError> {
+    let usage = get_usage(&app_state, user.user.id).await?;
+
+    let response = UsageResponseDto {
+        status: "success".to_string(),
+        data: UsageData {
+            usage: FilterUsageDto::filter_usage(&usage, analyses_reset_at()),
+        },
+    };
+
+    Ok(Json(response))
+}
+
 pub async fn get_users(
     Query(query_params): Query<RequestQueryDto>,
     Extension(app_state): Extension<Arc<AppState>>,
@@ -0,0 +1,225 @@
+use std::{sync::Arc, time::{Duration, Instant}};
+
+use axum::http::StatusCode;
+use chrono::Utc;
+
+use crate::{
+    models::{analysis::ResumeAnalysis, resume::{AnalysisStatus, Resume}},
+    services::{database::{AnalysisActions, UserActions}, quota::{release_analysis, reserve_analysis}},
+    utils::error::{ErrorMessage, HttpError},
+    AppState,
+};
+
+/// Runs the configured analyzer against a stored resume and records the run in its analysis history.
+/// Unless `force` is set, an earlier analysis of identical content by the same model is reused.
+/// With `charge_quota`, a call to the analyzer uses up one of the owner's daily analyses, failing
+/// with 429 when none are left; reused analyses and failed calls don't count.
+pub async fn run_analysis(
+    app_state: &AppState,
+    resume: &Resume,
+    model: &str,
+    model_version: &str,
+    force: bool,
+    charge_quota: bool,
+) -> Result<ResumeAnalysis, HttpError> {
+    let file_path = stored_file(resume)?;
+
+    if !force && let Some(analysis) = reuse_analysis(app_state, resume, model, model_version).await? {
+        return Ok(analysis);
+    }
+
+    let reserved = if charge_quota {
+        Some(reserve_analysis(app_state, resume.user_id).await?)
+    } else {
+        None
+    };
+
+    let file_name = resume.original_file_name();
+
+    let started_at = Utc::now();
+    let timer = Instant::now();
+
+    let result = app_state
+        .analyzer
+        .analyze(file_path, &file_name, resume.extracted_text.as_deref(), model)
+        .await;
+    let mut result = match result {
+        Ok(result) => result,
+        Err(e) => {
+            // Nothing came of it, so a retry of a pending resume doesn't pay twice
+            if let Some(day) = reserved {
+                release_analysis(app_state, resume.user_id, day).await;
+            }
+            return Err(e.into());
+        }
+    };
+    result.skills = app_state.taxonomy.get().normalize(&result.skills);
+
+    let duration_ms = timer.elapsed().as_millis() as i64;
+
+    app_state
+        .db_client
+        .save_analysis(
+            resume.id,
+            &result,
+            model,
+            model_version,
+            started_at,
+            duration_ms,
+        )
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))
+}
+
+/// The resume's stored file. Anonymized resumes have none left, and only scrubbed text.
+fn stored_file(resume: &Resume) -> Result<&str, HttpError> {
+    resume
+        .file_path
+        .as_deref()
+        .ok_or_else(|| HttpError::new(ErrorMessage::ResumeAnonymized.to_string(), StatusCode::CONFLICT))
+}
+
+/// Copies the latest analysis of the same content into this resume's history, if there is one.
+async fn reuse_analysis(
+    app_state: &AppState,
+    resume: &Resume,
+    model: &str,
+    model_version: &str,
+) -> Result<Option<ResumeAnalysis>, HttpError> {
+    let Some(content_hash) = &resume.content_hash else {
+        return Ok(None);
+    };
+
+    let cached = app_state
+        .db_client
+        .get_cached_analysis(content_hash, model, model_version)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+    let Some(cached) = cached else {
+        return Ok(None);
+    };
+
+    let Some(mut result) = cached.analysis() else {
+        return Ok(None);
+    };
+    result.skills = app_state.taxonomy.get().normalize(&result.skills);
+
+    tracing::info!("Reusing analysis {} for resume {} with identical content", cached.id, resume.id);
+
+    app_state
+        .db_client
+        .save_analysis(resume.id, &result, model, model_version, Utc::now(), 0)
+        .await
+        .map(Some)
+        .map_err(|e| HttpError::server_error(e.to_string()))
+}
+
+const REANALYZE_BATCH_SIZE: i64 = 100;
+
+/// Re-analyzes every resume with the given model, one at a time so the NLP service is not flooded.
+/// Pages through resume ids and loads each resume only when its turn comes.
+pub async fn reanalyze_all(app_state: Arc<AppState>, model: String, model_version: String, force: bool) {
+    let mut after = None;
+    let mut processed = 0;
+
+    loop {
+        let ids = match app_state.db_client.get_resume_ids_after(after, REANALYZE_BATCH_SIZE).await {
+            Ok(ids) => ids,
+            Err(e) => {
+                tracing::warn!("Could not load resumes to re-analyze: {}", e);
+                return;
+            }
+        };
+        let Some(&(last_id, _)) = ids.last() else {
+            break;
+        };
+        after = Some(last_id);
+
+        for (resume_id, user_id) in ids {
+            // Deleted since the page was read
+            let resume = match app_state.db_client.get_resume(Some(user_id), Some(resume_id)).await {
+                Ok(Some(resume)) => resume,
+                Ok(None) => continue,
+                Err(e) => {
+                    tracing::warn!("Could not load resume {} for re-analysis: {}", resume_id, e);
+                    continue;
+                }
+            };
+
+            if let Err(e) = run_analysis(&app_state, &resume, &model, &model_version, force, false).await {
+                tracing::warn!("Error re-analyzing resume {}: {}", resume.id, e);
+            }
+            processed += 1;
+        }
+    }
+
+    tracing::info!("Finished re-analysis of {} resumes with model {}", processed, model);
+}
+
+const PENDING_BATCH_SIZE: usize = 20;
+
+/// Analyzes resumes that were accepted while the NLP service was unavailable or queued by a bulk
+/// upload. Runs periodically, or right away when woken through `AppState::pending_analyses`.
+pub async fn process_pending_analyses(app_state: Arc<AppState>) {
+    let mut interval = tokio::time::interval(Duration::from_secs(app_state.env.nlp_pending_retry_secs));
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {}
+            _ = app_state.pending_analyses.notified() => {}
+        }
+
+        if app_state.circuit_breaker.is_open() {
+            continue;
+        }
+
+        let resumes = match app_state.db_client.get_pending_resumes(PENDING_BATCH_SIZE).await {
+            Ok(resumes) => resumes,
+            Err(e) => {
+                tracing::warn!("Could not load resumes pending analysis: {}", e);
+                continue;
+            }
+        };
+
+        let full_batch = resumes.len() == PENDING_BATCH_SIZE;
+
+        for resume in resumes {
+            let result = run_analysis(
+                &app_state,
+                &resume,
+                &app_state.env.nlp_model,
+                &app_state.env.nlp_model_version,
+                false,
+                true,
+            )
+            .await;
+
+            if let Err(e) = result {
+                // Over quota, the resume stays pending until the owner's allowance resets
+                if e.status == StatusCode::TOO_MANY_REQUESTS {
+                    tracing::debug!("Pending analysis of resume {} deferred: {}", resume.id, e.message);
+                    continue;
+                }
+
+                // Leave the rest pending until the service recovers
+                if app_state.circuit_breaker.is_open() {
+                    break;
+                }
+
+                tracing::warn!("Pending analysis of resume {} failed: {}", resume.id, e);
+                if let Err(e) = app_state
+                    .db_client
+                    .update_analysis_status(resume.id, AnalysisStatus::Failed)
+                    .await
+                {
+                    tracing::warn!("Could not mark resume {} as failed: {}", resume.id, e);
+                }
+            }
+        }
+
+        // Keep draining a large backlog, such as a bulk upload, instead of waiting for the next tick
+        if full_batch && !app_state.circuit_breaker.is_open() {
+            app_state.pending_analyses.notify_one();
+        }
+    }
+}
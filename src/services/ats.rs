@@ -0,0 +1,477 @@
+use bytes::Bytes;
+use chrono::Utc;
+use tokio::fs;
+
+use crate::{models::{ats::{AtsIssue, AtsReport, AtsSeverity}, resume::Resume}, utils::error::HttpError, AppState};
+
+use super::{database::AnalysisActions, extraction::{extract_document, DocumentFormat, ExtractedDocument}};
+
+/// Fewer selectable words per page than this means the page is most likely a picture of text.
+const MIN_WORDS_PER_PAGE: i32 = 25;
+
+const RECOMMENDED_MAX_PAGES: i32 = 2;
+const MAX_PAGES: i32 = 4;
+const RECOMMENDED_MAX_WORDS: i32 = 1000;
+const MAX_WORDS: i32 = 1600;
+
+/// Section headings every ATS recognizes, grouped by the section they introduce.
+const STANDARD_SECTIONS: &[(&str, &[&str])] = &[
+    ("experience", &["experience", "work experience", "professional experience", "employment", "employment history", "work history", "career history"]),
+    ("education", &["education", "academic background", "qualifications", "education and training"]),
+    ("skills", &["skills", "technical skills", "core skills", "key skills", "competencies", "core competencies"]),
+];
+
+/// Other headings that are standard but not required.
+const OPTIONAL_SECTIONS: &[&str] = &[
+    "summary", "professional summary", "profile", "objective", "career objective", "about me",
+    "contact", "contact information", "certifications", "certificates", "projects", "languages",
+    "publications", "awards", "volunteering", "volunteer experience", "interests", "references",
+    "achievements", "training", "courses",
+];
+
+fn issue(code: &str, severity: AtsSeverity, message: impl Into<String>, suggestion: &str) -> AtsIssue {
+    AtsIssue {
+        code: code.to_string(),
+        severity,
+        message: message.into(),
+        suggestion: suggestion.to_string(),
+    }
+}
+
+/// Runs every rule against an extracted document and scores the result.
+pub fn check(document: &ExtractedDocument) -> AtsReport {
+    let mut issues = Vec::new();
+
+    check_readable_text(document, &mut issues);
+    check_layout(document, &mut issues);
+    check_contact(&document.text, &mut issues);
+    check_headings(&document.text, &mut issues);
+    check_length(document, &mut issues);
+
+    issues.sort_by_key(|issue| std::cmp::Reverse(issue.severity));
+
+    let penalty: i32 = issues
+        .iter()
+        .map(|issue| match issue.severity {
+            AtsSeverity::Critical => 25,
+            AtsSeverity::Warning => 10,
+            AtsSeverity::Info => 2,
+        })
+        .sum();
+
+    AtsReport {
+        score: (100 - penalty).max(0),
+        passed: !issues.iter().any(|issue| issue.severity == AtsSeverity::Critical),
+        issues,
+        checked_at: Utc::now(),
+    }
+}
+
+/// Checks an extracted document and stores the report on the resume.
+pub async fn record_ats_report(
+    app_state: &AppState,
+    resume: &mut Resume,
+    document: &ExtractedDocument,
+) -> Result<(), HttpError> {
+    let report = check(document);
+
+    app_state
+        .db_client
+        .save_ats_report(resume.id, &report)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    resume.ats_report = serde_json::to_value(&report).ok();
+    Ok(())
+}
+
+/// Creates the report for resumes uploaded before the ATS check existed, re-reading the stored file.
+pub async fn backfill_ats_report(app_state: &AppState, resume: &mut Resume) {
+    if resume.ats_report.is_some() {
+        return;
+    }
+
+    // Anonymized resumes have no file to check
+    let Some(file_path) = resume.file_path.as_deref() else {
+        return;
+    };
+
+    let bytes = match fs::read(file_path).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            tracing::warn!("Could not read {} for ATS check: {}", file_path, e);
+            return;
+        }
+    };
+
+    let result = match extract_document(resume.original_file_name(), Bytes::from(bytes)).await {
+        Ok(document) => record_ats_report(app_state, resume, &document).await,
+        Err(e) => Err(HttpError::server_error(e.to_string())),
+    };
+    if let Err(e) = result {
+        tracing::warn!("Could not run ATS check for resume {}: {}", resume.id, e);
+    }
+}
+
+fn check_readable_text(document: &ExtractedDocument, issues: &mut Vec<AtsIssue>) {
+    let layout = &document.layout;
+
+    if document.format == DocumentFormat::Pdf {
+        let pages = document.page_count.unwrap_or(1).max(1);
+        if layout.images > 0 && document.word_count < MIN_WORDS_PER_PAGE * pages {
+            issues.push(issue(
+                "images_only",
+                AtsSeverity::Critical,
+                "The PDF is made of images with little or no selectable text",
+                "Export the resume from your word processor as a text based PDF instead of scanning it or saving it as images.",
+            ));
+            return;
+        }
+        if document.word_count == 0 {
+            issues.push(issue(
+                "no_text",
+                AtsSeverity::Critical,
+                "No readable text could be found in the PDF, the text may have been converted to outlines",
+                "Turn off \"convert text to outlines/curves\" when exporting, or upload a DOCX version.",
+            ));
+            return;
+        }
+    }
+
+    if layout.outline_fonts > 0 {
+        issues.push(issue(
+            "outline_fonts",
+            AtsSeverity::Warning,
+            format!("{} font(s) are embedded as drawn outlines, text set in them may not be readable", layout.outline_fonts),
+            "Use standard fonts such as Arial, Calibri or Times New Roman and embed them as regular fonts when exporting.",
+        ));
+    }
+
+    // Fonts without a usable character map come out as replacement characters or private use code points
+    let total = document.text.chars().filter(|c| !c.is_whitespace()).count();
+    let garbled = document
+        .text
+        .chars()
+        .filter(|c| *c == '\u{fffd}' || ('\u{e000}'..='\u{f8ff}').contains(c) || (c.is_control() && !c.is_whitespace()))
+        .count();
+    if total > 0 && garbled * 20 > total {
+        issues.push(issue(
+            "garbled_text",
+            AtsSeverity::Critical,
+            "Part of the text can't be decoded, the fonts are probably embedded without a character map",
+            "Re-export the resume with standard fonts, or upload a DOCX version.",
+        ));
+    }
+}
+
+fn check_layout(document: &ExtractedDocument, issues: &mut Vec<AtsIssue>) {
+    let layout = &document.layout;
+
+    if layout.tables > 0 {
+        issues.push(issue(
+            "tables",
+            AtsSeverity::Warning,
+            format!("The resume uses {} table(s), many ATS read tables out of order or skip them", layout.tables),
+            "Replace tables with plain paragraphs and bullet points.",
+        ));
+    }
+
+    if layout.columns > 1 {
+        issues.push(issue(
+            "columns",
+            AtsSeverity::Warning,
+            format!("The resume is laid out in {} columns, which ATS often merge line by line", layout.columns),
+            "Use a single column layout.",
+        ));
+    }
+
+    if layout.text_boxes > 0 {
+        issues.push(issue(
+            "text_boxes",
+            AtsSeverity::Warning,
+            format!("{} text box(es) found, their content is frequently ignored by ATS", layout.text_boxes),
+            "Move the content of text boxes into the main body of the document.",
+        ));
+    }
+
+    if document.format != DocumentFormat::Pdf && layout.images > 0 {
+        issues.push(issue(
+            "images",
+            AtsSeverity::Info,
+            format!("{} image(s) found, any text inside them won't be read", layout.images),
+            "Make sure no important information (like contact details or skills) is only shown in an image.",
+        ));
+    }
+}
+
+fn has_email(text: &str) -> bool {
+    text.split(|c: char| c.is_whitespace() || matches!(c, '<' | '>' | '(' | ')' | ',' | ';' | '|'))
+        .any(|word| {
+            let word = word.trim_matches(|c: char| !c.is_alphanumeric());
+            match word.split_once('@') {
+                Some((local, domain)) => !local.is_empty() && domain.contains('.') && !domain.starts_with('.') && !domain.ends_with('.'),
+                None => false,
+            }
+        })
+}
+
+fn has_phone(text: &str) -> bool {
+    text.lines().any(|line| {
+        // Runs of digits with the separators phone numbers are usually written with
+        line.split(|c: char| !(c.is_ascii_digit() || matches!(c, ' ' | '-' | '.' | '(' | ')' | '+')))
+            .any(|candidate| {
+                let digits = candidate.chars().filter(|c| c.is_ascii_digit()).count();
+                (7..=15).contains(&digits)
+            })
+    })
+}
+
+fn check_contact(text: &str, issues: &mut Vec<AtsIssue>) {
+    let email = has_email(text);
+    let phone = has_phone(text);
+
+    match (email, phone) {
+        (false, false) => issues.push(issue(
+            "missing_contact",
+            AtsSeverity::Critical,
+            "No email address or phone number found",
+            "Add a contact section with your email address and phone number in the main body, not in the header or footer.",
+        )),
+        (false, true) => issues.push(issue(
+            "missing_email",
+            AtsSeverity::Warning,
+            "No email address found",
+            "Add your email address to the contact section.",
+        )),
+        (true, false) => issues.push(issue(
+            "missing_phone",
+            AtsSeverity::Info,
+            "No phone number found",
+            "Consider adding a phone number to the contact section.",
+        )),
+        (true, true) => {}
+    }
+}
+
+/// Short lines without sentence punctuation, the way section headings are written.
+pub fn is_heading(line: &str) -> bool {
+    let line = line.trim().trim_end_matches(':').trim();
+    let words = line.split_whitespace().count();
+    (1..=4).contains(&words)
+        && line.chars().count() <= 40
+        && !line.ends_with('.')
+        && !line.contains('@')
+        && !line.chars().any(|c| c.is_ascii_digit())
+        && line.chars().next().is_some_and(|c| c.is_uppercase())
+}
+
+/// Whether a heading names one of the sections ATS know about.
+pub fn is_standard_heading(heading: &str) -> bool {
+    let heading = heading.trim().trim_end_matches(':').trim().to_lowercase();
+    OPTIONAL_SECTIONS.contains(&heading.as_str())
+        || STANDARD_SECTIONS.iter().any(|(_, names)| names.contains(&heading.as_str()))
+}
+
+fn heading_candidates(text: &str) -> Vec<String> {
+    text.lines()
+        .filter(|line| is_heading(line))
+        .map(|line| line.trim().trim_end_matches(':').trim().to_lowercase())
+        .collect()
+}
+
+fn check_headings(text: &str, issues: &mut Vec<AtsIssue>) {
+    let headings = heading_candidates(text);
+
+    let missing: Vec<&str> = STANDARD_SECTIONS
+        .iter()
+        .filter(|(_, names)| !headings.iter().any(|heading| names.contains(&heading.as_str())))
+        .map(|(section, _)| *section)
+        .collect();
+
+    if missing.is_empty() {
+        return;
+    }
+
+    let recognized = headings.iter().any(|heading| is_standard_heading(heading));
+
+    let severity = if recognized { AtsSeverity::Info } else { AtsSeverity::Warning };
+    issues.push(issue(
+        "unusual_headings",
+        severity,
+        format!("No standard heading found for: {}", missing.join(", ")),
+        "Use conventional section headings such as \"Work Experience\", \"Education\" and \"Skills\".",
+    ));
+}
+
+fn check_length(document: &ExtractedDocument, issues: &mut Vec<AtsIssue>) {
+    let too_long = match document.page_count {
+        Some(pages) if pages > MAX_PAGES => Some((AtsSeverity::Critical, format!("The resume is {} pages long", pages))),
+        Some(pages) if pages > RECOMMENDED_MAX_PAGES => Some((AtsSeverity::Warning, format!("The resume is {} pages long", pages))),
+        _ if document.word_count > MAX_WORDS => Some((AtsSeverity::Critical, format!("The resume has {} words", document.word_count))),
+        _ if document.word_count > RECOMMENDED_MAX_WORDS => Some((AtsSeverity::Warning, format!("The resume has {} words", document.word_count))),
+        _ => None,
+    };
+
+    if let Some((severity, message)) = too_long {
+        issues.push(issue(
+            "too_long",
+            severity,
+            message,
+            "Keep the resume to one or two pages, focusing on the most recent and relevant experience.",
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::services::extraction::DocumentLayout;
+
+    use super::*;
+
+    const RESUME: &str = "Jane Doe\njane.doe@example.com\n+49 30 1234567\nExperience\nDeveloper at Acme\nEducation\nBSc Computer Science\nSkills\nRust, SQL";
+
+    fn document(format: DocumentFormat, text: &str) -> ExtractedDocument {
+        ExtractedDocument {
+            format,
+            text: text.to_string(),
+            page_count: None,
+            word_count: text.split_whitespace().count() as i32,
+            language: None,
+            layout: DocumentLayout::default(),
+        }
+    }
+
+    fn findings(document: &ExtractedDocument) -> Vec<(String, AtsSeverity)> {
+        check(document).issues.into_iter().map(|issue| (issue.code, issue.severity)).collect()
+    }
+
+    fn finding(code: &str, severity: AtsSeverity) -> Vec<(String, AtsSeverity)> {
+        vec![(code.to_string(), severity)]
+    }
+
+    #[test]
+    fn passes_a_clean_resume() {
+        let report = check(&document(DocumentFormat::Docx, RESUME));
+
+        assert!(report.issues.is_empty());
+        assert_eq!(report.score, 100);
+        assert!(report.passed);
+    }
+
+    #[test]
+    fn flags_pdfs_made_of_images() {
+        let mut scanned = document(DocumentFormat::Pdf, RESUME);
+        scanned.page_count = Some(1);
+        scanned.layout.images = 1;
+        scanned.word_count = MIN_WORDS_PER_PAGE - 1;
+        assert_eq!(findings(&scanned), finding("images_only", AtsSeverity::Critical));
+
+        scanned.word_count = MIN_WORDS_PER_PAGE;
+        assert!(findings(&scanned).is_empty());
+
+        let mut outlined = document(DocumentFormat::Pdf, "");
+        outlined.text = RESUME.to_string();
+        assert_eq!(findings(&outlined), finding("no_text", AtsSeverity::Critical));
+    }
+
+    #[test]
+    fn flags_unreadable_fonts() {
+        let mut outlines = document(DocumentFormat::Pdf, RESUME);
+        outlines.layout.outline_fonts = 1;
+        assert_eq!(findings(&outlines), finding("outline_fonts", AtsSeverity::Warning));
+
+        // One in twenty characters garbled is tolerated, more is not
+        let characters = RESUME.chars().filter(|c| !c.is_whitespace()).count();
+        let tolerated = format!("{}\n{}", RESUME, "\u{e000}".repeat(characters / 19));
+        assert!(findings(&document(DocumentFormat::Docx, &tolerated)).is_empty());
+        let garbled = format!("{}\n{}", RESUME, "\u{fffd}".repeat(characters / 10));
+        assert_eq!(findings(&document(DocumentFormat::Docx, &garbled)), finding("garbled_text", AtsSeverity::Critical));
+    }
+
+    #[test]
+    fn flags_layouts_ats_struggle_with() {
+        let mut layout = document(DocumentFormat::Docx, RESUME);
+        layout.layout = DocumentLayout {
+            tables: 1,
+            columns: 2,
+            images: 1,
+            text_boxes: 1,
+            ..Default::default()
+        };
+
+        let codes: Vec<String> = findings(&layout).into_iter().map(|(code, _)| code).collect();
+        assert_eq!(codes, vec!["tables", "columns", "text_boxes", "images"]);
+
+        // PDFs with images are covered by the readable text check
+        layout.format = DocumentFormat::Pdf;
+        layout.word_count = 200;
+        layout.layout = DocumentLayout {
+            images: 1,
+            ..Default::default()
+        };
+        assert!(findings(&layout).is_empty());
+    }
+
+    #[test]
+    fn flags_missing_contact_details() {
+        let without = |line: &str| document(DocumentFormat::Docx, &RESUME.replace(line, ""));
+
+        assert_eq!(findings(&without("jane.doe@example.com")), finding("missing_email", AtsSeverity::Warning));
+        assert_eq!(findings(&without("+49 30 1234567")), finding("missing_phone", AtsSeverity::Info));
+        assert_eq!(
+            findings(&without("jane.doe@example.com\n+49 30 1234567")),
+            finding("missing_contact", AtsSeverity::Critical)
+        );
+    }
+
+    #[test]
+    fn flags_unusual_headings() {
+        let some_standard = RESUME.replace("Skills", "Things I Know");
+        assert_eq!(findings(&document(DocumentFormat::Docx, &some_standard)), finding("unusual_headings", AtsSeverity::Info));
+
+        let none_standard = "Jane Doe\njane.doe@example.com\n+49 30 1234567\nWhere I Worked\nAcme\nThings I Know\nRust";
+        assert_eq!(findings(&document(DocumentFormat::Docx, none_standard)), finding("unusual_headings", AtsSeverity::Warning));
+    }
+
+    #[test]
+    fn flags_long_resumes() {
+        let mut long = document(DocumentFormat::Pdf, RESUME);
+        long.word_count = 200;
+
+        long.page_count = Some(RECOMMENDED_MAX_PAGES);
+        assert!(findings(&long).is_empty());
+        long.page_count = Some(RECOMMENDED_MAX_PAGES + 1);
+        assert_eq!(findings(&long), finding("too_long", AtsSeverity::Warning));
+        long.page_count = Some(MAX_PAGES + 1);
+        assert_eq!(findings(&long), finding("too_long", AtsSeverity::Critical));
+
+        let mut wordy = document(DocumentFormat::Docx, RESUME);
+        wordy.word_count = RECOMMENDED_MAX_WORDS;
+        assert!(findings(&wordy).is_empty());
+        wordy.word_count = RECOMMENDED_MAX_WORDS + 1;
+        assert_eq!(findings(&wordy), finding("too_long", AtsSeverity::Warning));
+        wordy.word_count = MAX_WORDS + 1;
+        assert_eq!(findings(&wordy), finding("too_long", AtsSeverity::Critical));
+    }
+
+    #[test]
+    fn scores_by_severity_and_never_below_zero() {
+        let mut poor = document(DocumentFormat::Docx, "Jane Doe\nWhere I Worked\nAcme");
+        let report = check(&poor);
+        // Missing contact (25) and unusual headings (10)
+        assert_eq!(report.score, 65);
+        assert!(!report.passed);
+        assert_eq!(report.issues[0].severity, AtsSeverity::Critical);
+
+        poor.word_count = MAX_WORDS + 1;
+        poor.layout = DocumentLayout {
+            tables: 1,
+            columns: 3,
+            text_boxes: 2,
+            outline_fonts: 1,
+            ..Default::default()
+        };
+        poor.text.push_str(&"\u{fffd}".repeat(50));
+        assert_eq!(check(&poor).score, 0);
+    }
+}
@@ -0,0 +1,268 @@
+use std::io::{Cursor, Read};
+
+use bytes::Bytes;
+use zip::ZipArchive;
+
+use crate::config::Config;
+
+use super::extraction::DocumentFormat;
+
+/// Entries expanding more than this are treated as zip bombs
+pub const MAX_COMPRESSION_RATIO: u64 = 100;
+
+#[derive(Debug, Clone, Copy)]
+pub struct ArchiveLimits {
+    pub max_files: usize,
+    pub max_file_bytes: u64,
+    pub max_extracted_bytes: u64,
+}
+
+impl ArchiveLimits {
+    pub fn from_config(config: &Config) -> Self {
+        ArchiveLimits {
+            max_files: config.bulk_max_files,
+            max_file_bytes: config.bulk_max_file_bytes,
+            max_extracted_bytes: config.bulk_max_extracted_bytes,
+        }
+    }
+}
+
+/// A file of a bulk upload, either sent directly or unpacked from an archive.
+/// `data` holds the reason when the file was rejected before it could be read.
+#[derive(Debug, Clone)]
+pub struct BulkFile {
+    pub file_name: String,
+    pub archive: Option<String>,
+    pub data: Result<Bytes, String>,
+}
+
+/// ZIP files that aren't DOCX or ODT documents, which are ZIPs themselves.
+pub fn is_archive(file_name: &str, bytes: &[u8]) -> bool {
+    bytes.starts_with(b"PK\x03\x04") && DocumentFormat::detect(file_name, bytes).is_none()
+}
+
+/// Unpacks the files of a ZIP archive, skipping folders and OS metadata.
+///
+/// Entries are never written to disk under their archive path, but unsafe paths are still
+/// rejected. Oversized or suspiciously compressed entries and nested archives fail on their own,
+/// once `budget` (bytes left to extract for the whole upload) runs out the remaining entries fail.
+pub fn unpack_archive(
+    archive_name: &str,
+    bytes: &[u8],
+    limits: &ArchiveLimits,
+    budget: &mut u64,
+) -> Result<Vec<BulkFile>, String> {
+    let mut archive = ZipArchive::new(Cursor::new(bytes)).map_err(|e| format!("Could not read archive: {}", e))?;
+    let mut files = Vec::new();
+
+    for index in 0..archive.len() {
+        let mut entry = match archive.by_index(index) {
+            Ok(entry) => entry,
+            Err(e) => {
+                files.push(BulkFile {
+                    file_name: format!("entry {}", index + 1),
+                    archive: Some(archive_name.to_string()),
+                    data: Err(format!("Could not read archive entry: {}", e)),
+                });
+                continue;
+            }
+        };
+
+        if entry.is_dir() || entry.name().starts_with("__MACOSX/") {
+            continue;
+        }
+
+        let Some(path) = entry.enclosed_name() else {
+            files.push(BulkFile {
+                file_name: entry.name().to_string(),
+                archive: Some(archive_name.to_string()),
+                data: Err("Unsafe path in archive".to_string()),
+            });
+            continue;
+        };
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| entry.name().to_string());
+        if file_name.starts_with('.') {
+            continue;
+        }
+
+        let data = read_entry(&mut entry, limits, budget).and_then(|data| {
+            if is_archive(&file_name, &data) {
+                Err("Nested archives are not supported".to_string())
+            } else {
+                Ok(Bytes::from(data))
+            }
+        });
+
+        files.push(BulkFile {
+            file_name,
+            archive: Some(archive_name.to_string()),
+            data,
+        });
+    }
+
+    Ok(files)
+}
+
+fn read_entry(
+    entry: &mut zip::read::ZipFile<'_>,
+    limits: &ArchiveLimits,
+    budget: &mut u64,
+) -> Result<Vec<u8>, String> {
+    if entry.encrypted() {
+        return Err("Encrypted files are not supported".to_string());
+    }
+
+    let declared = entry.size();
+    if declared > limits.max_file_bytes {
+        return Err(format!("File is larger than {} bytes", limits.max_file_bytes));
+    }
+    if entry.compressed_size() > 0 && declared / entry.compressed_size() > MAX_COMPRESSION_RATIO {
+        return Err("File is compressed too heavily to be extracted safely".to_string());
+    }
+    if declared > *budget {
+        return Err("Upload exceeds the total size that can be extracted".to_string());
+    }
+
+    // Headers can lie about sizes, so cap what is actually read as well
+    let cap = limits.max_file_bytes.min(*budget);
+    let mut data = Vec::new();
+    entry
+        .take(cap + 1)
+        .read_to_end(&mut data)
+        .map_err(|e| format!("Could not extract file: {}", e))?;
+    if data.len() as u64 > cap {
+        return Err("File is larger than its archive entry claims".to_string());
+    }
+
+    *budget -= data.len() as u64;
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
+
+    use super::*;
+
+    const LIMITS: ArchiveLimits = ArchiveLimits {
+        max_files: 10,
+        max_file_bytes: 1024 * 1024,
+        max_extracted_bytes: 4 * 1024 * 1024,
+    };
+
+    fn zip(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+        for (name, data) in entries {
+            if name.ends_with('/') {
+                writer.add_directory(*name, options).unwrap();
+            } else {
+                writer.start_file(*name, options).unwrap();
+                writer.write_all(data).unwrap();
+            }
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    fn unpack(bytes: &[u8], limits: &ArchiveLimits, budget: &mut u64) -> Vec<(String, Result<Vec<u8>, String>)> {
+        unpack_archive("resumes.zip", bytes, limits, budget)
+            .unwrap()
+            .into_iter()
+            .inspect(|file| assert_eq!(file.archive.as_deref(), Some("resumes.zip")))
+            .map(|file| (file.file_name, file.data.map(|data| data.to_vec())))
+            .collect()
+    }
+
+    #[test]
+    fn unpacks_files_and_skips_folders_and_metadata() {
+        let archive = zip(&[
+            ("cvs/", b""),
+            ("cvs/jane.txt", b"Jane Doe"),
+            ("__MACOSX/cvs/._jane.txt", b"metadata"),
+            ("cvs/.DS_Store", b"metadata"),
+            ("john.txt", b"John Doe"),
+        ]);
+        let mut budget = LIMITS.max_extracted_bytes;
+
+        assert_eq!(
+            unpack(&archive, &LIMITS, &mut budget),
+            vec![
+                ("jane.txt".to_string(), Ok(b"Jane Doe".to_vec())),
+                ("john.txt".to_string(), Ok(b"John Doe".to_vec())),
+            ]
+        );
+        assert_eq!(budget, LIMITS.max_extracted_bytes - 16);
+    }
+
+    #[test]
+    fn rejects_unsafe_paths() {
+        let archive = zip(&[("../evil.txt", b"evil"), ("ok.txt", b"fine")]);
+        let mut budget = LIMITS.max_extracted_bytes;
+        let files = unpack(&archive, &LIMITS, &mut budget);
+
+        assert_eq!(files[0], ("../evil.txt".to_string(), Err("Unsafe path in archive".to_string())));
+        assert!(files[1].1.is_ok());
+    }
+
+    #[test]
+    fn rejects_nested_archives_but_not_documents() {
+        let nested = zip(&[("jane.txt", b"Jane Doe")]);
+        let docx = zip(&[("[Content_Types].xml", b"<Types/>"), ("word/document.xml", b"<w:document/>")]);
+        let archive = zip(&[("more.zip", &nested), ("jane.docx", &docx)]);
+        let mut budget = LIMITS.max_extracted_bytes;
+        let files = unpack(&archive, &LIMITS, &mut budget);
+
+        assert_eq!(files[0].1, Err("Nested archives are not supported".to_string()));
+        assert_eq!(files[1].1.as_deref(), Ok(docx.as_slice()));
+        assert!(is_archive("more.zip", &nested));
+        assert!(!is_archive("jane.docx", &docx));
+        assert!(!is_archive("jane.txt", b"Jane Doe"));
+    }
+
+    #[test]
+    fn rejects_files_over_the_size_limit() {
+        let limits = ArchiveLimits { max_file_bytes: 100, ..LIMITS };
+        let archive = zip(&[("big.txt", &[b'a'; 101]), ("small.txt", &[b'a'; 100])]);
+        let mut budget = LIMITS.max_extracted_bytes;
+        let files = unpack(&archive, &limits, &mut budget);
+
+        assert_eq!(files[0].1, Err("File is larger than 100 bytes".to_string()));
+        assert!(files[1].1.is_ok());
+    }
+
+    #[test]
+    fn rejects_zip_bombs() {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        writer.start_file("bomb.txt", SimpleFileOptions::default()).unwrap();
+        writer.write_all(&vec![0; 1024 * 1024]).unwrap();
+        let archive = writer.finish().unwrap().into_inner();
+        let mut budget = LIMITS.max_extracted_bytes;
+
+        let files = unpack(&archive, &LIMITS, &mut budget);
+        assert_eq!(files[0].1, Err("File is compressed too heavily to be extracted safely".to_string()));
+        assert_eq!(budget, LIMITS.max_extracted_bytes);
+    }
+
+    #[test]
+    fn stops_extracting_once_the_budget_is_spent() {
+        let archive = zip(&[("one.txt", &[b'a'; 100]), ("two.txt", &[b'a'; 100]), ("three.txt", &[b'a'; 40])]);
+        let mut budget = 150;
+
+        let files = unpack(&archive, &LIMITS, &mut budget);
+        assert!(files[0].1.is_ok());
+        assert_eq!(files[1].1, Err("Upload exceeds the total size that can be extracted".to_string()));
+        assert!(files[2].1.is_ok());
+        assert_eq!(budget, 10);
+    }
+
+    #[test]
+    fn fails_on_unreadable_archives() {
+        let error = unpack_archive("resumes.zip", b"PK\x03\x04 not really", &LIMITS, &mut 0).unwrap_err();
+        assert!(error.starts_with("Could not read archive"));
+    }
+}
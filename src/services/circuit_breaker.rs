@@ -0,0 +1,387 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::models::analysis_result::AnalysisResult;
+
+use super::nlp::{Analyzer, NlpError};
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CircuitStatus {
+    pub state: CircuitState,
+    pub consecutive_failures: u32,
+    pub failure_threshold: u32,
+    pub last_failure_at: Option<DateTime<Utc>>,
+    pub last_success_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub retry_after_secs: Option<u64>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    state: CircuitState,
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    half_open_in_flight: u32,
+    last_failure_at: Option<DateTime<Utc>>,
+    last_success_at: Option<DateTime<Utc>>,
+    last_error: Option<String>,
+}
+
+/// Tracks NLP service failures and stops sending requests once it looks down.
+///
+/// After `failure_threshold` consecutive failures the circuit opens and calls fail fast
+/// for `open_duration`. It then lets up to `half_open_max_calls` trial calls through;
+/// one success closes the circuit again, one failure re-opens it.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    open_duration: Duration,
+    half_open_max_calls: u32,
+    inner: Mutex<Inner>,
+}
+
+impl CircuitBreaker {
+    pub fn new(failure_threshold: u32, open_duration: Duration, half_open_max_calls: u32) -> Self {
+        CircuitBreaker {
+            failure_threshold: failure_threshold.max(1),
+            open_duration,
+            half_open_max_calls: half_open_max_calls.max(1),
+            inner: Mutex::new(Inner {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                opened_at: None,
+                half_open_in_flight: 0,
+                last_failure_at: None,
+                last_success_at: None,
+                last_error: None,
+            }),
+        }
+    }
+
+    /// Returns true when requests would currently be rejected without being attempted.
+    pub fn is_open(&self) -> bool {
+        let inner = self.inner.lock().unwrap();
+        match inner.state {
+            CircuitState::Closed => false,
+            CircuitState::Open => !self.cooldown_elapsed(&inner),
+            CircuitState::HalfOpen => inner.half_open_in_flight >= self.half_open_max_calls,
+        }
+    }
+
+    fn cooldown_elapsed(&self, inner: &Inner) -> bool {
+        inner
+            .opened_at
+            .is_some_and(|opened_at| opened_at.elapsed() >= self.open_duration)
+    }
+
+    /// Reserves a slot for a call, moving from open to half-open once the cooldown has passed.
+    pub fn try_acquire(&self) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        match inner.state {
+            CircuitState::Closed => true,
+            CircuitState::Open => {
+                if !self.cooldown_elapsed(&inner) {
+                    return false;
+                }
+                tracing::info!("NLP circuit breaker half-open, sending trial request");
+                inner.state = CircuitState::HalfOpen;
+                inner.half_open_in_flight = 1;
+                true
+            }
+            CircuitState::HalfOpen => {
+                if inner.half_open_in_flight >= self.half_open_max_calls {
+                    return false;
+                }
+                inner.half_open_in_flight += 1;
+                true
+            }
+        }
+    }
+
+    pub fn record_success(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.state != CircuitState::Closed {
+            tracing::info!("NLP circuit breaker closed");
+        }
+        inner.state = CircuitState::Closed;
+        inner.consecutive_failures = 0;
+        inner.opened_at = None;
+        inner.half_open_in_flight = 0;
+        inner.last_success_at = Some(Utc::now());
+    }
+
+    /// Gives back a reserved slot without counting the call either way.
+    pub fn release(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.state == CircuitState::HalfOpen {
+            inner.half_open_in_flight = inner.half_open_in_flight.saturating_sub(1);
+        }
+    }
+
+    pub fn record_failure(&self, error: &NlpError) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.consecutive_failures += 1;
+        inner.last_failure_at = Some(Utc::now());
+        inner.last_error = Some(error.to_string());
+
+        let should_open = match inner.state {
+            CircuitState::HalfOpen => true,
+            CircuitState::Closed => inner.consecutive_failures >= self.failure_threshold,
+            CircuitState::Open => false,
+        };
+
+        if should_open {
+            tracing::warn!(
+                "NLP circuit breaker opened after {} consecutive failures: {}",
+                inner.consecutive_failures,
+                error
+            );
+            inner.state = CircuitState::Open;
+            inner.opened_at = Some(Instant::now());
+            inner.half_open_in_flight = 0;
+        }
+    }
+
+    pub fn status(&self) -> CircuitStatus {
+        let inner = self.inner.lock().unwrap();
+        let retry_after_secs = match inner.state {
+            CircuitState::Open => inner.opened_at.map(|opened_at| {
+                self.open_duration
+                    .saturating_sub(opened_at.elapsed())
+                    .as_secs()
+            }),
+            _ => None,
+        };
+
+        CircuitStatus {
+            state: inner.state,
+            consecutive_failures: inner.consecutive_failures,
+            failure_threshold: self.failure_threshold,
+            last_failure_at: inner.last_failure_at,
+            last_success_at: inner.last_success_at,
+            last_error: inner.last_error.clone(),
+            retry_after_secs,
+        }
+    }
+}
+
+/// Wraps an analyzer so every call goes through the circuit breaker.
+#[derive(Debug)]
+pub struct CircuitBreakerAnalyzer {
+    inner: Arc<dyn Analyzer>,
+    breaker: Arc<CircuitBreaker>,
+}
+
+impl CircuitBreakerAnalyzer {
+    pub fn new(inner: Arc<dyn Analyzer>, breaker: Arc<CircuitBreaker>) -> Self {
+        CircuitBreakerAnalyzer { inner, breaker }
+    }
+}
+
+#[async_trait]
+impl Analyzer for CircuitBreakerAnalyzer {
+    async fn analyze(
+        &self,
+        file_path: &str,
+        file_name: &str,
+        text: Option<&str>,
+        model: &str,
+    ) -> Result<AnalysisResult, NlpError> {
+        if !self.breaker.try_acquire() {
+            return Err(NlpError::CircuitOpen);
+        }
+
+        match self.inner.analyze(file_path, file_name, text, model).await {
+            Ok(result) => {
+                self.breaker.record_success();
+                Ok(result)
+            }
+            Err(e) if e.is_retryable() => {
+                self.breaker.record_failure(&e);
+                Err(e)
+            }
+            // Bad input or a malformed payload says nothing about whether the service is up
+            Err(e) => {
+                self.breaker.release();
+                Err(e)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::services::nlp::StubAnalyzer;
+
+    use super::*;
+
+    const COOLDOWN: Duration = Duration::from_millis(20);
+
+    fn transport_error() -> NlpError {
+        NlpError::Transport("connection refused".to_string())
+    }
+
+    /// Fails every call, with a transport error when `retryable` and a schema error otherwise.
+    #[derive(Debug)]
+    struct FailingAnalyzer {
+        retryable: bool,
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl Analyzer for FailingAnalyzer {
+        async fn analyze(&self, _: &str, _: &str, _: Option<&str>, _: &str) -> Result<AnalysisResult, NlpError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if self.retryable {
+                Err(transport_error())
+            } else {
+                Err(NlpError::InvalidSchema("missing skills".to_string()))
+            }
+        }
+    }
+
+    #[test]
+    fn opens_after_threshold_consecutive_failures() {
+        let breaker = CircuitBreaker::new(3, COOLDOWN, 1);
+
+        for _ in 0..2 {
+            assert!(breaker.try_acquire());
+            breaker.record_failure(&transport_error());
+        }
+        assert_eq!(breaker.status().state, CircuitState::Closed);
+
+        assert!(breaker.try_acquire());
+        breaker.record_failure(&transport_error());
+
+        let status = breaker.status();
+        assert_eq!(status.state, CircuitState::Open);
+        assert_eq!(status.consecutive_failures, 3);
+        assert!(breaker.is_open());
+        assert!(!breaker.try_acquire());
+    }
+
+    #[test]
+    fn success_resets_the_failure_count() {
+        let breaker = CircuitBreaker::new(2, COOLDOWN, 1);
+
+        breaker.record_failure(&transport_error());
+        breaker.record_success();
+        breaker.record_failure(&transport_error());
+
+        assert_eq!(breaker.status().state, CircuitState::Closed);
+        assert_eq!(breaker.status().consecutive_failures, 1);
+    }
+
+    #[test]
+    fn half_open_trial_success_closes_the_circuit() {
+        let breaker = CircuitBreaker::new(1, COOLDOWN, 1);
+        breaker.record_failure(&transport_error());
+        assert!(!breaker.try_acquire());
+
+        std::thread::sleep(COOLDOWN * 2);
+        assert!(!breaker.is_open());
+
+        assert!(breaker.try_acquire());
+        assert_eq!(breaker.status().state, CircuitState::HalfOpen);
+        // Only one trial call at a time
+        assert!(!breaker.try_acquire());
+        assert!(breaker.is_open());
+
+        breaker.record_success();
+        let status = breaker.status();
+        assert_eq!(status.state, CircuitState::Closed);
+        assert_eq!(status.consecutive_failures, 0);
+        assert!(status.last_success_at.is_some());
+        assert!(breaker.try_acquire());
+    }
+
+    #[test]
+    fn half_open_trial_failure_reopens_the_circuit() {
+        let breaker = CircuitBreaker::new(1, COOLDOWN, 1);
+        breaker.record_failure(&transport_error());
+
+        std::thread::sleep(COOLDOWN * 2);
+        assert!(breaker.try_acquire());
+        breaker.record_failure(&transport_error());
+
+        assert_eq!(breaker.status().state, CircuitState::Open);
+        assert!(!breaker.try_acquire());
+    }
+
+    #[test]
+    fn release_frees_a_half_open_slot() {
+        let breaker = CircuitBreaker::new(1, COOLDOWN, 1);
+        breaker.record_failure(&transport_error());
+
+        std::thread::sleep(COOLDOWN * 2);
+        assert!(breaker.try_acquire());
+        breaker.release();
+
+        assert_eq!(breaker.status().state, CircuitState::HalfOpen);
+        assert!(breaker.try_acquire());
+    }
+
+    #[tokio::test]
+    async fn analyzer_opens_on_retryable_errors_and_fails_fast() {
+        let inner = Arc::new(FailingAnalyzer { retryable: true, calls: AtomicUsize::new(0) });
+        let breaker = Arc::new(CircuitBreaker::new(2, Duration::from_secs(60), 1));
+        let analyzer = CircuitBreakerAnalyzer::new(inner.clone(), breaker.clone());
+
+        for _ in 0..2 {
+            let result = analyzer.analyze("", "cv.txt", Some("text"), "model").await;
+            assert!(matches!(result, Err(NlpError::Transport(_))));
+        }
+        let result = analyzer.analyze("", "cv.txt", Some("text"), "model").await;
+
+        assert!(matches!(result, Err(NlpError::CircuitOpen)));
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn non_retryable_errors_release_the_half_open_slot() {
+        let inner = Arc::new(FailingAnalyzer { retryable: false, calls: AtomicUsize::new(0) });
+        let breaker = Arc::new(CircuitBreaker::new(1, COOLDOWN, 1));
+        breaker.record_failure(&transport_error());
+        std::thread::sleep(COOLDOWN * 2);
+        let analyzer = CircuitBreakerAnalyzer::new(inner, breaker.clone());
+
+        let result = analyzer.analyze("", "cv.txt", Some("text"), "model").await;
+
+        assert!(matches!(result, Err(NlpError::InvalidSchema(_))));
+        let status = breaker.status();
+        assert_eq!(status.state, CircuitState::HalfOpen);
+        assert_eq!(status.consecutive_failures, 1);
+        assert!(breaker.try_acquire());
+    }
+
+    #[tokio::test]
+    async fn stub_provider_is_deterministic_and_closes_the_circuit() {
+        let breaker = Arc::new(CircuitBreaker::new(1, COOLDOWN, 1));
+        breaker.record_failure(&transport_error());
+        std::thread::sleep(COOLDOWN * 2);
+        let analyzer = CircuitBreakerAnalyzer::new(Arc::new(StubAnalyzer), breaker.clone());
+
+        let first = analyzer.analyze("", "cv.txt", Some("Jane Doe"), "stub-model").await.unwrap();
+        let second = analyzer.analyze("", "cv.txt", Some("Jane Doe"), "stub-model").await.unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(first.summary.as_deref(), Some("Stub analysis of cv.txt (8 bytes) using stub-model"));
+        assert_eq!(breaker.status().state, CircuitState::Closed);
+    }
+}
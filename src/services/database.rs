@@ -1,9 +1,9 @@
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
-use sqlx::{Pool, Postgres};
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::{Pool, Postgres, Transaction};
 use uuid::Uuid;
 
-use crate::models::{resume::Resume, users::{User, UserRole}};
+use crate::{services::extraction::ExtractedDocument, models::{analysis::ResumeAnalysis, batch::{BatchFileResult, BatchProgress, ResumeBatch}, ats::AtsReport, embedding::ResumeEmbedding, job::{JobDescription, JobMatchScore, Seniority}, analysis_result::AnalysisResult, resume::{AnalysisStatus, Resume, ResumeDocument}, pipeline::{PipelineStage, PipelineStageCount, StageTransition}, quota::{Quota, QuotaUsage}, retention::{RetentionAction, RetentionCandidate, RetentionPolicy, RetentionSummary}, skill::{Skill, SkillAlias}, tag::{ResumeNote, ResumeTag, Tag}, search::{ResumeSearchHit, SearchMode}, share::{ShareAccess, ShareLink}, users::{User, UserRole}}};
 
 #[derive(Debug, Clone)]
 pub struct DBClient {
@@ -16,6 +16,34 @@ impl DBClient {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct NewResumeParams {
+    pub file_path: String,
+    pub file_name: String,
+    pub content_hash: String,
+    pub file_size: i64,
+    /// Document to add the upload to as its next version, a new one is created otherwise
+    pub document_id: Option<Uuid>,
+    pub batch_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct QuotaParams {
+    pub max_resumes: Option<i32>,
+    pub max_storage_bytes: Option<i64>,
+    pub max_analyses_per_day: Option<i32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewShareLinkParams {
+    pub token_hash: String,
+    pub token_prefix: String,
+    pub password_hash: Option<String>,
+    pub redacted: bool,
+    pub expires_at: DateTime<Utc>,
+    pub max_views: Option<i32>,
+}
+
 #[async_trait]
 pub trait UserActions {
     async fn get_user(
@@ -53,67 +81,169 @@ pub trait UserActions {
         password: String,
     ) -> Result<User, sqlx::Error>;
 
-    async fn verifed_token(&self, token: &str) -> Result<(), sqlx::Error>;
-
-    async fn add_verifed_token(
-        &self,
-        user_id: Uuid,
-        token: &str,
-        expires_at: DateTime<Utc>,
-    ) -> Result<(), sqlx::Error>;
-
-    async fn save_resume<T: Into<String> + Send>(
+    async fn save_resume(
         &self,
         user_id: Uuid,
-        file_path: T,
-        analysis_result: Option<serde_json::Value>,
+        params: &NewResumeParams,
+        document: Option<&ExtractedDocument>,
     ) -> Result<Resume, sqlx::Error>;
 
+    async fn count_file_references(&self, file_path: &str) -> Result<i64, sqlx::Error>;
+
+    /// Takes the lock `save_resume` holds while inserting a resume with this content. It is
+    /// released when the returned transaction ends.
+    async fn lock_content(&self, content_hash: &str) -> Result<Transaction<'static, Postgres>, sqlx::Error>;
+
     async fn get_resume(
         &self,
         user_id: Option<Uuid>,
         resume_id: Option<Uuid>,
     ) -> Result<Option<Resume>, sqlx::Error>;
 
-    async fn delete_resume(
-        &self,
-        user_id: Option<Uuid>,
-        resume_id: Option<Uuid>,
-    ) -> Result<(), sqlx::Error>;
-
-    async fn get_resumes(
+    async fn get_resumes_by_ids(
         &self,
         user_id: Uuid,
-        page: u32,
-        limit: usize,
+        resume_ids: &[Uuid],
     ) -> Result<Vec<Resume>, sqlx::Error>;
 
 }
 
 #[async_trait]
 impl UserActions for DBClient {
-    async fn save_resume<T: Into<String> + Send>(
+    async fn save_resume(
         &self,
         user_id: Uuid,
-        file_path: T,
-        analysis_result: Option<serde_json::Value>,
+        params: &NewResumeParams,
+        document: Option<&ExtractedDocument>,
     ) -> Result<Resume, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        // Serializes with release_file, so a purge counting references to this content either
+        // sees the new row or has already deleted the file before it is inserted
+        sqlx::query!(r#"SELECT pg_advisory_xact_lock(hashtext($1))"#, params.content_hash)
+            .execute(&mut *tx)
+            .await?;
+
+        // Locking the document keeps concurrent uploads from taking the same version number
+        let document_id = match params.document_id {
+            Some(document_id) => sqlx::query_scalar!(
+                r#"SELECT id FROM resume_documents WHERE id = $1 AND user_id = $2 FOR UPDATE"#,
+                document_id,
+                user_id
+            )
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?,
+            None => {
+                sqlx::query_scalar!(
+                    r#"INSERT INTO resume_documents (user_id, title) VALUES ($1, $2) RETURNING id"#,
+                    user_id,
+                    params.file_name
+                )
+                .fetch_one(&mut *tx)
+                .await?
+            }
+        };
+
         let resume = sqlx::query_as!(
             Resume,
             r#"
-            INSERT INTO resumes (user_id, file_path, analysis_result)
-            VALUES ($1, $2, $3::jsonb)
-            RETURNING id, user_id, file_path, analysis_result, uploaded_at
+            INSERT INTO resumes (user_id, file_path, document_format, extracted_text, page_count, word_count, language, document_id, version, file_name, content_hash, batch_id, file_size, stage_id, stage_changed_at)
+            VALUES (
+                $1, $2, $3, $4, $5, $6, $7, $8, (SELECT COALESCE(MAX(version), 0) + 1 FROM resumes WHERE document_id = $8), $9, $10, $11, $12,
+                COALESCE(
+                    (SELECT r.stage_id FROM resume_documents d JOIN resumes r ON r.id = d.current_resume_id WHERE d.id = $8),
+                    (SELECT id FROM pipeline_stages ORDER BY position, name LIMIT 1)
+                ),
+                COALESCE(
+                    (SELECT r.stage_changed_at FROM resume_documents d JOIN resumes r ON r.id = d.current_resume_id WHERE d.id = $8),
+                    NOW()
+                )
+            )
+            RETURNING id, user_id, file_path, analysis_result, uploaded_at, latest_analysis_id, analysis_status as "analysis_status: AnalysisStatus", analysis_schema_version, document_format, extracted_text, page_count, word_count, language, ats_report, document_id, version, file_name, content_hash, stage_id, stage_changed_at, deleted_at, anonymized_at
             "#,
             user_id,
-            file_path.into(),
-            analysis_result
+            params.file_path,
+            document.map(|d| d.format.to_str().to_string()),
+            document.map(|d| d.text.clone()),
+            document.and_then(|d| d.page_count),
+            document.map(|d| d.word_count),
+            document.and_then(|d| d.language.clone()),
+            document_id,
+            params.file_name,
+            params.content_hash,
+            params.batch_id,
+            params.file_size
         )
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        // A new version keeps the tags and pipeline stage of the one it replaces,
+        // a new candidate starts the timeline in the first stage
+        if params.document_id.is_none() {
+            sqlx::query!(
+                r#"
+                INSERT INTO resume_stage_history (resume_id, to_stage_id, to_stage, changed_by)
+                SELECT $1, id, name, $3 FROM pipeline_stages WHERE id = $2
+                "#,
+                resume.id,
+                resume.stage_id,
+                user_id
+            )
+            .execute(&mut *tx)
+            .await?;
+        } else {
+            sqlx::query!(
+                r#"
+                INSERT INTO resume_tags (resume_id, tag_id)
+                SELECT $1, rt.tag_id
+                FROM resume_tags rt
+                JOIN resume_documents d ON d.current_resume_id = rt.resume_id
+                WHERE d.id = $2
+                ON CONFLICT DO NOTHING
+                "#,
+                resume.id,
+                document_id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        // A new upload becomes the current version, the user can point back at an older one
+        sqlx::query!(
+            r#"UPDATE resume_documents SET current_resume_id = $1, updated_at = NOW() WHERE id = $2"#,
+            resume.id,
+            document_id
+        )
+        .execute(&mut *tx)
         .await?;
+
+        tx.commit().await?;
+
         Ok(resume)
     }
 
+    async fn count_file_references(&self, file_path: &str) -> Result<i64, sqlx::Error> {
+        let count = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) FROM resumes WHERE file_path = $1"#,
+            file_path
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count.unwrap_or(0))
+    }
+
+    async fn lock_content(&self, content_hash: &str) -> Result<Transaction<'static, Postgres>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!(r#"SELECT pg_advisory_xact_lock(hashtext($1))"#, content_hash)
+            .execute(&mut *tx)
+            .await?;
+
+        Ok(tx)
+    }
+
     async fn get_resume(
         &self,
         user_id: Option<Uuid>,
@@ -123,9 +253,9 @@ impl UserActions for DBClient {
             let resume = sqlx::query_as!(
                 Resume,
                 r#"
-                SELECT id, user_id, file_path, analysis_result, uploaded_at
+                SELECT id, user_id, file_path, analysis_result, uploaded_at, latest_analysis_id, analysis_status as "analysis_status: AnalysisStatus", analysis_schema_version, document_format, extracted_text, page_count, word_count, language, ats_report, document_id, version, file_name, content_hash, stage_id, stage_changed_at, deleted_at, anonymized_at
                 FROM resumes
-                WHERE id = $1 AND user_id = $2
+                WHERE id = $1 AND user_id = $2 AND deleted_at IS NULL
                 "#,
                 resume_id,
                 user_id
@@ -139,46 +269,20 @@ impl UserActions for DBClient {
         }
     }
 
-    async fn delete_resume(
-        &self,
-        user_id: Option<Uuid>,
-        resume_id: Option<Uuid>
-    ) -> Result<(), sqlx::Error> {
-        if let (Some(user_id), Some(resume_id)) = (user_id, resume_id) {
-            let _ = sqlx::query!(
-                r#"
-                DELETE FROM resumes
-                WHERE id = $1 AND user_id = $2
-                "#,
-                resume_id,
-                user_id
-            )
-            .execute(&self.pool)
-            .await?;
-        }
-        Ok(())
-    }
-
-    async fn get_resumes(
+    async fn get_resumes_by_ids(
         &self,
         user_id: Uuid,
-        page: u32,
-        limit: usize,
+        resume_ids: &[Uuid],
     ) -> Result<Vec<Resume>, sqlx::Error> {
-        let offset = (page - 1) * limit as u32;
-
         let resumes = sqlx::query_as!(
             Resume,
             r#"
-            SELECT id, user_id, file_path, analysis_result, uploaded_at
+            SELECT id, user_id, file_path, analysis_result, uploaded_at, latest_analysis_id, analysis_status as "analysis_status: AnalysisStatus", analysis_schema_version, document_format, extracted_text, page_count, word_count, language, ats_report, document_id, version, file_name, content_hash, stage_id, stage_changed_at, deleted_at, anonymized_at
             FROM resumes
-            WHERE user_id = $1
-            ORDER BY uploaded_at DESC
-            LIMIT $2 OFFSET $3
+            WHERE user_id = $1 AND id = ANY($2) AND deleted_at IS NULL
             "#,
             user_id,
-            limit as i64,
-            offset as i64
+            resume_ids
         )
         .fetch_all(&self.pool)
         .await?;
@@ -340,43 +444,2779 @@ impl UserActions for DBClient {
         Ok(user)
     }
 
-    async fn verifed_token(&self, token: &str) -> Result<(), sqlx::Error> {
-        let _ = sqlx::query!(
+}
+
+#[async_trait]
+pub trait AnalysisActions {
+    async fn save_analysis<T: Into<String> + Send>(
+        &self,
+        resume_id: Uuid,
+        analysis_result: &AnalysisResult,
+        model: T,
+        model_version: T,
+        started_at: DateTime<Utc>,
+        duration_ms: i64,
+    ) -> Result<ResumeAnalysis, sqlx::Error>;
+
+    async fn get_analyses(
+        &self,
+        resume_id: Uuid,
+        page: u32,
+        limit: usize,
+    ) -> Result<Vec<ResumeAnalysis>, sqlx::Error>;
+
+    async fn get_analysis_count(&self, resume_id: Uuid) -> Result<i64, sqlx::Error>;
+
+    async fn get_resume_ids_after(&self, after: Option<Uuid>, limit: i64) -> Result<Vec<(Uuid, Uuid)>, sqlx::Error>;
+
+    async fn update_analysis_status(
+        &self,
+        resume_id: Uuid,
+        status: AnalysisStatus,
+    ) -> Result<(), sqlx::Error>;
+
+    async fn get_pending_resumes(&self, limit: usize) -> Result<Vec<Resume>, sqlx::Error>;
+
+    async fn save_ats_report(&self, resume_id: Uuid, report: &AtsReport) -> Result<(), sqlx::Error>;
+
+    async fn get_cached_analysis(
+        &self,
+        content_hash: &str,
+        model: &str,
+        model_version: &str,
+    ) -> Result<Option<ResumeAnalysis>, sqlx::Error>;
+}
+
+#[async_trait]
+impl AnalysisActions for DBClient {
+    async fn save_analysis<T: Into<String> + Send>(
+        &self,
+        resume_id: Uuid,
+        analysis_result: &AnalysisResult,
+        model: T,
+        model_version: T,
+        started_at: DateTime<Utc>,
+        duration_ms: i64,
+    ) -> Result<ResumeAnalysis, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let analysis = sqlx::query_as!(
+            ResumeAnalysis,
+            r#"
+            INSERT INTO resume_analyses (resume_id, analysis_result, schema_version, model, model_version, started_at, duration_ms)
+            VALUES ($1, $2::jsonb, $3, $4, $5, $6, $7)
+            RETURNING id, resume_id, analysis_result, schema_version, model, model_version, started_at, completed_at, duration_ms
+            "#,
+            resume_id,
+            analysis_result.to_value(),
+            analysis_result.schema_version,
+            model.into(),
+            model_version.into(),
+            started_at,
+            duration_ms
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE resumes
+            SET analysis_result = $1, analysis_schema_version = $2, latest_analysis_id = $3, analysis_status = 'completed'
+            WHERE id = $4
+            "#,
+            analysis.analysis_result,
+            analysis.schema_version,
+            analysis.id,
+            resume_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        // Embeddings include the analysis, recompute them lazily from the new result
+        sqlx::query!(
+            r#"DELETE FROM resume_embeddings WHERE resume_id = $1"#,
+            resume_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(analysis)
+    }
+
+    async fn get_analyses(
+        &self,
+        resume_id: Uuid,
+        page: u32,
+        limit: usize,
+    ) -> Result<Vec<ResumeAnalysis>, sqlx::Error> {
+        let offset = (page - 1) * limit as u32;
+
+        let analyses = sqlx::query_as!(
+            ResumeAnalysis,
             r#"
-            UPDATE users
-            SET verified = true, 
-                updated_at = Now(),
-                verification_token = NULL,
-                token_expires_at = NULL
-            WHERE verification_token = $1
+            SELECT id, resume_id, analysis_result, schema_version, model, model_version, started_at, completed_at, duration_ms
+            FROM resume_analyses
+            WHERE resume_id = $1
+            ORDER BY completed_at DESC
+            LIMIT $2 OFFSET $3
+            "#,
+            resume_id,
+            limit as i64,
+            offset as i64
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(analyses)
+    }
+
+    async fn get_analysis_count(&self, resume_id: Uuid) -> Result<i64, sqlx::Error> {
+        let count = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) FROM resume_analyses WHERE resume_id = $1"#,
+            resume_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count.unwrap_or(0))
+    }
+
+    async fn get_resume_ids_after(&self, after: Option<Uuid>, limit: i64) -> Result<Vec<(Uuid, Uuid)>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id, user_id
+            FROM resumes
+            WHERE deleted_at IS NULL AND anonymized_at IS NULL AND ($1::uuid IS NULL OR id > $1)
+            ORDER BY id
+            LIMIT $2
+            "#,
+            after,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|row| (row.id, row.user_id)).collect())
+    }
+
+    async fn update_analysis_status(
+        &self,
+        resume_id: Uuid,
+        status: AnalysisStatus,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            UPDATE resumes
+            SET analysis_status = $1
+            WHERE id = $2
+            "#,
+            status as AnalysisStatus,
+            resume_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn get_pending_resumes(&self, limit: usize) -> Result<Vec<Resume>, sqlx::Error> {
+        let resumes = sqlx::query_as!(
+            Resume,
+            r#"
+            SELECT id, user_id, file_path, analysis_result, uploaded_at, latest_analysis_id, analysis_status as "analysis_status: AnalysisStatus", analysis_schema_version, document_format, extracted_text, page_count, word_count, language, ats_report, document_id, version, file_name, content_hash, stage_id, stage_changed_at, deleted_at, anonymized_at
+            FROM resumes
+            WHERE analysis_status = 'pending' AND deleted_at IS NULL AND anonymized_at IS NULL
+                -- Owners out of analyses for the day wait for tomorrow without holding up everyone else
+                AND NOT EXISTS (
+                    SELECT 1
+                    FROM users u
+                    JOIN LATERAL (
+                        SELECT max_analyses_per_day FROM quotas q WHERE q.user_id = u.id OR q.role = u.role ORDER BY q.user_id NULLS LAST LIMIT 1
+                    ) q ON TRUE
+                    JOIN analysis_usage a ON a.user_id = u.id AND a.day = (NOW() AT TIME ZONE 'UTC')::DATE
+                    WHERE u.id = resumes.user_id AND a.analyses >= q.max_analyses_per_day
+                )
+            ORDER BY uploaded_at ASC
+            LIMIT $1
             "#,
-            token
+            limit as i64
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(resumes)
+    }
+
+    async fn save_ats_report(&self, resume_id: Uuid, report: &AtsReport) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE resumes SET ats_report = $2 WHERE id = $1"#,
+            resume_id,
+            serde_json::to_value(report).unwrap_or_default()
         )
         .execute(&self.pool)
-        .await;
+        .await?;
 
         Ok(())
     }
 
-    async fn add_verifed_token(
+    async fn get_cached_analysis(
+        &self,
+        content_hash: &str,
+        model: &str,
+        model_version: &str,
+    ) -> Result<Option<ResumeAnalysis>, sqlx::Error> {
+        let analysis = sqlx::query_as!(
+            ResumeAnalysis,
+            r#"
+            SELECT a.id, a.resume_id, a.analysis_result, a.schema_version, a.model, a.model_version, a.started_at, a.completed_at, a.duration_ms
+            FROM resume_analyses a
+            JOIN resumes r ON r.id = a.resume_id
+            WHERE r.content_hash = $1 AND a.model = $2 AND a.model_version = $3 AND r.anonymized_at IS NULL
+            ORDER BY a.completed_at DESC
+            LIMIT 1
+            "#,
+            content_hash,
+            model,
+            model_version
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(analysis)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ResumeSearchParams {
+    pub mode: SearchMode,
+    pub query: String,
+    pub skills: Vec<String>,
+    pub uploaded_from: Option<DateTime<Utc>>,
+    pub uploaded_to: Option<DateTime<Utc>>,
+    pub page: u32,
+    pub limit: usize,
+}
+
+#[async_trait]
+pub trait SearchActions {
+    async fn search_resumes(
         &self,
         user_id: Uuid,
-        token: &str,
-        token_expires_at: DateTime<Utc>,
-    ) -> Result<(), sqlx::Error> {
-        let _ = sqlx::query!(
+        params: &ResumeSearchParams,
+    ) -> Result<Vec<ResumeSearchHit>, sqlx::Error>;
+}
+
+#[async_trait]
+impl SearchActions for DBClient {
+    async fn search_resumes(
+        &self,
+        user_id: Uuid,
+        params: &ResumeSearchParams,
+    ) -> Result<Vec<ResumeSearchHit>, sqlx::Error> {
+        let offset = (params.page - 1) * params.limit as u32;
+
+        let hits = sqlx::query_as!(
+            ResumeSearchHit,
             r#"
-            UPDATE users
-            SET verification_token = $1, token_expires_at = $2, updated_at = Now()
-            WHERE id = $3
+            WITH search AS (
+                SELECT CASE $2
+                    WHEN 'phrase' THEN phraseto_tsquery('english', $3)
+                    WHEN 'prefix' THEN to_tsquery('english', $3)
+                    ELSE websearch_to_tsquery('english', $3)
+                END AS query
+            )
+            SELECT
+                r.id,
+                ts_rank_cd(r.search_vector, search.query) AS "rank!",
+                ts_headline(
+                    'english',
+                    coalesce(r.extracted_text, r.analysis_result ->> 'summary', ''),
+                    search.query,
+                    'StartSel=<mark>, StopSel=</mark>, MaxFragments=3, MaxWords=20, MinWords=5'
+                ) AS "snippet!",
+                COUNT(*) OVER () AS "total!"
+            FROM resumes r, search
+            WHERE r.user_id = $1
+                AND r.deleted_at IS NULL
+                AND r.search_vector @@ search.query
+                AND ($4::timestamptz IS NULL OR r.uploaded_at >= $4)
+                AND ($5::timestamptz IS NULL OR r.uploaded_at < $5)
+                AND NOT EXISTS (
+                    SELECT 1 FROM unnest($6::text[]) AS wanted(skill)
+                    WHERE NOT EXISTS (
+                        SELECT 1 FROM jsonb_path_query(r.analysis_result, '$.skills[*]') AS have(skill)
+                        WHERE lower(have.skill #>> '{}') = lower(wanted.skill)
+                    )
+                )
+            ORDER BY 2 DESC, r.uploaded_at DESC
+            LIMIT $7 OFFSET $8
             "#,
-            token,
-            token_expires_at,
             user_id,
+            params.mode.to_str(),
+            params.query,
+            params.uploaded_from,
+            params.uploaded_to,
+            &params.skills,
+            params.limit as i64,
+            offset as i64
         )
-        .execute(&self.pool)
+        .fetch_all(&self.pool)
         .await?;
 
-        Ok(())
+        Ok(hits)
+    }
+}
+
+#[async_trait]
+pub trait DocumentActions {
+    /// `tags` are lowercased names, only documents whose current version carries all of them are listed
+    async fn get_documents(
+        &self,
+        user_id: Uuid,
+        page: u32,
+        limit: usize,
+        tags: &[String],
+    ) -> Result<Vec<ResumeDocument>, sqlx::Error>;
+
+    async fn get_document(
+        &self,
+        user_id: Uuid,
+        document_id: Uuid,
+    ) -> Result<Option<ResumeDocument>, sqlx::Error>;
+
+    async fn get_document_versions(
+        &self,
+        user_id: Uuid,
+        document_id: Uuid,
+    ) -> Result<Vec<Resume>, sqlx::Error>;
+
+    async fn set_current_version(
+        &self,
+        user_id: Uuid,
+        document_id: Uuid,
+        resume_id: Uuid,
+    ) -> Result<bool, sqlx::Error>;
+}
+
+#[async_trait]
+impl DocumentActions for DBClient {
+    async fn get_documents(
+        &self,
+        user_id: Uuid,
+        page: u32,
+        limit: usize,
+        tags: &[String],
+    ) -> Result<Vec<ResumeDocument>, sqlx::Error> {
+        let offset = (page - 1) * limit as u32;
+
+        let documents = sqlx::query_as!(
+            ResumeDocument,
+            r#"
+            SELECT d.id, d.user_id, d.title, d.current_resume_id, COUNT(r.id) AS "version_count!", COALESCE(MAX(r.version), 0) AS "latest_version!", d.created_at, d.updated_at
+            FROM resume_documents d
+            LEFT JOIN resumes r ON r.document_id = d.id AND r.deleted_at IS NULL
+            WHERE d.user_id = $1
+            AND (
+                cardinality($4::TEXT[]) = 0
+                OR (
+                    SELECT COUNT(*)
+                    FROM resume_tags rt
+                    JOIN tags t ON t.id = rt.tag_id
+                    WHERE rt.resume_id = d.current_resume_id AND lower(t.name) = ANY($4)
+                ) = cardinality($4::TEXT[])
+            )
+            GROUP BY d.id
+            HAVING COUNT(r.id) > 0
+            ORDER BY d.updated_at DESC
+            LIMIT $2 OFFSET $3
+            "#,
+            user_id,
+            limit as i64,
+            offset as i64,
+            tags
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(documents)
+    }
+
+    async fn get_document(
+        &self,
+        user_id: Uuid,
+        document_id: Uuid,
+    ) -> Result<Option<ResumeDocument>, sqlx::Error> {
+        let document = sqlx::query_as!(
+            ResumeDocument,
+            r#"
+            SELECT d.id, d.user_id, d.title, d.current_resume_id, COUNT(r.id) AS "version_count!", COALESCE(MAX(r.version), 0) AS "latest_version!", d.created_at, d.updated_at
+            FROM resume_documents d
+            LEFT JOIN resumes r ON r.document_id = d.id AND r.deleted_at IS NULL
+            WHERE d.id = $1 AND d.user_id = $2
+            GROUP BY d.id
+            HAVING COUNT(r.id) > 0
+            "#,
+            document_id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(document)
     }
-}
\ No newline at end of file
+
+    async fn get_document_versions(
+        &self,
+        user_id: Uuid,
+        document_id: Uuid,
+    ) -> Result<Vec<Resume>, sqlx::Error> {
+        let resumes = sqlx::query_as!(
+            Resume,
+            r#"
+            SELECT id, user_id, file_path, analysis_result, uploaded_at, latest_analysis_id, analysis_status as "analysis_status: AnalysisStatus", analysis_schema_version, document_format, extracted_text, page_count, word_count, language, ats_report, document_id, version, file_name, content_hash, stage_id, stage_changed_at, deleted_at, anonymized_at
+            FROM resumes
+            WHERE document_id = $1 AND user_id = $2 AND deleted_at IS NULL
+            ORDER BY version ASC
+            "#,
+            document_id,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(resumes)
+    }
+
+    async fn set_current_version(
+        &self,
+        user_id: Uuid,
+        document_id: Uuid,
+        resume_id: Uuid,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE resume_documents d
+            SET current_resume_id = $3, updated_at = NOW()
+            WHERE d.id = $1 AND d.user_id = $2
+                AND EXISTS (SELECT 1 FROM resumes r WHERE r.id = $3 AND r.document_id = d.id AND r.deleted_at IS NULL)
+            "#,
+            document_id,
+            user_id,
+            resume_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[async_trait]
+pub trait BatchActions {
+    async fn save_batch(&self, user_id: Uuid) -> Result<ResumeBatch, sqlx::Error>;
+
+    async fn finish_batch(
+        &self,
+        batch_id: Uuid,
+        results: &[BatchFileResult],
+    ) -> Result<ResumeBatch, sqlx::Error>;
+
+    async fn get_batch(
+        &self,
+        user_id: Uuid,
+        batch_id: Uuid,
+    ) -> Result<Option<ResumeBatch>, sqlx::Error>;
+
+    async fn get_batch_progress(&self, batch_id: Uuid) -> Result<BatchProgress, sqlx::Error>;
+}
+
+#[async_trait]
+impl BatchActions for DBClient {
+    async fn save_batch(&self, user_id: Uuid) -> Result<ResumeBatch, sqlx::Error> {
+        let batch = sqlx::query_as!(
+            ResumeBatch,
+            r#"
+            INSERT INTO resume_batches (user_id)
+            VALUES ($1)
+            RETURNING id, user_id, file_count, failed_count, results, created_at
+            "#,
+            user_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(batch)
+    }
+
+    async fn finish_batch(
+        &self,
+        batch_id: Uuid,
+        results: &[BatchFileResult],
+    ) -> Result<ResumeBatch, sqlx::Error> {
+        let failed_count = results.iter().filter(|result| result.resume_id.is_none()).count() as i32;
+
+        let batch = sqlx::query_as!(
+            ResumeBatch,
+            r#"
+            UPDATE resume_batches
+            SET file_count = $2, failed_count = $3, results = $4
+            WHERE id = $1
+            RETURNING id, user_id, file_count, failed_count, results, created_at
+            "#,
+            batch_id,
+            results.len() as i32,
+            failed_count,
+            serde_json::to_value(results).unwrap_or_default()
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(batch)
+    }
+
+    async fn get_batch(
+        &self,
+        user_id: Uuid,
+        batch_id: Uuid,
+    ) -> Result<Option<ResumeBatch>, sqlx::Error> {
+        let batch = sqlx::query_as!(
+            ResumeBatch,
+            r#"
+            SELECT id, user_id, file_count, failed_count, results, created_at
+            FROM resume_batches
+            WHERE id = $1 AND user_id = $2
+            "#,
+            batch_id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(batch)
+    }
+
+    async fn get_batch_progress(&self, batch_id: Uuid) -> Result<BatchProgress, sqlx::Error> {
+        let progress = sqlx::query_as!(
+            BatchProgress,
+            r#"
+            SELECT
+                COUNT(*) FILTER (WHERE analysis_status = 'pending') AS "pending!",
+                COUNT(*) FILTER (WHERE analysis_status = 'completed') AS "completed!",
+                COUNT(*) FILTER (WHERE analysis_status = 'failed') AS "failed!"
+            FROM resumes
+            WHERE batch_id = $1 AND deleted_at IS NULL
+            "#,
+            batch_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(progress)
+    }
+}
+
+#[async_trait]
+pub trait TagActions {
+    async fn get_tags(&self, user_id: Uuid) -> Result<Vec<Tag>, sqlx::Error>;
+
+    async fn get_tag(&self, user_id: Uuid, tag_id: Uuid) -> Result<Option<Tag>, sqlx::Error>;
+
+    async fn save_tag(&self, user_id: Uuid, name: &str, colour: &str) -> Result<Tag, sqlx::Error>;
+
+    async fn update_tag(
+        &self,
+        user_id: Uuid,
+        tag_id: Uuid,
+        name: Option<&str>,
+        colour: Option<&str>,
+    ) -> Result<Option<Tag>, sqlx::Error>;
+
+    async fn delete_tag(&self, user_id: Uuid, tag_id: Uuid) -> Result<bool, sqlx::Error>;
+
+    async fn get_tags_for_resumes(
+        &self,
+        user_id: Uuid,
+        resume_ids: &[Uuid],
+    ) -> Result<Vec<ResumeTag>, sqlx::Error>;
+
+    /// Applies every tag to every resume, pairs that are already tagged are left alone.
+    async fn tag_resumes(
+        &self,
+        user_id: Uuid,
+        resume_ids: &[Uuid],
+        tag_ids: &[Uuid],
+    ) -> Result<u64, sqlx::Error>;
+
+    async fn untag_resumes(
+        &self,
+        user_id: Uuid,
+        resume_ids: &[Uuid],
+        tag_ids: &[Uuid],
+    ) -> Result<u64, sqlx::Error>;
+
+    async fn get_notes(&self, resume_id: Uuid) -> Result<Vec<ResumeNote>, sqlx::Error>;
+
+    async fn save_note(
+        &self,
+        resume_id: Uuid,
+        author_id: Uuid,
+        body: &str,
+    ) -> Result<ResumeNote, sqlx::Error>;
+
+    async fn update_note(
+        &self,
+        resume_id: Uuid,
+        note_id: Uuid,
+        author_id: Uuid,
+        body: &str,
+    ) -> Result<Option<ResumeNote>, sqlx::Error>;
+
+    async fn delete_note(
+        &self,
+        resume_id: Uuid,
+        note_id: Uuid,
+        author_id: Uuid,
+    ) -> Result<bool, sqlx::Error>;
+}
+
+#[async_trait]
+impl TagActions for DBClient {
+    async fn get_tags(&self, user_id: Uuid) -> Result<Vec<Tag>, sqlx::Error> {
+        let tags = sqlx::query_as!(
+            Tag,
+            r#"
+            SELECT t.id, t.user_id, t.name, t.colour, COUNT(r.id) AS "resume_count!", t.created_at, t.updated_at
+            FROM tags t
+            LEFT JOIN resume_tags rt ON rt.tag_id = t.id
+            LEFT JOIN resumes r ON r.id = rt.resume_id AND r.deleted_at IS NULL
+            WHERE t.user_id = $1
+            GROUP BY t.id
+            ORDER BY lower(t.name)
+            "#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(tags)
+    }
+
+    async fn get_tag(&self, user_id: Uuid, tag_id: Uuid) -> Result<Option<Tag>, sqlx::Error> {
+        let tag = sqlx::query_as!(
+            Tag,
+            r#"
+            SELECT t.id, t.user_id, t.name, t.colour, COUNT(r.id) AS "resume_count!", t.created_at, t.updated_at
+            FROM tags t
+            LEFT JOIN resume_tags rt ON rt.tag_id = t.id
+            LEFT JOIN resumes r ON r.id = rt.resume_id AND r.deleted_at IS NULL
+            WHERE t.id = $1 AND t.user_id = $2
+            GROUP BY t.id
+            "#,
+            tag_id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(tag)
+    }
+
+    async fn save_tag(&self, user_id: Uuid, name: &str, colour: &str) -> Result<Tag, sqlx::Error> {
+        let tag = sqlx::query_as!(
+            Tag,
+            r#"
+            INSERT INTO tags (user_id, name, colour)
+            VALUES ($1, $2, $3)
+            RETURNING id, user_id, name, colour, 0::BIGINT AS "resume_count!", created_at, updated_at
+            "#,
+            user_id,
+            name,
+            colour
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(tag)
+    }
+
+    async fn update_tag(
+        &self,
+        user_id: Uuid,
+        tag_id: Uuid,
+        name: Option<&str>,
+        colour: Option<&str>,
+    ) -> Result<Option<Tag>, sqlx::Error> {
+        let tag = sqlx::query_as!(
+            Tag,
+            r#"
+            UPDATE tags
+            SET name = COALESCE($3, name), colour = COALESCE($4, colour), updated_at = NOW()
+            WHERE id = $1 AND user_id = $2
+            RETURNING id, user_id, name, colour, (SELECT COUNT(*) FROM resume_tags rt JOIN resumes r ON r.id = rt.resume_id WHERE rt.tag_id = tags.id AND r.deleted_at IS NULL) AS "resume_count!", created_at, updated_at
+            "#,
+            tag_id,
+            user_id,
+            name,
+            colour
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(tag)
+    }
+
+    async fn delete_tag(&self, user_id: Uuid, tag_id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"DELETE FROM tags WHERE id = $1 AND user_id = $2"#,
+            tag_id,
+            user_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn get_tags_for_resumes(
+        &self,
+        user_id: Uuid,
+        resume_ids: &[Uuid],
+    ) -> Result<Vec<ResumeTag>, sqlx::Error> {
+        let tags = sqlx::query_as!(
+            ResumeTag,
+            r#"
+            SELECT rt.resume_id, t.id, t.name, t.colour, rt.tagged_at
+            FROM resume_tags rt
+            JOIN tags t ON t.id = rt.tag_id
+            WHERE t.user_id = $1 AND rt.resume_id = ANY($2)
+            ORDER BY lower(t.name)
+            "#,
+            user_id,
+            resume_ids
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(tags)
+    }
+
+    async fn tag_resumes(
+        &self,
+        user_id: Uuid,
+        resume_ids: &[Uuid],
+        tag_ids: &[Uuid],
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO resume_tags (resume_id, tag_id)
+            SELECT r.id, t.id
+            FROM resumes r
+            CROSS JOIN tags t
+            WHERE r.user_id = $1 AND r.id = ANY($2) AND r.deleted_at IS NULL AND t.user_id = $1 AND t.id = ANY($3)
+            ON CONFLICT DO NOTHING
+            "#,
+            user_id,
+            resume_ids,
+            tag_ids
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn untag_resumes(
+        &self,
+        user_id: Uuid,
+        resume_ids: &[Uuid],
+        tag_ids: &[Uuid],
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM resume_tags rt
+            USING tags t
+            WHERE t.id = rt.tag_id AND t.user_id = $1 AND rt.resume_id = ANY($2) AND rt.tag_id = ANY($3)
+            "#,
+            user_id,
+            resume_ids,
+            tag_ids
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn get_notes(&self, resume_id: Uuid) -> Result<Vec<ResumeNote>, sqlx::Error> {
+        let notes = sqlx::query_as!(
+            ResumeNote,
+            r#"
+            SELECT n.id, n.resume_id, n.author_id, u.name AS author_name, n.body, n.created_at, n.updated_at
+            FROM resume_notes n
+            JOIN users u ON u.id = n.author_id
+            WHERE n.resume_id = $1
+            ORDER BY n.created_at
+            "#,
+            resume_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(notes)
+    }
+
+    async fn save_note(
+        &self,
+        resume_id: Uuid,
+        author_id: Uuid,
+        body: &str,
+    ) -> Result<ResumeNote, sqlx::Error> {
+        let note = sqlx::query_as!(
+            ResumeNote,
+            r#"
+            WITH n AS (
+                INSERT INTO resume_notes (resume_id, author_id, body)
+                VALUES ($1, $2, $3)
+                RETURNING id, resume_id, author_id, body, created_at, updated_at
+            )
+            SELECT n.id AS "id!", n.resume_id AS "resume_id!", n.author_id AS "author_id!", u.name AS author_name, n.body AS "body!", n.created_at, n.updated_at
+            FROM n
+            JOIN users u ON u.id = n.author_id
+            "#,
+            resume_id,
+            author_id,
+            body
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(note)
+    }
+
+    async fn update_note(
+        &self,
+        resume_id: Uuid,
+        note_id: Uuid,
+        author_id: Uuid,
+        body: &str,
+    ) -> Result<Option<ResumeNote>, sqlx::Error> {
+        let note = sqlx::query_as!(
+            ResumeNote,
+            r#"
+            WITH n AS (
+                UPDATE resume_notes
+                SET body = $4, updated_at = NOW()
+                WHERE id = $1 AND resume_id = $2 AND author_id = $3
+                RETURNING id, resume_id, author_id, body, created_at, updated_at
+            )
+            SELECT n.id AS "id!", n.resume_id AS "resume_id!", n.author_id AS "author_id!", u.name AS author_name, n.body AS "body!", n.created_at, n.updated_at
+            FROM n
+            JOIN users u ON u.id = n.author_id
+            "#,
+            note_id,
+            resume_id,
+            author_id,
+            body
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(note)
+    }
+
+    async fn delete_note(
+        &self,
+        resume_id: Uuid,
+        note_id: Uuid,
+        author_id: Uuid,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"DELETE FROM resume_notes WHERE id = $1 AND resume_id = $2 AND author_id = $3"#,
+            note_id,
+            resume_id,
+            author_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[async_trait]
+pub trait PipelineActions {
+    async fn get_stages(&self) -> Result<Vec<PipelineStage>, sqlx::Error>;
+
+    async fn get_stage(&self, stage_id: Uuid) -> Result<Option<PipelineStage>, sqlx::Error>;
+
+    /// Without a position the stage is added after the last one
+    async fn save_stage(
+        &self,
+        name: &str,
+        position: Option<i32>,
+        terminal: bool,
+    ) -> Result<PipelineStage, sqlx::Error>;
+
+    async fn update_stage(
+        &self,
+        stage_id: Uuid,
+        name: Option<&str>,
+        position: Option<i32>,
+        terminal: Option<bool>,
+    ) -> Result<Option<PipelineStage>, sqlx::Error>;
+
+    async fn delete_stage(&self, stage_id: Uuid) -> Result<bool, sqlx::Error>;
+
+    /// Moves a resume and records the transition, `None` if the resume isn't the user's
+    async fn move_resume_stage(
+        &self,
+        user_id: Uuid,
+        resume_id: Uuid,
+        stage_id: Uuid,
+        note: Option<&str>,
+    ) -> Result<Option<StageTransition>, sqlx::Error>;
+
+    /// Transitions of every version of the resume's document, oldest first
+    async fn get_stage_timeline(
+        &self,
+        user_id: Uuid,
+        resume_id: Uuid,
+    ) -> Result<Vec<StageTransition>, sqlx::Error>;
+
+    /// Current versions for the pipeline board, the `per_stage` longest in each stage
+    async fn get_pipeline_resume_ids(
+        &self,
+        user_id: Uuid,
+        per_stage: i64,
+        tags: &[String],
+    ) -> Result<Vec<Uuid>, sqlx::Error>;
+
+    async fn get_pipeline_counts(
+        &self,
+        user_id: Uuid,
+        tags: &[String],
+    ) -> Result<Vec<PipelineStageCount>, sqlx::Error>;
+}
+
+#[async_trait]
+impl PipelineActions for DBClient {
+    async fn get_stages(&self) -> Result<Vec<PipelineStage>, sqlx::Error> {
+        let stages = sqlx::query_as!(
+            PipelineStage,
+            r#"
+            SELECT id, name, position, terminal, created_at, updated_at
+            FROM pipeline_stages
+            ORDER BY position, name
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(stages)
+    }
+
+    async fn get_stage(&self, stage_id: Uuid) -> Result<Option<PipelineStage>, sqlx::Error> {
+        let stage = sqlx::query_as!(
+            PipelineStage,
+            r#"
+            SELECT id, name, position, terminal, created_at, updated_at
+            FROM pipeline_stages
+            WHERE id = $1
+            "#,
+            stage_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(stage)
+    }
+
+    async fn save_stage(
+        &self,
+        name: &str,
+        position: Option<i32>,
+        terminal: bool,
+    ) -> Result<PipelineStage, sqlx::Error> {
+        let stage = sqlx::query_as!(
+            PipelineStage,
+            r#"
+            INSERT INTO pipeline_stages (name, position, terminal)
+            VALUES ($1, COALESCE($2, (SELECT COALESCE(MAX(position), -1) + 1 FROM pipeline_stages)), $3)
+            RETURNING id, name, position, terminal, created_at, updated_at
+            "#,
+            name,
+            position,
+            terminal
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(stage)
+    }
+
+    async fn update_stage(
+        &self,
+        stage_id: Uuid,
+        name: Option<&str>,
+        position: Option<i32>,
+        terminal: Option<bool>,
+    ) -> Result<Option<PipelineStage>, sqlx::Error> {
+        let stage = sqlx::query_as!(
+            PipelineStage,
+            r#"
+            UPDATE pipeline_stages
+            SET name = COALESCE($2, name), position = COALESCE($3, position), terminal = COALESCE($4, terminal), updated_at = NOW()
+            WHERE id = $1
+            RETURNING id, name, position, terminal, created_at, updated_at
+            "#,
+            stage_id,
+            name,
+            position,
+            terminal
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(stage)
+    }
+
+    async fn delete_stage(&self, stage_id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"DELETE FROM pipeline_stages WHERE id = $1"#,
+            stage_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn move_resume_stage(
+        &self,
+        user_id: Uuid,
+        resume_id: Uuid,
+        stage_id: Uuid,
+        note: Option<&str>,
+    ) -> Result<Option<StageTransition>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let from = sqlx::query!(
+            r#"
+            SELECT r.stage_id, s.name
+            FROM resumes r
+            JOIN pipeline_stages s ON s.id = r.stage_id
+            WHERE r.id = $1 AND r.user_id = $2 AND r.deleted_at IS NULL
+            FOR UPDATE OF r
+            "#,
+            resume_id,
+            user_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let Some(from) = from else {
+            return Ok(None);
+        };
+
+        sqlx::query!(
+            r#"UPDATE resumes SET stage_id = $2, stage_changed_at = NOW() WHERE id = $1"#,
+            resume_id,
+            stage_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let transition = sqlx::query_as!(
+            StageTransition,
+            r#"
+            WITH h AS (
+                INSERT INTO resume_stage_history (resume_id, from_stage_id, from_stage, to_stage_id, to_stage, changed_by, note)
+                SELECT $1, $2, $3, s.id, s.name, $5, $6
+                FROM pipeline_stages s
+                WHERE s.id = $4
+                RETURNING id, resume_id, from_stage_id, from_stage, to_stage_id, to_stage, changed_by, note, changed_at
+            )
+            SELECT h.id AS "id!", h.resume_id AS "resume_id!", r.version, h.from_stage_id, h.from_stage, h.to_stage_id, h.to_stage AS "to_stage!", h.changed_by, u.name AS "changed_by_name?", h.note, h.changed_at
+            FROM h
+            JOIN resumes r ON r.id = h.resume_id
+            LEFT JOIN users u ON u.id = h.changed_by
+            "#,
+            resume_id,
+            from.stage_id,
+            from.name,
+            stage_id,
+            user_id,
+            note
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(Some(transition))
+    }
+
+    async fn get_stage_timeline(
+        &self,
+        user_id: Uuid,
+        resume_id: Uuid,
+    ) -> Result<Vec<StageTransition>, sqlx::Error> {
+        let timeline = sqlx::query_as!(
+            StageTransition,
+            r#"
+            SELECT h.id, h.resume_id, r.version, h.from_stage_id, h.from_stage, h.to_stage_id, h.to_stage, h.changed_by, u.name AS "changed_by_name?", h.note, h.changed_at
+            FROM resume_stage_history h
+            JOIN resumes r ON r.id = h.resume_id
+            LEFT JOIN users u ON u.id = h.changed_by
+            WHERE r.user_id = $1 AND r.document_id = (SELECT document_id FROM resumes WHERE id = $2 AND user_id = $1 AND deleted_at IS NULL)
+            ORDER BY h.changed_at
+            "#,
+            user_id,
+            resume_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(timeline)
+    }
+
+    async fn get_pipeline_resume_ids(
+        &self,
+        user_id: Uuid,
+        per_stage: i64,
+        tags: &[String],
+    ) -> Result<Vec<Uuid>, sqlx::Error> {
+        let ids = sqlx::query_scalar!(
+            r#"
+            SELECT ranked.id AS "id!"
+            FROM (
+                SELECT r.id, ROW_NUMBER() OVER (PARTITION BY r.stage_id ORDER BY r.stage_changed_at) AS stage_rank
+                FROM resumes r
+                JOIN resume_documents d ON d.current_resume_id = r.id
+                WHERE d.user_id = $1 AND r.deleted_at IS NULL
+                AND (
+                    cardinality($3::TEXT[]) = 0
+                    OR (
+                        SELECT COUNT(*)
+                        FROM resume_tags rt
+                        JOIN tags t ON t.id = rt.tag_id
+                        WHERE rt.resume_id = r.id AND lower(t.name) = ANY($3)
+                    ) = cardinality($3::TEXT[])
+                )
+            ) ranked
+            WHERE ranked.stage_rank <= $2
+            "#,
+            user_id,
+            per_stage,
+            tags
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(ids)
+    }
+
+    async fn get_pipeline_counts(
+        &self,
+        user_id: Uuid,
+        tags: &[String],
+    ) -> Result<Vec<PipelineStageCount>, sqlx::Error> {
+        let counts = sqlx::query_as!(
+            PipelineStageCount,
+            r#"
+            SELECT r.stage_id, COUNT(*) AS "count!"
+            FROM resumes r
+            JOIN resume_documents d ON d.current_resume_id = r.id
+            WHERE d.user_id = $1 AND r.deleted_at IS NULL
+            AND (
+                cardinality($2::TEXT[]) = 0
+                OR (
+                    SELECT COUNT(*)
+                    FROM resume_tags rt
+                    JOIN tags t ON t.id = rt.tag_id
+                    WHERE rt.resume_id = r.id AND lower(t.name) = ANY($2)
+                ) = cardinality($2::TEXT[])
+            )
+            GROUP BY r.stage_id
+            "#,
+            user_id,
+            tags
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(counts)
+    }
+}
+
+#[async_trait]
+pub trait ShareActions {
+    async fn save_share_link(
+        &self,
+        user_id: Uuid,
+        resume_id: Uuid,
+        params: &NewShareLinkParams,
+    ) -> Result<ShareLink, sqlx::Error>;
+
+    async fn get_share_links(
+        &self,
+        user_id: Uuid,
+        resume_id: Uuid,
+    ) -> Result<Vec<ShareLink>, sqlx::Error>;
+
+    async fn get_share_link(
+        &self,
+        user_id: Uuid,
+        share_id: Uuid,
+    ) -> Result<Option<ShareLink>, sqlx::Error>;
+
+    async fn get_share_link_by_token(&self, token_hash: &str) -> Result<Option<ShareLink>, sqlx::Error>;
+
+    async fn revoke_share_link(
+        &self,
+        user_id: Uuid,
+        share_id: Uuid,
+    ) -> Result<Option<ShareLink>, sqlx::Error>;
+
+    /// Counts a view if the link is still usable, `None` once it has expired, been revoked or run out of views
+    async fn record_share_view(&self, share_id: Uuid) -> Result<Option<ShareLink>, sqlx::Error>;
+
+    async fn log_share_access(
+        &self,
+        share_id: Uuid,
+        granted: bool,
+        reason: Option<&str>,
+        ip: Option<&str>,
+        user_agent: Option<&str>,
+    ) -> Result<(), sqlx::Error>;
+
+    async fn count_failed_share_access(
+        &self,
+        share_id: Uuid,
+        reason: &str,
+        since: DateTime<Utc>,
+    ) -> Result<i64, sqlx::Error>;
+
+    async fn get_share_access(
+        &self,
+        share_id: Uuid,
+        page: u32,
+        limit: usize,
+    ) -> Result<Vec<ShareAccess>, sqlx::Error>;
+
+    async fn get_share_access_count(&self, share_id: Uuid) -> Result<i64, sqlx::Error>;
+}
+
+#[async_trait]
+impl ShareActions for DBClient {
+    async fn save_share_link(
+        &self,
+        user_id: Uuid,
+        resume_id: Uuid,
+        params: &NewShareLinkParams,
+    ) -> Result<ShareLink, sqlx::Error> {
+        let link = sqlx::query_as!(
+            ShareLink,
+            r#"
+            INSERT INTO share_links (resume_id, user_id, token_hash, token_prefix, password_hash, redacted, expires_at, max_views)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING id, resume_id, user_id, token_prefix, password_hash, redacted, expires_at, max_views, view_count, last_viewed_at, revoked_at, created_at
+            "#,
+            resume_id,
+            user_id,
+            params.token_hash,
+            params.token_prefix,
+            params.password_hash,
+            params.redacted,
+            params.expires_at,
+            params.max_views
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(link)
+    }
+
+    async fn get_share_links(
+        &self,
+        user_id: Uuid,
+        resume_id: Uuid,
+    ) -> Result<Vec<ShareLink>, sqlx::Error> {
+        let links = sqlx::query_as!(
+            ShareLink,
+            r#"
+            SELECT id, resume_id, user_id, token_prefix, password_hash, redacted, expires_at, max_views, view_count, last_viewed_at, revoked_at, created_at
+            FROM share_links
+            WHERE resume_id = $1 AND user_id = $2
+            ORDER BY created_at DESC
+            "#,
+            resume_id,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(links)
+    }
+
+    async fn get_share_link(
+        &self,
+        user_id: Uuid,
+        share_id: Uuid,
+    ) -> Result<Option<ShareLink>, sqlx::Error> {
+        let link = sqlx::query_as!(
+            ShareLink,
+            r#"
+            SELECT id, resume_id, user_id, token_prefix, password_hash, redacted, expires_at, max_views, view_count, last_viewed_at, revoked_at, created_at
+            FROM share_links
+            WHERE id = $1 AND user_id = $2
+            "#,
+            share_id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(link)
+    }
+
+    async fn get_share_link_by_token(&self, token_hash: &str) -> Result<Option<ShareLink>, sqlx::Error> {
+        let link = sqlx::query_as!(
+            ShareLink,
+            r#"
+            SELECT id, resume_id, user_id, token_prefix, password_hash, redacted, expires_at, max_views, view_count, last_viewed_at, revoked_at, created_at
+            FROM share_links
+            WHERE token_hash = $1
+            "#,
+            token_hash
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(link)
+    }
+
+    async fn revoke_share_link(
+        &self,
+        user_id: Uuid,
+        share_id: Uuid,
+    ) -> Result<Option<ShareLink>, sqlx::Error> {
+        let link = sqlx::query_as!(
+            ShareLink,
+            r#"
+            UPDATE share_links
+            SET revoked_at = COALESCE(revoked_at, NOW())
+            WHERE id = $1 AND user_id = $2
+            RETURNING id, resume_id, user_id, token_prefix, password_hash, redacted, expires_at, max_views, view_count, last_viewed_at, revoked_at, created_at
+            "#,
+            share_id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(link)
+    }
+
+    async fn record_share_view(&self, share_id: Uuid) -> Result<Option<ShareLink>, sqlx::Error> {
+        let link = sqlx::query_as!(
+            ShareLink,
+            r#"
+            UPDATE share_links
+            SET view_count = view_count + 1, last_viewed_at = NOW()
+            WHERE id = $1
+            AND revoked_at IS NULL
+            AND expires_at > NOW()
+            AND (max_views IS NULL OR view_count < max_views)
+            RETURNING id, resume_id, user_id, token_prefix, password_hash, redacted, expires_at, max_views, view_count, last_viewed_at, revoked_at, created_at
+            "#,
+            share_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(link)
+    }
+
+    async fn log_share_access(
+        &self,
+        share_id: Uuid,
+        granted: bool,
+        reason: Option<&str>,
+        ip: Option<&str>,
+        user_agent: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO share_link_access (share_link_id, granted, reason, ip, user_agent)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+            share_id,
+            granted,
+            reason,
+            ip,
+            user_agent
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn count_failed_share_access(
+        &self,
+        share_id: Uuid,
+        reason: &str,
+        since: DateTime<Utc>,
+    ) -> Result<i64, sqlx::Error> {
+        let count = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*)
+            FROM share_link_access
+            WHERE share_link_id = $1 AND NOT granted AND reason = $2 AND accessed_at >= $3
+            "#,
+            share_id,
+            reason,
+            since
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count.unwrap_or(0))
+    }
+
+    async fn get_share_access(
+        &self,
+        share_id: Uuid,
+        page: u32,
+        limit: usize,
+    ) -> Result<Vec<ShareAccess>, sqlx::Error> {
+        let offset = (page - 1) * limit as u32;
+
+        let access = sqlx::query_as!(
+            ShareAccess,
+            r#"
+            SELECT id, share_link_id, granted, reason, ip, user_agent, accessed_at
+            FROM share_link_access
+            WHERE share_link_id = $1
+            ORDER BY accessed_at DESC
+            LIMIT $2 OFFSET $3
+            "#,
+            share_id,
+            limit as i64,
+            offset as i64
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(access)
+    }
+
+    async fn get_share_access_count(&self, share_id: Uuid) -> Result<i64, sqlx::Error> {
+        let count = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) FROM share_link_access WHERE share_link_id = $1"#,
+            share_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count.unwrap_or(0))
+    }
+}
+
+#[async_trait]
+pub trait TrashActions {
+    async fn trash_resume(&self, user_id: Uuid, resume_id: Uuid) -> Result<bool, sqlx::Error>;
+
+    async fn restore_resume(&self, user_id: Uuid, resume_id: Uuid) -> Result<Option<Resume>, sqlx::Error>;
+
+    async fn get_trash(
+        &self,
+        user_id: Uuid,
+        page: u32,
+        limit: usize,
+    ) -> Result<Vec<Resume>, sqlx::Error>;
+
+    async fn get_trash_count(&self, user_id: Uuid) -> Result<i64, sqlx::Error>;
+
+    async fn get_expired_trash(
+        &self,
+        deleted_before: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<Resume>, sqlx::Error>;
+
+    async fn purge_resume(
+        &self,
+        user_id: Option<Uuid>,
+        resume_id: Uuid,
+    ) -> Result<Option<Option<String>>, sqlx::Error>;
+}
+
+#[async_trait]
+impl TrashActions for DBClient {
+    async fn trash_resume(&self, user_id: Uuid, resume_id: Uuid) -> Result<bool, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let document_id = sqlx::query_scalar!(
+            r#"
+            UPDATE resumes
+            SET deleted_at = NOW()
+            WHERE id = $1 AND user_id = $2 AND deleted_at IS NULL
+            RETURNING document_id
+            "#,
+            resume_id,
+            user_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let Some(document_id) = document_id else {
+            return Ok(false);
+        };
+
+        // The newest live version takes over, a document with none left drops out of listings
+        sqlx::query!(
+            r#"
+            UPDATE resume_documents
+            SET current_resume_id = (
+                SELECT id FROM resumes WHERE document_id = $1 AND deleted_at IS NULL ORDER BY version DESC LIMIT 1
+            ), updated_at = NOW()
+            WHERE id = $1 AND current_resume_id = $2
+            "#,
+            document_id,
+            resume_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    async fn restore_resume(&self, user_id: Uuid, resume_id: Uuid) -> Result<Option<Resume>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let resume = sqlx::query_as!(
+            Resume,
+            r#"
+            UPDATE resumes
+            SET deleted_at = NULL
+            WHERE id = $1 AND user_id = $2 AND deleted_at IS NOT NULL
+            RETURNING id, user_id, file_path, analysis_result, uploaded_at, latest_analysis_id, analysis_status as "analysis_status: AnalysisStatus", analysis_schema_version, document_format, extracted_text, page_count, word_count, language, ats_report, document_id, version, file_name, content_hash, stage_id, stage_changed_at, deleted_at, anonymized_at
+            "#,
+            resume_id,
+            user_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        // Only a document left without a live version gets this one back as current
+        if let Some(resume) = &resume {
+            sqlx::query!(
+                r#"
+                UPDATE resume_documents
+                SET current_resume_id = $2, updated_at = NOW()
+                WHERE id = $1 AND current_resume_id IS NULL
+                "#,
+                resume.document_id,
+                resume_id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(resume)
+    }
+
+    async fn get_trash(
+        &self,
+        user_id: Uuid,
+        page: u32,
+        limit: usize,
+    ) -> Result<Vec<Resume>, sqlx::Error> {
+        let offset = (page - 1) * limit as u32;
+
+        let resumes = sqlx::query_as!(
+            Resume,
+            r#"
+            SELECT id, user_id, file_path, analysis_result, uploaded_at, latest_analysis_id, analysis_status as "analysis_status: AnalysisStatus", analysis_schema_version, document_format, extracted_text, page_count, word_count, language, ats_report, document_id, version, file_name, content_hash, stage_id, stage_changed_at, deleted_at, anonymized_at
+            FROM resumes
+            WHERE user_id = $1 AND deleted_at IS NOT NULL
+            ORDER BY deleted_at DESC
+            LIMIT $2 OFFSET $3
+            "#,
+            user_id,
+            limit as i64,
+            offset as i64
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(resumes)
+    }
+
+    async fn get_trash_count(&self, user_id: Uuid) -> Result<i64, sqlx::Error> {
+        let count = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) FROM resumes WHERE user_id = $1 AND deleted_at IS NOT NULL"#,
+            user_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count.unwrap_or(0))
+    }
+
+    async fn get_expired_trash(
+        &self,
+        deleted_before: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<Resume>, sqlx::Error> {
+        let resumes = sqlx::query_as!(
+            Resume,
+            r#"
+            SELECT id, user_id, file_path, analysis_result, uploaded_at, latest_analysis_id, analysis_status as "analysis_status: AnalysisStatus", analysis_schema_version, document_format, extracted_text, page_count, word_count, language, ats_report, document_id, version, file_name, content_hash, stage_id, stage_changed_at, deleted_at, anonymized_at
+            FROM resumes
+            WHERE deleted_at < $1
+            ORDER BY deleted_at ASC
+            LIMIT $2
+            "#,
+            deleted_before,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(resumes)
+    }
+
+    async fn purge_resume(
+        &self,
+        user_id: Option<Uuid>,
+        resume_id: Uuid,
+    ) -> Result<Option<Option<String>>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        // Only trashed resumes can be purged, live ones have to go through the trash first
+        let purged = sqlx::query!(
+            r#"
+            DELETE FROM resumes
+            WHERE id = $1 AND ($2::UUID IS NULL OR user_id = $2) AND deleted_at IS NOT NULL
+            RETURNING document_id, file_path
+            "#,
+            resume_id,
+            user_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let Some(purged) = purged else {
+            return Ok(None);
+        };
+
+        // Drop the document with its last version, live or trashed
+        sqlx::query!(
+            r#"
+            DELETE FROM resume_documents d
+            WHERE d.id = $1 AND NOT EXISTS (SELECT 1 FROM resumes r WHERE r.document_id = d.id)
+            "#,
+            purged.document_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(Some(purged.file_path))
+    }
+}
+
+/// Personal details removed from a resume and what hangs off it, applied in one go.
+#[derive(Debug, Clone)]
+pub struct AnonymizedResume {
+    pub resume_id: Uuid,
+    pub file_name: Option<String>,
+    pub extracted_text: Option<String>,
+    pub analysis_result: Option<serde_json::Value>,
+    pub analysis_schema_version: i32,
+    pub note_ids: Vec<Uuid>,
+    pub note_bodies: Vec<String>,
+}
+
+#[async_trait]
+pub trait RetentionActions {
+    async fn get_retention_policies(&self) -> Result<Vec<RetentionPolicy>, sqlx::Error>;
+
+    async fn save_retention_policy(
+        &self,
+        user_id: Option<Uuid>,
+        retention_months: Option<i32>,
+        action: RetentionAction,
+        updated_by: Uuid,
+    ) -> Result<RetentionPolicy, sqlx::Error>;
+
+    async fn delete_retention_policy(&self, user_id: Uuid) -> Result<bool, sqlx::Error>;
+
+    async fn get_retention_candidates(&self, limit: i64) -> Result<Vec<RetentionCandidate>, sqlx::Error>;
+
+    async fn get_retention_summary(&self) -> Result<Vec<RetentionSummary>, sqlx::Error>;
+
+    async fn expire_resume(&self, resume_id: Uuid) -> Result<Option<Option<String>>, sqlx::Error>;
+
+    async fn anonymize_resume(&self, anonymized: &AnonymizedResume) -> Result<Option<Option<String>>, sqlx::Error>;
+}
+
+#[async_trait]
+impl RetentionActions for DBClient {
+    async fn get_retention_policies(&self) -> Result<Vec<RetentionPolicy>, sqlx::Error> {
+        let policies = sqlx::query_as!(
+            RetentionPolicy,
+            r#"
+            SELECT p.id, p.user_id, u.email AS "user_email?", p.retention_months, p.action as "action: RetentionAction", p.updated_by, p.created_at, p.updated_at
+            FROM retention_policies p
+            LEFT JOIN users u ON u.id = p.user_id
+            ORDER BY p.user_id IS NOT NULL, u.email
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(policies)
+    }
+
+    async fn save_retention_policy(
+        &self,
+        user_id: Option<Uuid>,
+        retention_months: Option<i32>,
+        action: RetentionAction,
+        updated_by: Uuid,
+    ) -> Result<RetentionPolicy, sqlx::Error> {
+        // The global policy always exists, users' own are created on first use
+        let policy = match user_id {
+            None => {
+                sqlx::query_as!(
+                    RetentionPolicy,
+                    r#"
+                    WITH saved AS (
+                        UPDATE retention_policies
+                        SET retention_months = $1, action = $2, updated_by = $3, updated_at = NOW()
+                        WHERE user_id IS NULL
+                        RETURNING *
+                    )
+                    SELECT s.id, s.user_id, NULL::VARCHAR AS "user_email?", s.retention_months, s.action as "action!: RetentionAction", s.updated_by, s.created_at, s.updated_at
+                    FROM saved s
+                    "#,
+                    retention_months,
+                    action as RetentionAction,
+                    updated_by
+                )
+                .fetch_one(&self.pool)
+                .await?
+            }
+            Some(user_id) => {
+                sqlx::query_as!(
+                    RetentionPolicy,
+                    r#"
+                    WITH saved AS (
+                        INSERT INTO retention_policies (user_id, retention_months, action, updated_by)
+                        VALUES ($1, $2, $3, $4)
+                        ON CONFLICT (user_id) DO UPDATE
+                        SET retention_months = EXCLUDED.retention_months, action = EXCLUDED.action, updated_by = EXCLUDED.updated_by, updated_at = NOW()
+                        RETURNING *
+                    )
+                    SELECT s.id, s.user_id, u.email AS "user_email?", s.retention_months, s.action as "action!: RetentionAction", s.updated_by, s.created_at, s.updated_at
+                    FROM saved s
+                    JOIN users u ON u.id = s.user_id
+                    "#,
+                    user_id,
+                    retention_months,
+                    action as RetentionAction,
+                    updated_by
+                )
+                .fetch_one(&self.pool)
+                .await?
+            }
+        };
+
+        Ok(policy)
+    }
+
+    async fn delete_retention_policy(&self, user_id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"DELETE FROM retention_policies WHERE user_id = $1"#,
+            user_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn get_retention_candidates(&self, limit: i64) -> Result<Vec<RetentionCandidate>, sqlx::Error> {
+        // Trashed resumes count too, the trash doesn't extend how long data is kept. They were
+        // meant to go, so they're deleted rather than kept around anonymized.
+        let candidates = sqlx::query_as!(
+            RetentionCandidate,
+            r#"
+            SELECT r.id AS resume_id, r.user_id, r.document_id, r.file_path, r.uploaded_at, r.deleted_at,
+                CASE WHEN r.deleted_at IS NULL THEN p.action ELSE 'delete' END AS "action!: RetentionAction", p.retention_months AS "retention_months!", p.user_id IS NOT NULL AS "user_policy!"
+            FROM resumes r
+            JOIN LATERAL (
+                SELECT user_id, retention_months, action
+                FROM retention_policies
+                WHERE user_id = r.user_id OR user_id IS NULL
+                ORDER BY user_id NULLS LAST
+                LIMIT 1
+            ) p ON TRUE
+            WHERE p.retention_months IS NOT NULL
+                AND r.uploaded_at < NOW() - make_interval(months => p.retention_months)
+                AND (p.action = 'delete' OR r.anonymized_at IS NULL)
+            ORDER BY r.uploaded_at ASC
+            LIMIT $1
+            "#,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(candidates)
+    }
+
+    async fn get_retention_summary(&self) -> Result<Vec<RetentionSummary>, sqlx::Error> {
+        let summary = sqlx::query_as!(
+            RetentionSummary,
+            r#"
+            SELECT CASE WHEN r.deleted_at IS NULL THEN p.action ELSE 'delete' END AS "action!: RetentionAction", COUNT(*) AS "resumes!", COUNT(DISTINCT r.user_id) AS "users!"
+            FROM resumes r
+            JOIN LATERAL (
+                SELECT user_id, retention_months, action
+                FROM retention_policies
+                WHERE user_id = r.user_id OR user_id IS NULL
+                ORDER BY user_id NULLS LAST
+                LIMIT 1
+            ) p ON TRUE
+            WHERE p.retention_months IS NOT NULL
+                AND r.uploaded_at < NOW() - make_interval(months => p.retention_months)
+                AND (p.action = 'delete' OR r.anonymized_at IS NULL)
+            GROUP BY 1
+            ORDER BY 1
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(summary)
+    }
+
+    async fn expire_resume(&self, resume_id: Uuid) -> Result<Option<Option<String>>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let expired = sqlx::query!(
+            r#"
+            DELETE FROM resumes
+            WHERE id = $1
+            RETURNING document_id, file_path
+            "#,
+            resume_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let Some(expired) = expired else {
+            return Ok(None);
+        };
+
+        // Same as leaving the trash: the newest live version takes over, or the document goes with its last one
+        sqlx::query!(
+            r#"
+            UPDATE resume_documents
+            SET current_resume_id = (
+                SELECT id FROM resumes WHERE document_id = $1 AND deleted_at IS NULL ORDER BY version DESC LIMIT 1
+            )
+            WHERE id = $1 AND current_resume_id IS NULL
+            "#,
+            expired.document_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            DELETE FROM resume_documents d
+            WHERE d.id = $1 AND NOT EXISTS (SELECT 1 FROM resumes r WHERE r.document_id = d.id)
+            "#,
+            expired.document_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(Some(expired.file_path))
+    }
+
+    async fn anonymize_resume(&self, anonymized: &AnonymizedResume) -> Result<Option<Option<String>>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let resume = sqlx::query!(
+            r#"
+            SELECT file_path, document_id, latest_analysis_id
+            FROM resumes
+            WHERE id = $1 AND anonymized_at IS NULL
+            FOR UPDATE
+            "#,
+            anonymized.resume_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let Some(resume) = resume else {
+            return Ok(None);
+        };
+
+        sqlx::query!(
+            r#"
+            UPDATE resumes
+            SET file_path = NULL, content_hash = NULL, file_name = $2, extracted_text = $3, analysis_result = $4, analysis_schema_version = $5, ats_report = NULL, file_size = 0, anonymized_at = NOW()
+            WHERE id = $1
+            "#,
+            anonymized.resume_id,
+            anonymized.file_name,
+            anonymized.extracted_text,
+            anonymized.analysis_result,
+            anonymized.analysis_schema_version
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        // Earlier runs still hold the original details, only the latest is kept and scrubbed
+        sqlx::query!(
+            r#"DELETE FROM resume_analyses WHERE resume_id = $1 AND id IS DISTINCT FROM $2"#,
+            anonymized.resume_id,
+            resume.latest_analysis_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        if let (Some(analysis_id), Some(analysis_result)) = (resume.latest_analysis_id, &anonymized.analysis_result) {
+            sqlx::query!(
+                r#"UPDATE resume_analyses SET analysis_result = $2, schema_version = $3 WHERE id = $1"#,
+                analysis_id,
+                analysis_result,
+                anonymized.analysis_schema_version
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        sqlx::query!(
+            r#"DELETE FROM resume_embeddings WHERE resume_id = $1"#,
+            anonymized.resume_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"UPDATE share_links SET revoked_at = NOW() WHERE resume_id = $1 AND revoked_at IS NULL"#,
+            anonymized.resume_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE resume_notes n
+            SET body = scrubbed.body
+            FROM UNNEST($2::UUID[], $3::TEXT[]) AS scrubbed(id, body)
+            WHERE n.id = scrubbed.id AND n.resume_id = $1
+            "#,
+            anonymized.resume_id,
+            &anonymized.note_ids,
+            &anonymized.note_bodies
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        // Reasons given when moving the resume between stages may name the candidate
+        sqlx::query!(
+            r#"UPDATE resume_stage_history SET note = NULL WHERE resume_id = $1"#,
+            anonymized.resume_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        // Document titles come from the uploaded file name, which tends to be the candidate's
+        sqlx::query!(
+            r#"UPDATE resume_documents SET title = COALESCE($2, title) WHERE id = $1"#,
+            resume.document_id,
+            anonymized.file_name
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(Some(resume.file_path))
+    }
+}
+
+#[async_trait]
+pub trait QuotaActions {
+    async fn get_quotas(&self) -> Result<Vec<Quota>, sqlx::Error>;
+
+    async fn save_role_quota(&self, role: UserRole, params: &QuotaParams, updated_by: Uuid) -> Result<Quota, sqlx::Error>;
+
+    async fn save_user_quota(&self, user_id: Uuid, params: &QuotaParams, updated_by: Uuid) -> Result<Quota, sqlx::Error>;
+
+    async fn delete_role_quota(&self, role: UserRole) -> Result<bool, sqlx::Error>;
+
+    async fn delete_user_quota(&self, user_id: Uuid) -> Result<bool, sqlx::Error>;
+
+    async fn get_quota_usage(&self, user_id: Uuid) -> Result<Option<QuotaUsage>, sqlx::Error>;
+
+    async fn reserve_analysis(&self, user_id: Uuid) -> Result<Option<NaiveDate>, sqlx::Error>;
+
+    async fn release_analysis(&self, user_id: Uuid, day: NaiveDate) -> Result<(), sqlx::Error>;
+
+    async fn get_unsized_files(&self, limit: i64) -> Result<Vec<String>, sqlx::Error>;
+
+    async fn set_file_size(&self, file_path: &str, file_size: i64) -> Result<(), sqlx::Error>;
+}
+
+#[async_trait]
+impl QuotaActions for DBClient {
+    async fn get_quotas(&self) -> Result<Vec<Quota>, sqlx::Error> {
+        let quotas = sqlx::query_as!(
+            Quota,
+            r#"
+            SELECT q.id, q.user_id, u.email AS "user_email?", q.role as "role: UserRole", q.max_resumes, q.max_storage_bytes, q.max_analyses_per_day, q.updated_by, q.created_at, q.updated_at
+            FROM quotas q
+            LEFT JOIN users u ON u.id = q.user_id
+            ORDER BY q.user_id IS NOT NULL, q.role, u.email
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(quotas)
+    }
+
+    async fn save_role_quota(&self, role: UserRole, params: &QuotaParams, updated_by: Uuid) -> Result<Quota, sqlx::Error> {
+        let quota = sqlx::query_as!(
+            Quota,
+            r#"
+            INSERT INTO quotas (role, max_resumes, max_storage_bytes, max_analyses_per_day, updated_by)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (role) DO UPDATE
+            SET max_resumes = EXCLUDED.max_resumes, max_storage_bytes = EXCLUDED.max_storage_bytes, max_analyses_per_day = EXCLUDED.max_analyses_per_day, updated_by = EXCLUDED.updated_by, updated_at = NOW()
+            RETURNING id, user_id, NULL::VARCHAR AS "user_email?", role as "role: UserRole", max_resumes, max_storage_bytes, max_analyses_per_day, updated_by, created_at, updated_at
+            "#,
+            role as UserRole,
+            params.max_resumes,
+            params.max_storage_bytes,
+            params.max_analyses_per_day,
+            updated_by
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(quota)
+    }
+
+    async fn save_user_quota(&self, user_id: Uuid, params: &QuotaParams, updated_by: Uuid) -> Result<Quota, sqlx::Error> {
+        let quota = sqlx::query_as!(
+            Quota,
+            r#"
+            WITH saved AS (
+                INSERT INTO quotas (user_id, max_resumes, max_storage_bytes, max_analyses_per_day, updated_by)
+                VALUES ($1, $2, $3, $4, $5)
+                ON CONFLICT (user_id) DO UPDATE
+                SET max_resumes = EXCLUDED.max_resumes, max_storage_bytes = EXCLUDED.max_storage_bytes, max_analyses_per_day = EXCLUDED.max_analyses_per_day, updated_by = EXCLUDED.updated_by, updated_at = NOW()
+                RETURNING *
+            )
+            SELECT s.id, s.user_id, u.email AS "user_email?", s.role as "role: UserRole", s.max_resumes, s.max_storage_bytes, s.max_analyses_per_day, s.updated_by, s.created_at, s.updated_at
+            FROM saved s
+            JOIN users u ON u.id = s.user_id
+            "#,
+            user_id,
+            params.max_resumes,
+            params.max_storage_bytes,
+            params.max_analyses_per_day,
+            updated_by
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(quota)
+    }
+
+    async fn delete_role_quota(&self, role: UserRole) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"DELETE FROM quotas WHERE role = $1"#,
+            role as UserRole
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn delete_user_quota(&self, user_id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"DELETE FROM quotas WHERE user_id = $1"#,
+            user_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn get_quota_usage(&self, user_id: Uuid) -> Result<Option<QuotaUsage>, sqlx::Error> {
+        let usage = sqlx::query_as!(
+            QuotaUsage,
+            r#"
+            SELECT q.id AS "quota_id?", COALESCE(q.user_id IS NOT NULL, FALSE) AS "user_quota!",
+                q.max_resumes AS "max_resumes?", q.max_storage_bytes AS "max_storage_bytes?", q.max_analyses_per_day AS "max_analyses_per_day?",
+                (SELECT COUNT(*) FROM resumes r WHERE r.user_id = u.id) AS "resumes!",
+                (
+                    SELECT COALESCE(SUM(f.file_size), 0)::BIGINT
+                    FROM (
+                        -- Identical uploads share one file, files from before hashing are their own
+                        SELECT DISTINCT ON (COALESCE(r.content_hash, r.file_path)) r.file_size
+                        FROM resumes r
+                        WHERE r.user_id = u.id AND r.file_path IS NOT NULL
+                    ) f
+                ) AS "storage_bytes!",
+                COALESCE((SELECT a.analyses FROM analysis_usage a WHERE a.user_id = u.id AND a.day = (NOW() AT TIME ZONE 'UTC')::DATE), 0) AS "analyses_today!"
+            FROM users u
+            LEFT JOIN LATERAL (
+                SELECT id, user_id, max_resumes, max_storage_bytes, max_analyses_per_day
+                FROM quotas q
+                WHERE q.user_id = u.id OR q.role = u.role
+                ORDER BY q.user_id NULLS LAST
+                LIMIT 1
+            ) q ON TRUE
+            WHERE u.id = $1
+            "#,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(usage)
+    }
+
+    async fn reserve_analysis(&self, user_id: Uuid) -> Result<Option<NaiveDate>, sqlx::Error> {
+        // Checked and counted in one statement, concurrent requests can't both take the last analysis
+        let reserved = sqlx::query_scalar!(
+            r#"
+            WITH quota AS (
+                SELECT q.max_analyses_per_day
+                FROM users u
+                LEFT JOIN LATERAL (
+                    SELECT max_analyses_per_day FROM quotas q WHERE q.user_id = u.id OR q.role = u.role ORDER BY q.user_id NULLS LAST LIMIT 1
+                ) q ON TRUE
+                WHERE u.id = $1
+            )
+            INSERT INTO analysis_usage (user_id, day, analyses)
+            SELECT $1, (NOW() AT TIME ZONE 'UTC')::DATE, 1
+            FROM quota
+            WHERE quota.max_analyses_per_day IS NULL OR quota.max_analyses_per_day > 0
+            ON CONFLICT (user_id, day) DO UPDATE
+            SET analyses = analysis_usage.analyses + 1
+            WHERE (SELECT max_analyses_per_day FROM quota) IS NULL
+                OR analysis_usage.analyses < (SELECT max_analyses_per_day FROM quota)
+            RETURNING day
+            "#,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(reserved)
+    }
+
+    async fn release_analysis(&self, user_id: Uuid, day: NaiveDate) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE analysis_usage SET analyses = analyses - 1 WHERE user_id = $1 AND day = $2 AND analyses > 0"#,
+            user_id,
+            day
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn get_unsized_files(&self, limit: i64) -> Result<Vec<String>, sqlx::Error> {
+        let files = sqlx::query_scalar!(
+            r#"SELECT DISTINCT file_path AS "file_path!" FROM resumes WHERE file_size IS NULL AND file_path IS NOT NULL LIMIT $1"#,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(files)
+    }
+
+    async fn set_file_size(&self, file_path: &str, file_size: i64) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE resumes SET file_size = $2 WHERE file_path = $1 AND file_size IS NULL"#,
+            file_path,
+            file_size
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+pub trait EmbeddingActions {
+    async fn save_embedding(
+        &self,
+        resume_id: Uuid,
+        model: &str,
+        embedding: &[f32],
+    ) -> Result<(), sqlx::Error>;
+
+    async fn get_embedding(
+        &self,
+        resume_id: Uuid,
+        model: &str,
+    ) -> Result<Option<ResumeEmbedding>, sqlx::Error>;
+
+    async fn get_user_embeddings(
+        &self,
+        user_id: Uuid,
+        model: &str,
+    ) -> Result<Vec<ResumeEmbedding>, sqlx::Error>;
+
+    async fn get_resumes_without_embedding(
+        &self,
+        user_id: Uuid,
+        model: &str,
+    ) -> Result<Vec<Resume>, sqlx::Error>;
+}
+
+#[async_trait]
+impl EmbeddingActions for DBClient {
+    async fn save_embedding(
+        &self,
+        resume_id: Uuid,
+        model: &str,
+        embedding: &[f32],
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO resume_embeddings (resume_id, model, dimensions, embedding)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (resume_id, model)
+            DO UPDATE SET dimensions = EXCLUDED.dimensions, embedding = EXCLUDED.embedding, created_at = NOW()
+            "#,
+            resume_id,
+            model,
+            embedding.len() as i32,
+            embedding
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn get_embedding(
+        &self,
+        resume_id: Uuid,
+        model: &str,
+    ) -> Result<Option<ResumeEmbedding>, sqlx::Error> {
+        let embedding = sqlx::query_as!(
+            ResumeEmbedding,
+            r#"
+            SELECT resume_id, model, dimensions, embedding, created_at
+            FROM resume_embeddings
+            WHERE resume_id = $1 AND model = $2
+            "#,
+            resume_id,
+            model
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(embedding)
+    }
+
+    async fn get_user_embeddings(
+        &self,
+        user_id: Uuid,
+        model: &str,
+    ) -> Result<Vec<ResumeEmbedding>, sqlx::Error> {
+        let embeddings = sqlx::query_as!(
+            ResumeEmbedding,
+            r#"
+            SELECT e.resume_id, e.model, e.dimensions, e.embedding, e.created_at
+            FROM resume_embeddings e
+            JOIN resumes r ON r.id = e.resume_id
+            WHERE r.user_id = $1 AND e.model = $2 AND r.deleted_at IS NULL
+            "#,
+            user_id,
+            model
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(embeddings)
+    }
+
+    async fn get_resumes_without_embedding(
+        &self,
+        user_id: Uuid,
+        model: &str,
+    ) -> Result<Vec<Resume>, sqlx::Error> {
+        let resumes = sqlx::query_as!(
+            Resume,
+            r#"
+            SELECT id, user_id, file_path, analysis_result, uploaded_at, latest_analysis_id, analysis_status as "analysis_status: AnalysisStatus", analysis_schema_version, document_format, extracted_text, page_count, word_count, language, ats_report, document_id, version, file_name, content_hash, stage_id, stage_changed_at, deleted_at, anonymized_at
+            FROM resumes r
+            WHERE user_id = $1
+                AND deleted_at IS NULL
+                AND NOT EXISTS (
+                    SELECT 1 FROM resume_embeddings e
+                    WHERE e.resume_id = r.id AND e.model = $2
+                )
+            "#,
+            user_id,
+            model
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(resumes)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct JobDescriptionParams {
+    pub title: String,
+    pub description: String,
+    pub required_skills: Vec<String>,
+    pub preferred_skills: Vec<String>,
+    pub seniority: Option<Seniority>,
+    pub min_years_experience: Option<i32>,
+}
+
+#[async_trait]
+pub trait JobActions {
+    async fn save_job(
+        &self,
+        user_id: Uuid,
+        params: &JobDescriptionParams,
+    ) -> Result<JobDescription, sqlx::Error>;
+
+    async fn get_job(
+        &self,
+        user_id: Uuid,
+        job_id: Uuid,
+    ) -> Result<Option<JobDescription>, sqlx::Error>;
+
+    async fn get_jobs(
+        &self,
+        user_id: Uuid,
+        page: u32,
+        limit: usize,
+    ) -> Result<Vec<JobDescription>, sqlx::Error>;
+
+    async fn get_job_count(&self, user_id: Uuid) -> Result<i64, sqlx::Error>;
+
+    async fn update_job(
+        &self,
+        user_id: Uuid,
+        job_id: Uuid,
+        params: &JobDescriptionParams,
+    ) -> Result<Option<JobDescription>, sqlx::Error>;
+
+    async fn delete_job(&self, user_id: Uuid, job_id: Uuid) -> Result<bool, sqlx::Error>;
+
+    /// Resumes of the job owner without an up to date cached score for the job.
+    async fn get_unscored_resumes(
+        &self,
+        job: &JobDescription,
+        limit: usize,
+    ) -> Result<Vec<Resume>, sqlx::Error>;
+
+    async fn save_match_scores(
+        &self,
+        job: &JobDescription,
+        scores: &[(Resume, f64, serde_json::Value)],
+    ) -> Result<(), sqlx::Error>;
+
+    /// Makes the cached scores of every job stale, for changes that affect all matching.
+    async fn invalidate_match_scores(&self) -> Result<(), sqlx::Error>;
+
+    async fn get_job_ranking(
+        &self,
+        job: &JobDescription,
+        min_score: f64,
+        page: u32,
+        limit: usize,
+    ) -> Result<Vec<JobMatchScore>, sqlx::Error>;
+}
+
+#[async_trait]
+impl JobActions for DBClient {
+    async fn save_job(
+        &self,
+        user_id: Uuid,
+        params: &JobDescriptionParams,
+    ) -> Result<JobDescription, sqlx::Error> {
+        let job = sqlx::query_as!(
+            JobDescription,
+            r#"
+            INSERT INTO job_descriptions (user_id, title, description, required_skills, preferred_skills, seniority, min_years_experience)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING id, user_id, title, description, required_skills, preferred_skills, seniority as "seniority: Seniority", min_years_experience, match_version, created_at, updated_at
+            "#,
+            user_id,
+            params.title,
+            params.description,
+            &params.required_skills,
+            &params.preferred_skills,
+            params.seniority as Option<Seniority>,
+            params.min_years_experience
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(job)
+    }
+
+    async fn get_job(
+        &self,
+        user_id: Uuid,
+        job_id: Uuid,
+    ) -> Result<Option<JobDescription>, sqlx::Error> {
+        let job = sqlx::query_as!(
+            JobDescription,
+            r#"
+            SELECT id, user_id, title, description, required_skills, preferred_skills, seniority as "seniority: Seniority", min_years_experience, match_version, created_at, updated_at
+            FROM job_descriptions
+            WHERE id = $1 AND user_id = $2
+            "#,
+            job_id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(job)
+    }
+
+    async fn get_jobs(
+        &self,
+        user_id: Uuid,
+        page: u32,
+        limit: usize,
+    ) -> Result<Vec<JobDescription>, sqlx::Error> {
+        let offset = (page - 1) * limit as u32;
+
+        let jobs = sqlx::query_as!(
+            JobDescription,
+            r#"
+            SELECT id, user_id, title, description, required_skills, preferred_skills, seniority as "seniority: Seniority", min_years_experience, match_version, created_at, updated_at
+            FROM job_descriptions
+            WHERE user_id = $1
+            ORDER BY created_at DESC
+            LIMIT $2 OFFSET $3
+            "#,
+            user_id,
+            limit as i64,
+            offset as i64
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(jobs)
+    }
+
+    async fn get_job_count(&self, user_id: Uuid) -> Result<i64, sqlx::Error> {
+        let count = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) FROM job_descriptions WHERE user_id = $1"#,
+            user_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count.unwrap_or(0))
+    }
+
+    async fn update_job(
+        &self,
+        user_id: Uuid,
+        job_id: Uuid,
+        params: &JobDescriptionParams,
+    ) -> Result<Option<JobDescription>, sqlx::Error> {
+        let job = sqlx::query_as!(
+            JobDescription,
+            r#"
+            UPDATE job_descriptions
+            SET match_version = CASE
+                    WHEN (title, description, required_skills, preferred_skills, seniority, min_years_experience)
+                        IS DISTINCT FROM ($3::VARCHAR, $4::TEXT, $5::TEXT[], $6::TEXT[], $7::job_seniority, $8::INTEGER)
+                    THEN match_version + 1
+                    ELSE match_version
+                END,
+                title = $3, description = $4, required_skills = $5, preferred_skills = $6,
+                seniority = $7, min_years_experience = $8, updated_at = NOW()
+            WHERE id = $1 AND user_id = $2
+            RETURNING id, user_id, title, description, required_skills, preferred_skills, seniority as "seniority: Seniority", min_years_experience, match_version, created_at, updated_at
+            "#,
+            job_id,
+            user_id,
+            params.title,
+            params.description,
+            &params.required_skills,
+            &params.preferred_skills,
+            params.seniority as Option<Seniority>,
+            params.min_years_experience
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(job)
+    }
+
+    async fn delete_job(&self, user_id: Uuid, job_id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"DELETE FROM job_descriptions WHERE id = $1 AND user_id = $2"#,
+            job_id,
+            user_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn get_unscored_resumes(
+        &self,
+        job: &JobDescription,
+        limit: usize,
+    ) -> Result<Vec<Resume>, sqlx::Error> {
+        let resumes = sqlx::query_as!(
+            Resume,
+            r#"
+            SELECT r.id, r.user_id, r.file_path, r.analysis_result, r.uploaded_at, r.latest_analysis_id, r.analysis_status as "analysis_status: AnalysisStatus", r.analysis_schema_version, r.document_format, r.extracted_text, r.page_count, r.word_count, r.language, r.ats_report, r.document_id, r.version, r.file_name, r.content_hash, r.stage_id, r.stage_changed_at, r.deleted_at, r.anonymized_at
+            FROM resumes r
+            LEFT JOIN job_match_scores s ON s.resume_id = r.id AND s.job_id = $2
+            WHERE r.user_id = $1
+                AND r.deleted_at IS NULL
+                AND (
+                    s.resume_id IS NULL
+                    OR s.job_match_version <> $3
+                    OR s.resume_analysis_id IS DISTINCT FROM r.latest_analysis_id
+                )
+            LIMIT $4
+            "#,
+            job.user_id,
+            job.id,
+            job.match_version,
+            limit as i64
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(resumes)
+    }
+
+    async fn save_match_scores(
+        &self,
+        job: &JobDescription,
+        scores: &[(Resume, f64, serde_json::Value)],
+    ) -> Result<(), sqlx::Error> {
+        let resume_ids: Vec<Uuid> = scores.iter().map(|(resume, _, _)| resume.id).collect();
+        let analysis_ids: Vec<Option<Uuid>> = scores.iter().map(|(resume, _, _)| resume.latest_analysis_id).collect();
+        let values: Vec<f64> = scores.iter().map(|(_, score, _)| *score).collect();
+        let reports: Vec<serde_json::Value> = scores.iter().map(|(_, _, report)| report.clone()).collect();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO job_match_scores (job_id, resume_id, job_match_version, resume_analysis_id, score, report)
+            SELECT $1, resume_id, $2, resume_analysis_id, score, report
+            FROM UNNEST($3::uuid[], $4::uuid[], $5::float8[], $6::jsonb[])
+                AS scores(resume_id, resume_analysis_id, score, report)
+            ON CONFLICT (job_id, resume_id)
+            DO UPDATE SET
+                job_match_version = EXCLUDED.job_match_version,
+                resume_analysis_id = EXCLUDED.resume_analysis_id,
+                score = EXCLUDED.score,
+                report = EXCLUDED.report,
+                computed_at = NOW()
+            "#,
+            job.id,
+            job.match_version,
+            &resume_ids,
+            &analysis_ids as &[Option<Uuid>],
+            &values,
+            &reports
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn invalidate_match_scores(&self) -> Result<(), sqlx::Error> {
+        sqlx::query!(r#"UPDATE job_descriptions SET match_version = match_version + 1"#)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_job_ranking(
+        &self,
+        job: &JobDescription,
+        min_score: f64,
+        page: u32,
+        limit: usize,
+    ) -> Result<Vec<JobMatchScore>, sqlx::Error> {
+        let offset = (page - 1) * limit as u32;
+
+        // Equal scores go to the most recently uploaded resume first
+        let scores = sqlx::query_as!(
+            JobMatchScore,
+            r#"
+            SELECT s.resume_id, s.score, s.report, COUNT(*) OVER() AS "total!"
+            FROM job_match_scores s
+            JOIN resumes r ON r.id = s.resume_id
+            WHERE s.job_id = $1 AND r.user_id = $2 AND r.deleted_at IS NULL AND s.job_match_version = $3 AND s.score >= $4
+            ORDER BY s.score DESC, r.uploaded_at DESC, r.id
+            LIMIT $5 OFFSET $6
+            "#,
+            job.id,
+            job.user_id,
+            job.match_version,
+            min_score,
+            limit as i64,
+            offset as i64
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(scores)
+    }
+}
+
+#[async_trait]
+pub trait TaxonomyActions {
+    async fn get_skills(&self) -> Result<Vec<Skill>, sqlx::Error>;
+
+    async fn get_skill_aliases(&self) -> Result<Vec<SkillAlias>, sqlx::Error>;
+
+    async fn get_skill_by_name(&self, name: &str) -> Result<Option<Skill>, sqlx::Error>;
+
+    async fn save_skill(
+        &self,
+        name: &str,
+        category: &str,
+        parent_id: Option<Uuid>,
+    ) -> Result<Skill, sqlx::Error>;
+
+    async fn save_skill_alias(&self, alias: &str, skill_id: Uuid) -> Result<SkillAlias, sqlx::Error>;
+
+    async fn delete_skill_alias(&self, alias: &str) -> Result<bool, sqlx::Error>;
+}
+
+#[async_trait]
+impl TaxonomyActions for DBClient {
+    async fn get_skills(&self) -> Result<Vec<Skill>, sqlx::Error> {
+        let skills = sqlx::query_as!(
+            Skill,
+            r#"SELECT id, name, category, parent_id, created_at FROM skills ORDER BY name"#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(skills)
+    }
+
+    async fn get_skill_aliases(&self) -> Result<Vec<SkillAlias>, sqlx::Error> {
+        let aliases = sqlx::query_as!(
+            SkillAlias,
+            r#"SELECT alias, skill_id, created_at FROM skill_aliases ORDER BY alias"#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(aliases)
+    }
+
+    async fn get_skill_by_name(&self, name: &str) -> Result<Option<Skill>, sqlx::Error> {
+        let skill = sqlx::query_as!(
+            Skill,
+            r#"
+            SELECT id, name, category, parent_id, created_at
+            FROM skills
+            WHERE LOWER(name) = LOWER($1)
+            "#,
+            name
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(skill)
+    }
+
+    async fn save_skill(
+        &self,
+        name: &str,
+        category: &str,
+        parent_id: Option<Uuid>,
+    ) -> Result<Skill, sqlx::Error> {
+        let skill = sqlx::query_as!(
+            Skill,
+            r#"
+            INSERT INTO skills (name, category, parent_id)
+            VALUES ($1, $2, $3)
+            RETURNING id, name, category, parent_id, created_at
+            "#,
+            name,
+            category,
+            parent_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(skill)
+    }
+
+    async fn save_skill_alias(&self, alias: &str, skill_id: Uuid) -> Result<SkillAlias, sqlx::Error> {
+        let alias = sqlx::query_as!(
+            SkillAlias,
+            r#"
+            INSERT INTO skill_aliases (alias, skill_id)
+            VALUES ($1, $2)
+            ON CONFLICT (alias) DO UPDATE SET skill_id = EXCLUDED.skill_id
+            RETURNING alias, skill_id, created_at
+            "#,
+            alias,
+            skill_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(alias)
+    }
+
+    async fn delete_skill_alias(&self, alias: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"DELETE FROM skill_aliases WHERE alias = $1"#,
+            alias
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
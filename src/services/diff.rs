@@ -0,0 +1,438 @@
+use std::collections::{BTreeMap, HashSet};
+
+use serde::Serialize;
+use similar::{ChangeTag, TextDiff};
+
+use crate::models::{analysis_result::{AnalysisResult, EducationEntry, ExperienceEntry}, resume::Resume};
+
+use super::{ats::{is_heading, is_standard_heading}, taxonomy::SkillTaxonomy};
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+    Unchanged,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum LineOp {
+    Equal,
+    Insert,
+    Delete,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LineChange {
+    pub op: LineOp,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SectionDiff {
+    /// Empty for the text above the first heading, usually the name and contact details
+    pub heading: String,
+    pub status: ChangeKind,
+    pub before: Option<String>,
+    pub after: Option<String>,
+    pub changes: Vec<LineChange>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Delta<T> {
+    pub before: Option<T>,
+    pub after: Option<T>,
+    pub delta: Option<T>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SkillsDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub unchanged: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EntriesDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AnalysisDiff {
+    pub skills: SkillsDiff,
+    pub scores: BTreeMap<String, Delta<f64>>,
+    pub summary_changed: bool,
+    pub experience: EntriesDiff,
+    pub education: EntriesDiff,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DocumentDiff {
+    pub word_count: Delta<i32>,
+    pub page_count: Delta<i32>,
+    pub ats_score: Delta<i32>,
+}
+
+/// Structured before/after comparison of two resumes, `base` being the older version.
+#[derive(Debug, Clone, Serialize)]
+pub struct ResumeDiff {
+    /// Share of identical lines in the extracted text, 0-1
+    pub text_similarity: f64,
+    pub sections: Vec<SectionDiff>,
+    /// Missing when either resume hasn't been analyzed
+    pub analysis: Option<AnalysisDiff>,
+    pub document: DocumentDiff,
+}
+
+pub fn diff_resumes(base: &Resume, target: &Resume, taxonomy: &SkillTaxonomy) -> ResumeDiff {
+    let base_text = base.extracted_text.as_deref().unwrap_or_default();
+    let target_text = target.extracted_text.as_deref().unwrap_or_default();
+
+    let analysis = match (base.analysis(), target.analysis()) {
+        (Some(before), Some(after)) => Some(diff_analysis(&before, &after, taxonomy)),
+        _ => None,
+    };
+
+    ResumeDiff {
+        text_similarity: round(TextDiff::from_lines(base_text, target_text).ratio() as f64),
+        sections: diff_sections(base_text, target_text),
+        analysis,
+        document: DocumentDiff {
+            word_count: int_delta(base.word_count, target.word_count),
+            page_count: int_delta(base.page_count, target.page_count),
+            ats_score: int_delta(base.ats().map(|ats| ats.score), target.ats().map(|ats| ats.score)),
+        },
+    }
+}
+
+fn round(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+fn int_delta(before: Option<i32>, after: Option<i32>) -> Delta<i32> {
+    Delta {
+        before,
+        after,
+        delta: before.zip(after).map(|(before, after)| after - before),
+    }
+}
+
+fn float_delta(before: Option<f64>, after: Option<f64>) -> Delta<f64> {
+    Delta {
+        before,
+        after,
+        delta: before.zip(after).map(|(before, after)| round(after - before)),
+    }
+}
+
+/// Splits text at section headings. Only headings ATS recognize or lines in capitals count,
+/// otherwise names and job titles would start sections of their own.
+fn split_sections(text: &str) -> Vec<(String, String)> {
+    let mut sections: Vec<(String, Vec<&str>)> = vec![(String::new(), Vec::new())];
+
+    for line in text.lines() {
+        let trimmed = line.trim();
+        let shouty = trimmed.chars().any(char::is_alphabetic) && !trimmed.chars().any(char::is_lowercase);
+        if is_heading(trimmed) && (is_standard_heading(trimmed) || shouty) {
+            sections.push((trimmed.trim_end_matches(':').trim().to_string(), Vec::new()));
+        } else if let Some((_, lines)) = sections.last_mut() {
+            lines.push(line);
+        }
+    }
+
+    sections
+        .into_iter()
+        .map(|(heading, lines)| (heading, lines.join("\n").trim().to_string()))
+        .filter(|(heading, body)| !heading.is_empty() || !body.is_empty())
+        .collect()
+}
+
+fn diff_sections(base: &str, target: &str) -> Vec<SectionDiff> {
+    let before = split_sections(base);
+    let after = split_sections(target);
+
+    let mut headings: Vec<String> = Vec::new();
+    for (heading, _) in before.iter().chain(after.iter()) {
+        if !headings.iter().any(|seen| seen.eq_ignore_ascii_case(heading)) {
+            headings.push(heading.clone());
+        }
+    }
+
+    // Repeated headings are compared as one section
+    let body = |sections: &[(String, String)], heading: &str| -> Option<String> {
+        let parts: Vec<&str> = sections
+            .iter()
+            .filter(|(name, _)| name.eq_ignore_ascii_case(heading))
+            .map(|(_, body)| body.as_str())
+            .collect();
+        (!parts.is_empty()).then(|| parts.join("\n"))
+    };
+
+    headings
+        .into_iter()
+        .map(|heading| {
+            let before = body(&before, &heading);
+            let after = body(&after, &heading);
+            let status = match (&before, &after) {
+                (Some(before), Some(after)) if before == after => ChangeKind::Unchanged,
+                (Some(_), Some(_)) => ChangeKind::Changed,
+                (None, _) => ChangeKind::Added,
+                (_, None) => ChangeKind::Removed,
+            };
+
+            let changes = if status == ChangeKind::Unchanged {
+                Vec::new()
+            } else {
+                // Bodies are trimmed, end both with a newline so appending a line doesn't change the last one
+                let lines = |body: &Option<String>| {
+                    body.as_deref().filter(|body| !body.is_empty()).map(|body| format!("{}\n", body)).unwrap_or_default()
+                };
+                TextDiff::from_lines(&lines(&before), &lines(&after))
+                    .iter_all_changes()
+                    .map(|change| LineChange {
+                        op: match change.tag() {
+                            ChangeTag::Equal => LineOp::Equal,
+                            ChangeTag::Insert => LineOp::Insert,
+                            ChangeTag::Delete => LineOp::Delete,
+                        },
+                        text: change.value().trim_end_matches('\n').to_string(),
+                    })
+                    .collect()
+            };
+
+            SectionDiff {
+                heading,
+                status,
+                before,
+                after,
+                changes,
+            }
+        })
+        .collect()
+}
+
+fn diff_analysis(before: &AnalysisResult, after: &AnalysisResult, taxonomy: &SkillTaxonomy) -> AnalysisDiff {
+    let before_skills = taxonomy.normalize(&before.skills);
+    let after_skills = taxonomy.normalize(&after.skills);
+    let before_keys: HashSet<String> = before_skills.iter().map(|skill| skill.to_lowercase()).collect();
+    let after_keys: HashSet<String> = after_skills.iter().map(|skill| skill.to_lowercase()).collect();
+
+    let skills = SkillsDiff {
+        added: after_skills.iter().filter(|skill| !before_keys.contains(&skill.to_lowercase())).cloned().collect(),
+        removed: before_skills.iter().filter(|skill| !after_keys.contains(&skill.to_lowercase())).cloned().collect(),
+        unchanged: after_skills.iter().filter(|skill| before_keys.contains(&skill.to_lowercase())).cloned().collect(),
+    };
+
+    let scores = [
+        ("overall", before.scores.overall, after.scores.overall),
+        ("skills", before.scores.skills, after.scores.skills),
+        ("experience", before.scores.experience, after.scores.experience),
+        ("education", before.scores.education, after.scores.education),
+        ("formatting", before.scores.formatting, after.scores.formatting),
+    ]
+    .into_iter()
+    .map(|(name, before, after)| (name.to_string(), float_delta(before, after)))
+    .collect();
+
+    AnalysisDiff {
+        skills,
+        scores,
+        summary_changed: before.summary != after.summary,
+        experience: diff_entries(
+            &before.experience.iter().map(experience_label).collect::<Vec<_>>(),
+            &after.experience.iter().map(experience_label).collect::<Vec<_>>(),
+        ),
+        education: diff_entries(
+            &before.education.iter().map(education_label).collect::<Vec<_>>(),
+            &after.education.iter().map(education_label).collect::<Vec<_>>(),
+        ),
+    }
+}
+
+fn label(parts: &[&Option<String>], separator: &str) -> String {
+    parts
+        .iter()
+        .filter_map(|part| part.as_deref())
+        .filter(|part| !part.trim().is_empty())
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+fn experience_label(entry: &ExperienceEntry) -> String {
+    label(&[&entry.title, &entry.company], " at ")
+}
+
+fn education_label(entry: &EducationEntry) -> String {
+    label(&[&entry.degree, &entry.institution], ", ")
+}
+
+fn diff_entries(before: &[String], after: &[String]) -> EntriesDiff {
+    let before_keys: HashSet<String> = before.iter().map(|entry| entry.to_lowercase()).collect();
+    let after_keys: HashSet<String> = after.iter().map(|entry| entry.to_lowercase()).collect();
+
+    EntriesDiff {
+        added: after
+            .iter()
+            .filter(|entry| !entry.is_empty() && !before_keys.contains(&entry.to_lowercase()))
+            .cloned()
+            .collect(),
+        removed: before
+            .iter()
+            .filter(|entry| !entry.is_empty() && !after_keys.contains(&entry.to_lowercase()))
+            .cloned()
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::models::{analysis_result::Scores, skill::{Skill, SkillAlias}};
+
+    use super::*;
+
+    fn taxonomy() -> SkillTaxonomy {
+        let javascript = Skill {
+            id: Uuid::new_v4(),
+            name: "JavaScript".to_string(),
+            category: "language".to_string(),
+            parent_id: None,
+            created_at: None,
+        };
+        let alias = SkillAlias {
+            alias: "js".to_string(),
+            skill_id: javascript.id,
+            created_at: None,
+        };
+        SkillTaxonomy::new(vec![javascript], vec![alias])
+    }
+
+    fn experience(title: &str, company: &str) -> ExperienceEntry {
+        ExperienceEntry {
+            title: Some(title.to_string()),
+            company: Some(company.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn finds_no_changes_between_identical_texts() {
+        let text = "Jane Doe\nEXPERIENCE\nDeveloper at Acme\nSkills\nRust";
+
+        let sections = diff_sections(text, text);
+
+        let headings: Vec<&str> = sections.iter().map(|section| section.heading.as_str()).collect();
+        assert_eq!(headings, vec!["", "EXPERIENCE", "Skills"]);
+        assert!(sections.iter().all(|section| section.status == ChangeKind::Unchanged && section.changes.is_empty()));
+    }
+
+    #[test]
+    fn finds_nothing_in_empty_texts() {
+        assert!(diff_sections("", "").is_empty());
+        assert!(split_sections("\n  \n").is_empty());
+    }
+
+    #[test]
+    fn compares_sections_by_heading() {
+        let base = "Jane Doe\nEXPERIENCE\nDeveloper at Acme\nSKILLS\nRust";
+        let target = "Jane Doe\nExperience:\nDeveloper at Acme\nLead at Globex\nEducation\nBSc, MIT";
+
+        let sections = diff_sections(base, target);
+
+        let statuses: Vec<(&str, ChangeKind)> = sections.iter().map(|section| (section.heading.as_str(), section.status)).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("", ChangeKind::Unchanged),
+                ("EXPERIENCE", ChangeKind::Changed),
+                ("SKILLS", ChangeKind::Removed),
+                ("Education", ChangeKind::Added),
+            ]
+        );
+        let inserted: Vec<&str> = sections[1]
+            .changes
+            .iter()
+            .filter(|change| change.op == LineOp::Insert)
+            .map(|change| change.text.as_str())
+            .collect();
+        assert_eq!(inserted, vec!["Lead at Globex"]);
+        let added: Vec<(LineOp, &str)> = sections[3].changes.iter().map(|change| (change.op, change.text.as_str())).collect();
+        assert_eq!(added, vec![(LineOp::Insert, "BSc, MIT")]);
+    }
+
+    #[test]
+    fn does_not_split_at_names_and_job_titles() {
+        let sections = split_sections("Jane Doe\nSenior Developer\nWORK EXPERIENCE\nAcme");
+
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0].1, "Jane Doe\nSenior Developer");
+    }
+
+    #[test]
+    fn compares_skills_after_normalizing_aliases() {
+        let before = AnalysisResult {
+            skills: vec!["js".to_string(), "Rust".to_string()],
+            ..Default::default()
+        };
+        let after = AnalysisResult {
+            skills: vec!["JavaScript".to_string(), "Go".to_string()],
+            ..Default::default()
+        };
+
+        let diff = diff_analysis(&before, &after, &taxonomy());
+
+        assert_eq!(diff.skills.added, vec!["Go"]);
+        assert_eq!(diff.skills.removed, vec!["Rust"]);
+        assert_eq!(diff.skills.unchanged, vec!["JavaScript"]);
+    }
+
+    #[test]
+    fn reports_score_deltas_and_changed_entries() {
+        let before = AnalysisResult {
+            summary: Some("Developer".to_string()),
+            experience: vec![experience("Developer", "Acme")],
+            scores: Scores {
+                overall: Some(60.0),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let after = AnalysisResult {
+            summary: Some("Team lead".to_string()),
+            experience: vec![experience("developer", "ACME"), experience("Lead", "Globex")],
+            scores: Scores {
+                overall: Some(75.5),
+                skills: Some(80.0),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let diff = diff_analysis(&before, &after, &taxonomy());
+
+        assert_eq!(diff.scores["overall"].delta, Some(15.5));
+        assert_eq!(diff.scores["skills"].delta, None);
+        assert!(diff.summary_changed);
+        assert_eq!(diff.experience.added, vec!["Lead at Globex"]);
+        assert!(diff.experience.removed.is_empty());
+    }
+
+    #[test]
+    fn finds_no_analysis_changes_between_identical_results() {
+        let analysis = AnalysisResult {
+            skills: vec!["Rust".to_string()],
+            experience: vec![experience("Developer", "Acme")],
+            ..Default::default()
+        };
+
+        let diff = diff_analysis(&analysis, &analysis, &taxonomy());
+
+        assert!(diff.skills.added.is_empty() && diff.skills.removed.is_empty());
+        assert!(!diff.summary_changed);
+        assert!(diff.experience.added.is_empty() && diff.experience.removed.is_empty());
+    }
+}
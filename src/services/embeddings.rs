@@ -0,0 +1,376 @@
+use std::{fmt, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use uuid::Uuid;
+
+use crate::{config::Config, models::resume::Resume, utils::error::HttpError, AppState};
+
+use super::database::EmbeddingActions;
+
+/// How many resumes are sent to the embedding provider per request.
+const EMBEDDING_BATCH_SIZE: usize = 16;
+
+/// Upper bound on how much resume text is embedded, the tail rarely changes the meaning.
+const MAX_EMBEDDING_TEXT_CHARS: usize = 8000;
+
+/// Common abbreviations folded into their canonical form before hashing,
+/// so "k8s" and "Kubernetes" land in the same buckets.
+const TERM_ALIASES: &[(&str, &str)] = &[
+    ("k8s", "kubernetes"),
+    ("js", "javascript"),
+    ("ts", "typescript"),
+    ("py", "python"),
+    ("golang", "go"),
+    ("postgres", "postgresql"),
+    ("psql", "postgresql"),
+    ("ml", "machine learning"),
+    ("ai", "artificial intelligence"),
+    ("nlp", "natural language processing"),
+    ("aws", "amazon web services"),
+    ("gcp", "google cloud platform"),
+    ("ci", "continuous integration"),
+    ("cd", "continuous delivery"),
+    ("react.js", "react"),
+    ("reactjs", "react"),
+    ("node.js", "node"),
+    ("nodejs", "node"),
+    ("c#", "csharp"),
+    ("c++", "cplusplus"),
+];
+
+#[derive(Debug)]
+pub enum EmbeddingError {
+    Transport(String),
+    Status { status: u16 },
+    MalformedResponse(String),
+}
+
+impl fmt::Display for EmbeddingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EmbeddingError::Transport(e) => write!(f, "Could not reach embedding service: {}", e),
+            EmbeddingError::Status { status } => write!(f, "Embedding service responded with status {}", status),
+            EmbeddingError::MalformedResponse(e) => {
+                write!(f, "Embedding service returned a malformed response: {}", e)
+            }
+        }
+    }
+}
+
+impl std::error::Error for EmbeddingError {}
+
+impl From<EmbeddingError> for HttpError {
+    fn from(err: EmbeddingError) -> Self {
+        HttpError::new(err.to_string(), axum::http::StatusCode::BAD_GATEWAY)
+    }
+}
+
+#[async_trait]
+pub trait Embedder: fmt::Debug + Send + Sync {
+    /// Identifies the provider and model, embeddings from different models are never compared.
+    fn model_id(&self) -> String;
+
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, EmbeddingError>;
+}
+
+pub fn build_embedder(config: &Config, http_client: reqwest::Client) -> Arc<dyn Embedder> {
+    match config.embedding_provider.as_str() {
+        "http" => Arc::new(HttpEmbedder::new(http_client, config)),
+        _ => Arc::new(LocalEmbedder::new(config.embedding_dimensions)),
+    }
+}
+
+/// CPU-only embedder using the hashing trick over words and character trigrams.
+///
+/// It needs no model files and is deterministic, which makes it a reasonable default
+/// for development and small deployments. Use the HTTP provider for real semantic models.
+#[derive(Debug, Clone, Copy)]
+pub struct LocalEmbedder {
+    dimensions: usize,
+}
+
+impl LocalEmbedder {
+    pub fn new(dimensions: usize) -> Self {
+        LocalEmbedder {
+            dimensions: dimensions.max(16),
+        }
+    }
+
+    fn bucket(&self, feature: &str) -> (usize, f32) {
+        let hash = fnv1a(feature.as_bytes());
+        // The top bit picks the sign so collisions cancel out instead of piling up
+        let sign = if hash >> 63 == 0 { 1.0 } else { -1.0 };
+        ((hash % self.dimensions as u64) as usize, sign)
+    }
+
+    fn embed_text(&self, text: &str) -> Vec<f32> {
+        let mut vector = vec![0.0f32; self.dimensions];
+
+        for term in normalized_terms(text) {
+            let (index, sign) = self.bucket(&term);
+            vector[index] += sign;
+
+            let padded = format!(" {} ", term);
+            let chars: Vec<char> = padded.chars().collect();
+            for trigram in chars.windows(3) {
+                let trigram: String = trigram.iter().collect();
+                let (index, sign) = self.bucket(&trigram);
+                vector[index] += sign * 0.5;
+            }
+        }
+
+        normalize(&mut vector);
+        vector
+    }
+}
+
+#[async_trait]
+impl Embedder for LocalEmbedder {
+    fn model_id(&self) -> String {
+        format!("local-hashing-{}", self.dimensions)
+    }
+
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, EmbeddingError> {
+        Ok(texts.iter().map(|text| self.embed_text(text)).collect())
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct EmbeddingRequest<'a> {
+    texts: &'a [String],
+    model: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+struct EmbeddingResponse {
+    embeddings: Vec<Vec<f32>>,
+}
+
+/// Asks the NLP service for embeddings: `POST {"texts": [..], "model": ..}` returning `{"embeddings": [[..]]}`.
+#[derive(Debug, Clone)]
+pub struct HttpEmbedder {
+    http_client: reqwest::Client,
+    url: String,
+    model: String,
+    timeout: Duration,
+    auth_header: Option<String>,
+}
+
+impl HttpEmbedder {
+    pub fn new(http_client: reqwest::Client, config: &Config) -> Self {
+        HttpEmbedder {
+            http_client,
+            url: config.embedding_service_url.clone(),
+            model: config.embedding_model.clone(),
+            timeout: Duration::from_secs(config.nlp_timeout_secs),
+            auth_header: config.nlp_auth_header.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl Embedder for HttpEmbedder {
+    fn model_id(&self) -> String {
+        format!("http-{}", self.model)
+    }
+
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, EmbeddingError> {
+        let mut request = self
+            .http_client
+            .post(&self.url)
+            .timeout(self.timeout)
+            .json(&EmbeddingRequest {
+                texts,
+                model: &self.model,
+            });
+
+        if let Some(auth_header) = &self.auth_header {
+            request = request.header(reqwest::header::AUTHORIZATION, auth_header);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| EmbeddingError::Transport(e.to_string()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            // The body can echo resume text back, so it goes to the log rather than to the client
+            let body = response.text().await.unwrap_or_default();
+            tracing::warn!("Embedding service responded with status {}: {}", status, body);
+            return Err(EmbeddingError::Status { status: status.as_u16() });
+        }
+
+        let body: EmbeddingResponse = response
+            .json()
+            .await
+            .map_err(|e| EmbeddingError::MalformedResponse(e.to_string()))?;
+
+        if body.embeddings.len() != texts.len() {
+            return Err(EmbeddingError::MalformedResponse(format!(
+                "expected {} embeddings, got {}",
+                texts.len(),
+                body.embeddings.len()
+            )));
+        }
+
+        let mut embeddings = body.embeddings;
+        embeddings.iter_mut().for_each(|vector| normalize(vector));
+        Ok(embeddings)
+    }
+}
+
+/// FNV-1a, stored vectors depend on it so it must never change between releases.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+fn normalized_terms(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| c.is_whitespace() || matches!(c, ',' | ';' | '/' | '(' | ')' | '|'))
+        .map(|term| term.trim_matches(|c: char| !c.is_alphanumeric() && c != '#' && c != '+'))
+        .filter(|term| !term.is_empty())
+        .flat_map(|term| {
+            let canonical = TERM_ALIASES
+                .iter()
+                .find(|(alias, _)| *alias == term)
+                .map(|(_, canonical)| *canonical)
+                .unwrap_or(term);
+            canonical
+                .split_whitespace()
+                .map(|word| word.to_string())
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+fn normalize(vector: &mut [f32]) {
+    let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|v| *v /= norm);
+    }
+}
+
+/// Cosine similarity of two unit vectors, zero when the dimensions disagree.
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+/// The text a resume is embedded from: the structured analysis first, then the document body.
+pub fn embedding_text(resume: &Resume) -> String {
+    let mut parts: Vec<String> = Vec::new();
+
+    if let Some(analysis) = &resume.analysis_result {
+        if let Some(skills) = analysis.get("skills").and_then(|s| s.as_array()) {
+            let skills: Vec<&str> = skills.iter().filter_map(|s| s.as_str()).collect();
+            parts.push(skills.join(", "));
+        }
+        if let Some(summary) = analysis.get("summary").and_then(|s| s.as_str()) {
+            parts.push(summary.to_string());
+        }
+    }
+
+    if let Some(text) = &resume.extracted_text {
+        parts.push(text.to_string());
+    }
+
+    let text = parts.join("\n");
+    match text.char_indices().nth(MAX_EMBEDDING_TEXT_CHARS) {
+        Some((index, _)) => text[..index].to_string(),
+        None => text,
+    }
+}
+
+/// Computes and stores embeddings for `resumes` with the configured provider.
+pub async fn embed_resumes(app_state: &AppState, resumes: &[Resume]) -> Result<(), HttpError> {
+    let model = app_state.embedder.model_id();
+
+    for batch in resumes.chunks(EMBEDDING_BATCH_SIZE) {
+        let texts: Vec<String> = batch.iter().map(embedding_text).collect();
+        let embeddings = app_state.embedder.embed(&texts).await?;
+
+        for (resume, embedding) in batch.iter().zip(embeddings) {
+            app_state
+                .db_client
+                .save_embedding(resume.id, &model, &embedding)
+                .await
+                .map_err(|e| HttpError::server_error(e.to_string()))?;
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn embed_query(app_state: &AppState, text: &str) -> Result<Vec<f32>, HttpError> {
+    let mut embeddings = app_state.embedder.embed(&[text.to_string()]).await?;
+    embeddings
+        .pop()
+        .ok_or_else(|| HttpError::server_error("Embedding provider returned no vector"))
+}
+
+pub async fn get_resume_embedding(app_state: &AppState, resume: &Resume) -> Result<Vec<f32>, HttpError> {
+    let model = app_state.embedder.model_id();
+
+    let stored = app_state
+        .db_client
+        .get_embedding(resume.id, &model)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    match stored {
+        Some(stored) => Ok(stored.embedding),
+        None => {
+            let embedding = embed_query(app_state, &embedding_text(resume)).await?;
+            app_state
+                .db_client
+                .save_embedding(resume.id, &model, &embedding)
+                .await
+                .map_err(|e| HttpError::server_error(e.to_string()))?;
+            Ok(embedding)
+        }
+    }
+}
+
+/// Ranks the user's resumes by cosine similarity to `query`, embedding any that are missing first.
+pub async fn find_similar(
+    app_state: &AppState,
+    user_id: Uuid,
+    query: &[f32],
+    exclude: Option<Uuid>,
+    limit: usize,
+    min_score: f32,
+) -> Result<Vec<(Uuid, f32)>, HttpError> {
+    let model = app_state.embedder.model_id();
+
+    let missing = app_state
+        .db_client
+        .get_resumes_without_embedding(user_id, &model)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+    embed_resumes(app_state, &missing).await?;
+
+    let embeddings = app_state
+        .db_client
+        .get_user_embeddings(user_id, &model)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    let mut scored: Vec<(Uuid, f32)> = embeddings
+        .iter()
+        .filter(|embedding| Some(embedding.resume_id) != exclude)
+        .map(|embedding| (embedding.resume_id, cosine_similarity(query, &embedding.embedding)))
+        .filter(|(_, score)| *score >= min_score)
+        .collect();
+
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+    scored.truncate(limit);
+
+    Ok(scored)
+}
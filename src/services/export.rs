@@ -0,0 +1,612 @@
+use std::{collections::HashMap, io, sync::Arc};
+
+use bytes::{BufMut, Bytes, BytesMut};
+use chrono::{DateTime, Utc};
+use futures_util::{stream, Stream};
+use lopdf::{content::{Content, Operation}, dictionary, Document, Object, Stream as PdfStream};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{models::{ats::AtsSeverity, resume::{Resume, ResumeDocument}, tag::ResumeTag}, services::{database::{DocumentActions, TagActions, UserActions}, redaction::{redact_document, redact_resume}}, AppState};
+
+/// Documents fetched per chunk of a streamed export.
+const EXPORT_PAGE_SIZE: usize = 50;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Csv,
+    Ndjson,
+}
+
+impl ExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+            ExportFormat::Ndjson => "application/x-ndjson",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Ndjson => "ndjson",
+        }
+    }
+}
+
+/// One exported resume, the current version of a document.
+#[derive(Debug, Serialize)]
+pub struct ExportRow {
+    pub document_id: Uuid,
+    pub resume_id: Uuid,
+    pub title: String,
+    pub file_name: String,
+    pub version: i32,
+    pub uploaded_at: Option<DateTime<Utc>>,
+    pub analysis_status: String,
+    pub stage: Option<String>,
+    pub tags: Vec<String>,
+    pub name: Option<String>,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+    pub location: Option<String>,
+    pub skills: Vec<String>,
+    pub overall_score: Option<f64>,
+    pub skills_score: Option<f64>,
+    pub experience_score: Option<f64>,
+    pub education_score: Option<f64>,
+    pub formatting_score: Option<f64>,
+    pub ats_score: Option<i32>,
+}
+
+const CSV_HEADER: [&str; 20] = [
+    "document_id", "resume_id", "title", "file_name", "version", "uploaded_at", "analysis_status", "stage", "tags",
+    "name", "email", "phone", "location", "skills", "overall_score", "skills_score", "experience_score",
+    "education_score", "formatting_score", "ats_score",
+];
+
+impl ExportRow {
+    pub fn new(document: &ResumeDocument, resume: &Resume, tags: &[ResumeTag], stage: Option<&String>) -> Self {
+        let analysis = resume.analysis().unwrap_or_default();
+
+        ExportRow {
+            document_id: document.id,
+            resume_id: resume.id,
+            title: document.title.clone(),
+            file_name: resume.original_file_name(),
+            version: resume.version,
+            uploaded_at: resume.uploaded_at,
+            analysis_status: resume.analysis_status.to_str().to_string(),
+            stage: stage.cloned(),
+            tags: tags
+                .iter()
+                .filter(|tag| tag.resume_id == resume.id)
+                .map(|tag| tag.name.clone())
+                .collect(),
+            name: analysis.contact.name,
+            email: analysis.contact.email,
+            phone: analysis.contact.phone,
+            location: analysis.contact.location,
+            skills: analysis.skills,
+            overall_score: analysis.scores.overall,
+            skills_score: analysis.scores.skills,
+            experience_score: analysis.scores.experience,
+            education_score: analysis.scores.education,
+            formatting_score: analysis.scores.formatting,
+            ats_score: resume.ats().map(|report| report.score),
+        }
+    }
+
+    fn csv_fields(&self) -> [String; 20] {
+        let text = |value: &Option<String>| value.clone().unwrap_or_default();
+        let number = |value: Option<f64>| value.map(|v| v.to_string()).unwrap_or_default();
+
+        [
+            self.document_id.to_string(),
+            self.resume_id.to_string(),
+            self.title.clone(),
+            self.file_name.clone(),
+            self.version.to_string(),
+            self.uploaded_at.map(|at| at.to_rfc3339()).unwrap_or_default(),
+            self.analysis_status.clone(),
+            text(&self.stage),
+            self.tags.join("; "),
+            text(&self.name),
+            text(&self.email),
+            text(&self.phone),
+            text(&self.location),
+            self.skills.join("; "),
+            number(self.overall_score),
+            number(self.skills_score),
+            number(self.experience_score),
+            number(self.education_score),
+            number(self.formatting_score),
+            self.ats_score.map(|score| score.to_string()).unwrap_or_default(),
+        ]
+    }
+
+    fn write(&self, format: ExportFormat, out: &mut BytesMut) -> Result<(), io::Error> {
+        match format {
+            ExportFormat::Csv => write_csv_record(out, self.csv_fields().iter().map(String::as_str)),
+            ExportFormat::Ndjson => {
+                serde_json::to_writer(out.writer(), self)?;
+                out.put_u8(b'\n');
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes one RFC 4180 record. Cells that a spreadsheet would read as a formula are prefixed
+/// with a quote so exported resume content cannot run in the recipient's spreadsheet.
+fn write_csv_record<'a>(out: &mut BytesMut, fields: impl Iterator<Item = &'a str>) {
+    for (i, field) in fields.enumerate() {
+        if i > 0 {
+            out.put_u8(b',');
+        }
+
+        let guarded = match field.chars().next() {
+            Some('=' | '+' | '-' | '@' | '\t' | '\r') => format!("'{}", field),
+            _ => field.to_string(),
+        };
+
+        if guarded.contains([',', '"', '\r', '\n']) {
+            out.put_u8(b'"');
+            out.put_slice(guarded.replace('"', "\"\"").as_bytes());
+            out.put_u8(b'"');
+        } else {
+            out.put_slice(guarded.as_bytes());
+        }
+    }
+    out.put_slice(b"\r\n");
+}
+
+struct ExportState {
+    app_state: Arc<AppState>,
+    user_id: Uuid,
+    tags: Vec<String>,
+    stages: HashMap<Uuid, String>,
+    blind: bool,
+    format: ExportFormat,
+    page: u32,
+    done: bool,
+}
+
+/// Streams the current version of each of a user's documents, a page of documents per chunk,
+/// so large exports are never held in memory. A failing page ends the stream with an error.
+pub fn export_resumes(
+    app_state: Arc<AppState>,
+    user_id: Uuid,
+    tags: Vec<String>,
+    stages: HashMap<Uuid, String>,
+    blind: bool,
+    format: ExportFormat,
+) -> impl Stream<Item = Result<Bytes, io::Error>> {
+    let state = ExportState { app_state, user_id, tags, stages, blind, format, page: 1, done: false };
+
+    stream::unfold(state, |mut state| async move {
+        if state.done {
+            return None;
+        }
+
+        match export_page(&mut state).await {
+            Ok(chunk) => Some((Ok(chunk), state)),
+            Err(e) => {
+                tracing::warn!("Resume export for user {} failed: {}", state.user_id, e);
+                state.done = true;
+                Some((Err(e), state))
+            }
+        }
+    })
+}
+
+async fn export_page(state: &mut ExportState) -> Result<Bytes, io::Error> {
+    let db_client = &state.app_state.db_client;
+    let mut out = BytesMut::new();
+
+    if state.page == 1 && state.format == ExportFormat::Csv {
+        write_csv_record(&mut out, CSV_HEADER.into_iter());
+    }
+
+    let documents = db_client
+        .get_documents(state.user_id, state.page, EXPORT_PAGE_SIZE, &state.tags)
+        .await
+        .map_err(io::Error::other)?;
+    state.page += 1;
+    state.done = documents.len() < EXPORT_PAGE_SIZE;
+
+    let current_ids: Vec<Uuid> = documents.iter().filter_map(|document| document.current_resume_id).collect();
+    if current_ids.is_empty() {
+        return Ok(out.freeze());
+    }
+
+    let tags = db_client
+        .get_tags_for_resumes(state.user_id, &current_ids)
+        .await
+        .map_err(io::Error::other)?;
+    let current: HashMap<Uuid, Resume> = db_client
+        .get_resumes_by_ids(state.user_id, &current_ids)
+        .await
+        .map_err(io::Error::other)?
+        .into_iter()
+        .map(|resume| (resume.id, if state.blind { redact_resume(&resume) } else { resume }))
+        .collect();
+
+    for document in &documents {
+        let Some(resume) = document.current_resume_id.and_then(|id| current.get(&id)) else {
+            continue;
+        };
+        let document = if state.blind { redact_document(document) } else { document.clone() };
+
+        ExportRow::new(&document, resume, &tags, state.stages.get(&resume.stage_id))
+            .write(state.format, &mut out)?;
+    }
+
+    Ok(out.freeze())
+}
+
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 50.0;
+const FOOTER_Y: f32 = 30.0;
+
+/// Advance widths of the printable ASCII characters in Helvetica, in 1/1000 of the font size.
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556, 556, 556, 556, 556,
+    556, 556, 556, 556, 278, 278, 584, 584, 584, 556, 1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667,
+    556, 833, 722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556, 333, 556,
+    556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556, 556, 556, 333, 500, 278, 556, 500, 722,
+    500, 500, 500, 334, 260, 334, 584,
+];
+
+#[derive(Clone, Copy)]
+enum Font {
+    Regular,
+    Bold,
+}
+
+impl Font {
+    fn resource(&self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+        }
+    }
+
+    /// Bold glyphs are wider, scaling the regular metrics keeps wrapped lines inside the margin.
+    fn text_width(&self, text: &str, size: f32) -> f32 {
+        let units: u32 = text
+            .chars()
+            .map(|c| match c {
+                ' '..='~' => HELVETICA_WIDTHS[c as usize - 32] as u32,
+                _ => 556,
+            })
+            .sum();
+        let scale = match self {
+            Font::Regular => 1.0,
+            Font::Bold => 1.1,
+        };
+        units as f32 * size / 1000.0 * scale
+    }
+}
+
+/// Maps text onto WinAnsiEncoding, the encoding of the standard PDF fonts, replacing what it
+/// cannot represent.
+fn win_ansi(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| match c {
+            ' '..='~' => c as u8,
+            '\u{a0}'..='\u{ff}' => c as u32 as u8,
+            '\t' | '\n' | '\r' => b' ',
+            '€' => 0x80,
+            '…' => 0x85,
+            '‘' => 0x91,
+            '’' => 0x92,
+            '“' => 0x93,
+            '”' => 0x94,
+            '•' => 0x95,
+            '–' => 0x96,
+            '—' => 0x97,
+            _ => b'?',
+        })
+        .collect()
+}
+
+/// Lays text out top to bottom over as many A4 pages as it needs.
+struct PdfLayout {
+    pages: Vec<Vec<Operation>>,
+    y: f32,
+}
+
+impl PdfLayout {
+    fn new() -> Self {
+        PdfLayout { pages: vec![Vec::new()], y: PAGE_HEIGHT - MARGIN }
+    }
+
+    fn ensure_space(&mut self, height: f32) {
+        if self.y - height < MARGIN {
+            self.pages.push(Vec::new());
+            self.y = PAGE_HEIGHT - MARGIN;
+        }
+    }
+
+    fn text_at(ops: &mut Vec<Operation>, font: Font, size: f32, x: f32, y: f32, text: &str) {
+        ops.push(Operation::new("BT", vec![]));
+        ops.push(Operation::new("Tf", vec![font.resource().into(), size.into()]));
+        ops.push(Operation::new("Td", vec![x.into(), y.into()]));
+        ops.push(Operation::new("Tj", vec![Object::string_literal(win_ansi(text))]));
+        ops.push(Operation::new("ET", vec![]));
+    }
+
+    fn line(&mut self, text: &str, font: Font, size: f32, indent: f32) {
+        let leading = size * 1.4;
+        self.ensure_space(leading);
+        self.y -= leading;
+        let y = self.y;
+        if let Some(ops) = self.pages.last_mut() {
+            Self::text_at(ops, font, size, MARGIN + indent, y, text);
+        }
+    }
+
+    /// Writes `text` word-wrapped to the page width, breaking words longer than a line.
+    fn paragraph(&mut self, text: &str, font: Font, size: f32, indent: f32) {
+        let max_width = PAGE_WIDTH - 2.0 * MARGIN - indent;
+
+        for source_line in text.lines() {
+            let mut line = String::new();
+            for word in source_line.split_whitespace() {
+                let candidate = if line.is_empty() { word.to_string() } else { format!("{} {}", line, word) };
+                if font.text_width(&candidate, size) <= max_width {
+                    line = candidate;
+                    continue;
+                }
+
+                if !line.is_empty() {
+                    self.line(&line, font, size, indent);
+                    line.clear();
+                }
+                for c in word.chars() {
+                    line.push(c);
+                    if font.text_width(&line, size) > max_width {
+                        line.pop();
+                        self.line(&line, font, size, indent);
+                        line = c.to_string();
+                    }
+                }
+            }
+            if !line.is_empty() {
+                self.line(&line, font, size, indent);
+            }
+        }
+    }
+
+    fn heading(&mut self, text: &str) {
+        // Keep a heading on the same page as the first line below it
+        self.ensure_space(40.0);
+        self.gap(8.0);
+        self.line(text, Font::Bold, 13.0, 0.0);
+        self.gap(2.0);
+    }
+
+    fn gap(&mut self, height: f32) {
+        self.y -= height;
+    }
+
+    fn into_document(mut self, title: &str, footer: &str) -> Result<Vec<u8>, lopdf::Error> {
+        let page_count = self.pages.len();
+        for (i, ops) in self.pages.iter_mut().enumerate() {
+            let text = format!("{} - Page {} of {}", footer, i + 1, page_count);
+            Self::text_at(ops, Font::Regular, 8.0, MARGIN, FOOTER_Y, &text);
+        }
+
+        let mut doc = Document::with_version("1.5");
+        let pages_id = doc.new_object_id();
+        let regular_id = doc.add_object(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type1",
+            "BaseFont" => "Helvetica",
+            "Encoding" => "WinAnsiEncoding",
+        });
+        let bold_id = doc.add_object(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type1",
+            "BaseFont" => "Helvetica-Bold",
+            "Encoding" => "WinAnsiEncoding",
+        });
+        let resources_id = doc.add_object(dictionary! {
+            "Font" => dictionary! {
+                "F1" => regular_id,
+                "F2" => bold_id,
+            },
+        });
+
+        let mut kids: Vec<Object> = Vec::with_capacity(page_count);
+        for operations in self.pages {
+            let content = Content { operations }.encode()?;
+            let content_id = doc.add_object(PdfStream::new(dictionary! {}, content));
+            let page_id = doc.add_object(dictionary! {
+                "Type" => "Page",
+                "Parent" => pages_id,
+                "Contents" => content_id,
+            });
+            kids.push(page_id.into());
+        }
+
+        doc.objects.insert(
+            pages_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Kids" => kids,
+                "Count" => page_count as i64,
+                "Resources" => resources_id,
+                "MediaBox" => vec![0.into(), 0.into(), PAGE_WIDTH.into(), PAGE_HEIGHT.into()],
+            }),
+        );
+        let catalog_id = doc.add_object(dictionary! {
+            "Type" => "Catalog",
+            "Pages" => pages_id,
+        });
+        let info_id = doc.add_object(dictionary! {
+            "Title" => Object::string_literal(win_ansi(title)),
+            "Producer" => Object::string_literal("ra-backend"),
+        });
+        doc.trailer.set("Root", catalog_id);
+        doc.trailer.set("Info", info_id);
+        doc.compress();
+
+        let mut bytes = Vec::new();
+        doc.save_to(&mut bytes)?;
+        Ok(bytes)
+    }
+}
+
+fn date_range(start: &Option<String>, end: &Option<String>) -> Option<String> {
+    match (start, end) {
+        (Some(start), Some(end)) => Some(format!("{} – {}", start, end)),
+        (Some(start), None) => Some(format!("{} – present", start)),
+        (None, Some(end)) => Some(end.clone()),
+        (None, None) => None,
+    }
+}
+
+/// Renders a resume's analysis and ATS report as a printable PDF.
+pub fn render_analysis_pdf(resume: &Resume, tags: &[ResumeTag]) -> Result<Vec<u8>, lopdf::Error> {
+    let analysis = resume.analysis();
+    let title = analysis
+        .as_ref()
+        .and_then(|analysis| analysis.contact.name.clone())
+        .unwrap_or_else(|| resume.original_file_name());
+
+    let mut layout = PdfLayout::new();
+    layout.line(&title, Font::Bold, 20.0, 0.0);
+    layout.paragraph(
+        &format!(
+            "{} • version {} • uploaded {} • analysis {}",
+            resume.original_file_name(),
+            resume.version,
+            resume.uploaded_at.map(|at| at.format("%Y-%m-%d").to_string()).unwrap_or_default(),
+            resume.analysis_status.to_str()
+        ),
+        Font::Regular,
+        9.0,
+        0.0,
+    );
+
+    let tag_names: Vec<&str> = tags
+        .iter()
+        .filter(|tag| tag.resume_id == resume.id)
+        .map(|tag| tag.name.as_str())
+        .collect();
+    if !tag_names.is_empty() {
+        layout.paragraph(&format!("Tags: {}", tag_names.join(", ")), Font::Regular, 9.0, 0.0);
+    }
+
+    let Some(analysis) = analysis else {
+        layout.heading("Analysis");
+        layout.paragraph("No analysis is available for this resume yet.", Font::Regular, 10.0, 0.0);
+        return layout.into_document(&title, &footer());
+    };
+
+    let scores = [
+        ("Overall", analysis.scores.overall),
+        ("Skills", analysis.scores.skills),
+        ("Experience", analysis.scores.experience),
+        ("Education", analysis.scores.education),
+        ("Formatting", analysis.scores.formatting),
+    ];
+    if scores.iter().any(|(_, score)| score.is_some()) {
+        layout.heading("Scores");
+        for (name, score) in scores {
+            if let Some(score) = score {
+                layout.line(&format!("{}: {:.0} / 100", name, score), Font::Regular, 10.0, 0.0);
+            }
+        }
+    }
+
+    let contact = &analysis.contact;
+    let contact_lines: Vec<String> = [&contact.email, &contact.phone, &contact.location]
+        .into_iter()
+        .flatten()
+        .cloned()
+        .chain(contact.links.iter().cloned())
+        .collect();
+    if !contact_lines.is_empty() {
+        layout.heading("Contact");
+        for line in contact_lines {
+            layout.paragraph(&line, Font::Regular, 10.0, 0.0);
+        }
+    }
+
+    if let Some(summary) = &analysis.summary {
+        layout.heading("Summary");
+        layout.paragraph(summary, Font::Regular, 10.0, 0.0);
+    }
+
+    if !analysis.skills.is_empty() {
+        layout.heading("Skills");
+        layout.paragraph(&analysis.skills.join(", "), Font::Regular, 10.0, 0.0);
+    }
+
+    if !analysis.experience.is_empty() {
+        layout.heading("Experience");
+        for entry in &analysis.experience {
+            let role = [&entry.title, &entry.company]
+                .into_iter()
+                .flatten()
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(", ");
+            layout.gap(4.0);
+            layout.paragraph(&role, Font::Bold, 10.0, 0.0);
+            if let Some(dates) = date_range(&entry.start_date, &entry.end_date) {
+                layout.line(&dates, Font::Regular, 9.0, 0.0);
+            }
+            if let Some(description) = &entry.description {
+                layout.paragraph(description, Font::Regular, 10.0, 10.0);
+            }
+        }
+    }
+
+    if !analysis.education.is_empty() {
+        layout.heading("Education");
+        for entry in &analysis.education {
+            let degree = match (&entry.degree, &entry.field) {
+                (Some(degree), Some(field)) => Some(format!("{} in {}", degree, field)),
+                (degree, field) => degree.clone().or_else(|| field.clone()),
+            };
+            let line = [degree, entry.institution.clone(), date_range(&entry.start_date, &entry.end_date)]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join(", ");
+            layout.paragraph(&line, Font::Regular, 10.0, 0.0);
+        }
+    }
+
+    if let Some(ats) = resume.ats() {
+        layout.heading("ATS compatibility");
+        layout.line(
+            &format!("Score {} / 100, {}", ats.score, if ats.passed { "passed" } else { "failed" }),
+            Font::Regular,
+            10.0,
+            0.0,
+        );
+        for issue in &ats.issues {
+            let severity = match issue.severity {
+                AtsSeverity::Info => "Info",
+                AtsSeverity::Warning => "Warning",
+                AtsSeverity::Critical => "Critical",
+            };
+            layout.gap(3.0);
+            layout.paragraph(&format!("{}: {}", severity, issue.message), Font::Bold, 10.0, 10.0);
+            layout.paragraph(&issue.suggestion, Font::Regular, 10.0, 10.0);
+        }
+    }
+
+    layout.into_document(&title, &footer())
+}
+
+fn footer() -> String {
+    format!("Resume analysis report, generated {}", Utc::now().format("%Y-%m-%d %H:%M UTC"))
+}
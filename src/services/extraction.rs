@@ -0,0 +1,683 @@
+use std::{
+    fmt,
+    io::{Cursor, Read},
+    path::Path,
+};
+
+use bytes::Bytes;
+use quick_xml::{events::Event, Reader};
+use serde::{Deserialize, Serialize};
+use zip::ZipArchive;
+
+use crate::services::{bulk::MAX_COMPRESSION_RATIO, importers::{import_linkedin, import_xml, is_linkedin_export, is_resume_xml}, json_resume::JsonResume};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum DocumentFormat {
+    Pdf,
+    Docx,
+    Odt,
+    Rtf,
+    Txt,
+    /// A JSON Resume document, read as the resume it describes
+    Json,
+    /// A LinkedIn "Download your data" archive
+    LinkedIn,
+    /// A Europass CV or HR-XML resume
+    Xml,
+}
+
+impl DocumentFormat {
+    pub fn to_str(self) -> &'static str {
+        match self {
+            DocumentFormat::Pdf => "pdf",
+            DocumentFormat::Docx => "docx",
+            DocumentFormat::Odt => "odt",
+            DocumentFormat::Rtf => "rtf",
+            DocumentFormat::Txt => "txt",
+            DocumentFormat::Json => "json",
+            DocumentFormat::LinkedIn => "linkedin",
+            DocumentFormat::Xml => "xml",
+        }
+    }
+
+    /// Sniffs the format from the file contents, falling back to the extension for JSON Resume and plain text.
+    pub fn detect(file_name: &str, bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(b"%PDF") {
+            return Some(DocumentFormat::Pdf);
+        }
+
+        if bytes.starts_with(b"{\\rtf") {
+            return Some(DocumentFormat::Rtf);
+        }
+
+        if bytes.starts_with(b"PK\x03\x04") {
+            let archive = ZipArchive::new(Cursor::new(bytes)).ok()?;
+            let names: Vec<&str> = archive.file_names().collect();
+            if names.contains(&"word/document.xml") {
+                return Some(DocumentFormat::Docx);
+            }
+            if names.contains(&"content.xml") {
+                return Some(DocumentFormat::Odt);
+            }
+            if is_linkedin_export(&archive) {
+                return Some(DocumentFormat::LinkedIn);
+            }
+            return None;
+        }
+
+        if is_resume_xml(bytes) {
+            return Some(DocumentFormat::Xml);
+        }
+
+        let extension = Path::new(file_name)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_lowercase());
+
+        match extension.as_deref() {
+            Some("json") => Some(DocumentFormat::Json),
+            Some("txt") | Some("text") | Some("md") => Some(DocumentFormat::Txt),
+            _ if std::str::from_utf8(bytes).is_ok() => Some(DocumentFormat::Txt),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ExtractionError {
+    UnsupportedFormat(String),
+    Corrupt(String),
+}
+
+impl fmt::Display for ExtractionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExtractionError::UnsupportedFormat(name) => {
+                write!(f, "Unsupported document format for {}", name)
+            }
+            ExtractionError::Corrupt(e) => write!(f, "Could not read document: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ExtractionError {}
+
+/// Structural features of the source document that text extraction throws away.
+#[derive(Debug, Clone, Serialize)]
+pub struct DocumentLayout {
+    pub tables: i32,
+    /// Highest column count of any section, 1 for a single column layout
+    pub columns: i32,
+    pub images: i32,
+    pub text_boxes: i32,
+    pub fonts: i32,
+    /// Fonts whose glyphs are drawn as vector outlines (PDF Type 3), text in them is often unreadable
+    pub outline_fonts: i32,
+}
+
+impl Default for DocumentLayout {
+    fn default() -> Self {
+        DocumentLayout {
+            tables: 0,
+            columns: 1,
+            images: 0,
+            text_boxes: 0,
+            fonts: 0,
+            outline_fonts: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExtractedDocument {
+    pub format: DocumentFormat,
+    pub text: String,
+    pub page_count: Option<i32>,
+    pub word_count: i32,
+    pub language: Option<String>,
+    pub layout: DocumentLayout,
+}
+
+/// Pulls plain text and basic statistics out of an uploaded resume.
+///
+/// This is CPU bound and can be slow for large PDFs, call it from `spawn_blocking`.
+pub fn extract(file_name: &str, bytes: &[u8]) -> Result<ExtractedDocument, ExtractionError> {
+    let format = DocumentFormat::detect(file_name, bytes)
+        .ok_or_else(|| ExtractionError::UnsupportedFormat(file_name.to_string()))?;
+
+    let (text, page_count, layout) = match format {
+        DocumentFormat::Pdf => extract_pdf(bytes)?,
+        DocumentFormat::Docx => extract_docx(bytes)?,
+        DocumentFormat::Odt => extract_odt(bytes)?,
+        DocumentFormat::Rtf => {
+            let rtf = String::from_utf8_lossy(bytes);
+            (extract_rtf(&rtf), None, rtf_layout(&rtf))
+        }
+        DocumentFormat::Txt => {
+            let text = String::from_utf8_lossy(bytes).into_owned();
+            let layout = DocumentLayout {
+                columns: text_columns(&text),
+                ..Default::default()
+            };
+            (text, None, layout)
+        }
+        DocumentFormat::Json => {
+            let resume = JsonResume::from_slice(bytes).map_err(ExtractionError::Corrupt)?;
+            (resume.to_text(), None, DocumentLayout::default())
+        }
+        DocumentFormat::LinkedIn => {
+            let resume = import_linkedin(bytes).map_err(ExtractionError::Corrupt)?;
+            (resume.to_text(), None, DocumentLayout::default())
+        }
+        DocumentFormat::Xml => {
+            let resume = import_xml(bytes).map_err(ExtractionError::Corrupt)?;
+            (resume.to_text(), None, DocumentLayout::default())
+        }
+    };
+
+    let text = normalize_whitespace(&text);
+    let word_count = text.split_whitespace().count() as i32;
+    let language = whatlang::detect(&text)
+        .filter(|info| info.is_reliable())
+        .map(|info| info.lang().code().to_string());
+
+    Ok(ExtractedDocument {
+        format,
+        text,
+        page_count,
+        word_count,
+        language,
+        layout,
+    })
+}
+
+/// Runs `extract` on the blocking thread pool.
+pub async fn extract_document(file_name: String, bytes: Bytes) -> Result<ExtractedDocument, ExtractionError> {
+    tokio::task::spawn_blocking(move || extract(&file_name, &bytes))
+        .await
+        .map_err(|e| ExtractionError::Corrupt(e.to_string()))?
+}
+
+fn normalize_whitespace(text: &str) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    let mut blank = false;
+
+    for line in text.lines() {
+        let line = line.split_whitespace().collect::<Vec<_>>().join(" ");
+        if line.is_empty() {
+            if !blank && !lines.is_empty() {
+                lines.push(String::new());
+            }
+            blank = true;
+        } else {
+            lines.push(line);
+            blank = false;
+        }
+    }
+
+    lines.join("\n").trim().to_string()
+}
+
+fn extract_pdf(bytes: &[u8]) -> Result<(String, Option<i32>, DocumentLayout), ExtractionError> {
+    let document = lopdf::Document::load_mem(bytes).map_err(|e| ExtractionError::Corrupt(e.to_string()))?;
+    let page_count = document.get_pages().len() as i32;
+
+    // pdf-extract panics on some malformed fonts instead of returning an error
+    let text = std::panic::catch_unwind(|| pdf_extract::extract_text_from_mem(bytes))
+        .map_err(|_| ExtractionError::Corrupt("PDF text extraction failed".to_string()))?
+        .map_err(|e| ExtractionError::Corrupt(e.to_string()))?;
+
+    let layout = pdf_layout(&document, &text);
+
+    Ok((text, Some(page_count), layout))
+}
+
+fn pdf_layout(document: &lopdf::Document, text: &str) -> DocumentLayout {
+    let mut layout = DocumentLayout {
+        columns: text_columns(text),
+        ..Default::default()
+    };
+
+    for object in document.objects.values() {
+        let dictionary = match object {
+            lopdf::Object::Dictionary(dictionary) => dictionary,
+            lopdf::Object::Stream(stream) => &stream.dict,
+            _ => continue,
+        };
+        let has_name = |key: &[u8], value: &[u8]| {
+            dictionary
+                .get(key)
+                .and_then(|object| object.as_name())
+                .is_ok_and(|name| name == value)
+        };
+
+        if has_name(b"Subtype", b"Image") {
+            layout.images += 1;
+        }
+        if has_name(b"Type", b"Font") {
+            layout.fonts += 1;
+            if has_name(b"Subtype", b"Type3") {
+                layout.outline_fonts += 1;
+            }
+        }
+    }
+
+    layout
+}
+
+/// Guesses the number of side by side columns from runs of spacing inside lines,
+/// which is how multi-column layouts come out of PDF and plain text extraction.
+fn text_columns(text: &str) -> i32 {
+    let lines: Vec<&str> = text.lines().filter(|line| !line.trim().is_empty()).collect();
+    let split_lines = lines
+        .iter()
+        .filter(|line| {
+            let line = line.trim();
+            line.contains('\t') || line.contains("    ")
+        })
+        .count();
+
+    if split_lines >= 5 && split_lines * 10 >= lines.len() * 3 {
+        2
+    } else {
+        1
+    }
+}
+
+fn count_occurrences(haystack: &str, needle: &str) -> i32 {
+    haystack.matches(needle).count() as i32
+}
+
+/// The largest number found right after any occurrence of `pattern`.
+fn max_number_after(haystack: &str, pattern: &str) -> Option<i32> {
+    haystack
+        .match_indices(pattern)
+        .filter_map(|(index, _)| {
+            haystack[index + pattern.len()..]
+                .chars()
+                .take_while(|c| c.is_ascii_digit())
+                .collect::<String>()
+                .parse()
+                .ok()
+        })
+        .max()
+}
+
+/// Office XML parts are read into memory whole, a resume never comes close to this
+const MAX_XML_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Reads a part of a DOCX or ODT package, `None` when it is missing or not text. Parts that
+/// would inflate past `MAX_XML_BYTES` or expand suspiciously much are refused as zip bombs.
+fn read_zip_entry(archive: &mut ZipArchive<Cursor<&[u8]>>, name: &str) -> Result<Option<String>, ExtractionError> {
+    let Ok(entry) = archive.by_name(name) else {
+        return Ok(None);
+    };
+
+    let declared = entry.size();
+    if declared > MAX_XML_BYTES {
+        return Err(ExtractionError::Corrupt(format!("{} is larger than {} bytes", name, MAX_XML_BYTES)));
+    }
+    if entry.compressed_size() > 0 && declared / entry.compressed_size() > MAX_COMPRESSION_RATIO {
+        return Err(ExtractionError::Corrupt(format!("{} is compressed too heavily to be extracted safely", name)));
+    }
+
+    // Headers can lie about sizes, so cap what is actually read as well
+    let mut data = Vec::new();
+    entry
+        .take(MAX_XML_BYTES + 1)
+        .read_to_end(&mut data)
+        .map_err(|e| ExtractionError::Corrupt(e.to_string()))?;
+    if data.len() as u64 > MAX_XML_BYTES {
+        return Err(ExtractionError::Corrupt(format!("{} is larger than its archive entry claims", name)));
+    }
+
+    Ok(String::from_utf8(data).ok())
+}
+
+/// Collects the text of an office XML document, starting a new line at the end of
+/// every element in `paragraph_tags` and inserting whitespace for break elements.
+fn xml_text(
+    xml: &str,
+    text_tags: &[&[u8]],
+    paragraph_tags: &[&[u8]],
+    break_tags: &[(&[u8], char)],
+) -> Result<String, ExtractionError> {
+    let mut reader = Reader::from_str(xml);
+    let mut text = String::new();
+    let mut in_text = 0usize;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) if text_tags.contains(&e.name().as_ref()) => in_text += 1,
+            Ok(Event::End(e)) => {
+                let name = e.name();
+                if text_tags.contains(&name.as_ref()) {
+                    in_text = in_text.saturating_sub(1);
+                }
+                if paragraph_tags.contains(&name.as_ref()) {
+                    text.push('\n');
+                }
+            }
+            Ok(Event::Empty(e)) => {
+                if let Some((_, c)) = break_tags.iter().find(|(tag, _)| *tag == e.name().as_ref()) {
+                    text.push(*c);
+                }
+            }
+            Ok(Event::Text(e)) if in_text > 0 => {
+                let content = e
+                    .unescape()
+                    .map_err(|e| ExtractionError::Corrupt(e.to_string()))?;
+                text.push_str(&content);
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(ExtractionError::Corrupt(e.to_string())),
+            _ => {}
+        }
+    }
+
+    Ok(text)
+}
+
+fn xml_number(xml: &str, pattern: &str) -> Option<i32> {
+    let start = xml.find(pattern)? + pattern.len();
+    let digits: String = xml[start..]
+        .chars()
+        .skip_while(|c| !c.is_ascii_digit())
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    digits.parse().ok()
+}
+
+fn extract_docx(bytes: &[u8]) -> Result<(String, Option<i32>, DocumentLayout), ExtractionError> {
+    let mut archive =
+        ZipArchive::new(Cursor::new(bytes)).map_err(|e| ExtractionError::Corrupt(e.to_string()))?;
+
+    let document = read_zip_entry(&mut archive, "word/document.xml")?
+        .ok_or_else(|| ExtractionError::Corrupt("missing word/document.xml".to_string()))?;
+
+    let text = xml_text(
+        &document,
+        &[b"w:t"],
+        &[b"w:p", b"w:tr"],
+        &[(b"w:tab", '\t'), (b"w:br", '\n'), (b"w:cr", '\n')],
+    )?;
+
+    // Word only knows the page count as of the last save, it is recorded in the app properties
+    let page_count = read_zip_entry(&mut archive, "docProps/app.xml")?
+        .and_then(|app| xml_number(&app, "<Pages>"));
+
+    let fonts = read_zip_entry(&mut archive, "word/fontTable.xml")?
+        .map(|fonts| count_occurrences(&fonts, "<w:font "))
+        .unwrap_or(0);
+    let layout = DocumentLayout {
+        tables: count_occurrences(&document, "<w:tbl>"),
+        columns: max_number_after(&document, "<w:cols w:num=\"").unwrap_or(1).max(1),
+        images: count_occurrences(&document, "<pic:pic"),
+        text_boxes: count_occurrences(&document, "<w:txbxContent"),
+        fonts,
+        outline_fonts: 0,
+    };
+
+    Ok((text, page_count, layout))
+}
+
+fn extract_odt(bytes: &[u8]) -> Result<(String, Option<i32>, DocumentLayout), ExtractionError> {
+    let mut archive =
+        ZipArchive::new(Cursor::new(bytes)).map_err(|e| ExtractionError::Corrupt(e.to_string()))?;
+
+    let content = read_zip_entry(&mut archive, "content.xml")?
+        .ok_or_else(|| ExtractionError::Corrupt("missing content.xml".to_string()))?;
+
+    let text = xml_text(
+        &content,
+        &[b"text:p", b"text:h", b"text:span", b"text:a"],
+        &[b"text:p", b"text:h"],
+        &[(b"text:tab", '\t'), (b"text:line-break", '\n'), (b"text:s", ' ')],
+    )?;
+
+    let page_count = read_zip_entry(&mut archive, "meta.xml")?
+        .and_then(|meta| xml_number(&meta, "meta:page-count="));
+
+    // Column styles can live in either the automatic styles of the content or in styles.xml
+    let styles = read_zip_entry(&mut archive, "styles.xml")?.unwrap_or_default();
+    let columns = [&content, &styles]
+        .iter()
+        .filter_map(|xml| max_number_after(xml, "fo:column-count=\""))
+        .max()
+        .unwrap_or(1)
+        .max(1);
+    let layout = DocumentLayout {
+        tables: count_occurrences(&content, "<table:table "),
+        columns,
+        images: count_occurrences(&content, "<draw:image"),
+        text_boxes: count_occurrences(&content, "<draw:text-box"),
+        fonts: count_occurrences(&content, "<style:font-face "),
+        outline_fonts: 0,
+    };
+
+    Ok((text, page_count, layout))
+}
+
+/// Destinations whose contents are metadata rather than document text.
+const RTF_SKIPPED_DESTINATIONS: &[&str] = &[
+    "fonttbl", "colortbl", "stylesheet", "info", "pict", "header", "footer", "object",
+    "listtable", "listoverridetable", "rsidtbl", "generator", "themedata", "datastore",
+];
+
+fn rtf_layout(rtf: &str) -> DocumentLayout {
+    DocumentLayout {
+        tables: count_occurrences(rtf, "\\trowd"),
+        columns: max_number_after(rtf, "\\cols").unwrap_or(1).max(1),
+        images: count_occurrences(rtf, "\\pict"),
+        text_boxes: count_occurrences(rtf, "\\shptxt"),
+        fonts: 0,
+        outline_fonts: 0,
+    }
+}
+
+fn extract_rtf(rtf: &str) -> String {
+    let mut text = String::new();
+    // Each entry records whether the group is skipped
+    let mut groups: Vec<bool> = vec![false];
+    let mut chars = rtf.chars().peekable();
+    let mut pending_unicode_skip = 0usize;
+
+    while let Some(c) = chars.next() {
+        let skipping = *groups.last().unwrap_or(&false);
+        match c {
+            '{' => groups.push(skipping),
+            '}' => {
+                groups.pop();
+            }
+            '\\' => {
+                let Some(&next) = chars.peek() else { break };
+                if !next.is_ascii_alphabetic() {
+                    chars.next();
+                    match next {
+                        '*' => {
+                            if let Some(group) = groups.last_mut() {
+                                *group = true;
+                            }
+                        }
+                        '\'' => {
+                            let hex: String = chars.by_ref().take(2).collect();
+                            let byte = u8::from_str_radix(&hex, 16);
+                            if pending_unicode_skip > 0 {
+                                pending_unicode_skip -= 1;
+                            } else if let (false, Ok(byte)) = (skipping, byte) {
+                                text.push(byte as char);
+                            }
+                        }
+                        '~' if !skipping => text.push(' '),
+                        '\\' | '{' | '}' if !skipping => text.push(next),
+                        _ => {}
+                    }
+                    continue;
+                }
+
+                let mut word = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_ascii_alphabetic() {
+                        word.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+
+                let mut param = String::new();
+                if chars.peek() == Some(&'-') {
+                    param.push('-');
+                    chars.next();
+                }
+                while let Some(&c) = chars.peek() {
+                    if c.is_ascii_digit() {
+                        param.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                if chars.peek() == Some(&' ') {
+                    chars.next();
+                }
+
+                if RTF_SKIPPED_DESTINATIONS.contains(&word.as_str()) {
+                    if let Some(group) = groups.last_mut() {
+                        *group = true;
+                    }
+                    continue;
+                }
+
+                if skipping {
+                    continue;
+                }
+
+                match word.as_str() {
+                    "par" | "line" | "row" | "sect" | "page" => text.push('\n'),
+                    "tab" | "cell" => text.push('\t'),
+                    "emdash" => text.push('\u{2014}'),
+                    "endash" => text.push('\u{2013}'),
+                    "bullet" => text.push('\u{2022}'),
+                    "u" => {
+                        if let Ok(code) = param.parse::<i32>() {
+                            // Negative values wrap 16-bit code points
+                            let code = if code < 0 { code + 65536 } else { code };
+                            if let Some(c) = char::from_u32(code as u32) {
+                                text.push(c);
+                            }
+                            pending_unicode_skip = 1;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            '\r' | '\n' => {}
+            _ if skipping => {}
+            _ if pending_unicode_skip > 0 => pending_unicode_skip -= 1,
+            _ => text.push(c),
+        }
+    }
+
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use zip::{write::SimpleFileOptions, ZipWriter};
+
+    use super::*;
+
+    fn zip(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, data) in entries {
+            writer.start_file(*name, SimpleFileOptions::default()).unwrap();
+            writer.write_all(data).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    fn docx(document: &str) -> Vec<u8> {
+        zip(&[("[Content_Types].xml", b"<Types/>"), ("word/document.xml", document.as_bytes())])
+    }
+
+    #[test]
+    fn detects_formats_from_contents_before_extensions() {
+        assert_eq!(DocumentFormat::detect("cv.txt", b"%PDF-1.7"), Some(DocumentFormat::Pdf));
+        assert_eq!(DocumentFormat::detect("cv.doc", b"{\\rtf1 Hi}"), Some(DocumentFormat::Rtf));
+        assert_eq!(DocumentFormat::detect("cv.zip", &docx("<w:document/>")), Some(DocumentFormat::Docx));
+        assert_eq!(
+            DocumentFormat::detect("cv.zip", &zip(&[("content.xml", b"<office:document-content/>")])),
+            Some(DocumentFormat::Odt)
+        );
+        assert_eq!(DocumentFormat::detect("cv.docx", &zip(&[("notes.txt", b"hi")])), None);
+        assert_eq!(DocumentFormat::detect("cv.json", b"{\"basics\": {}}"), Some(DocumentFormat::Json));
+        assert_eq!(DocumentFormat::detect("cv", b"Plain text"), Some(DocumentFormat::Txt));
+        assert_eq!(DocumentFormat::detect("cv.bin", &[0xff, 0xfe, 0x00, 0x81]), None);
+    }
+
+    #[test]
+    fn reads_rtf_escapes() {
+        let rtf = r"{\rtf1\ansi{\fonttbl{\f0 Arial;}}{\info{\title Secret}}{\*\generator Writer;}\pard Caf\'e9 \u-1279?le\par Next\tab line}";
+
+        assert_eq!(extract_rtf(rtf), "Caf\u{e9} \u{fb01}le\nNext\tline");
+    }
+
+    #[test]
+    fn skips_the_fallback_after_rtf_unicode() {
+        assert_eq!(extract_rtf(r"{\rtf1 \u8364\'80 5\u8212-}"), "\u{20ac} 5\u{2014}");
+    }
+
+    #[test]
+    fn reads_text_of_office_xml() {
+        let xml = "<w:body><w:p><w:r><w:t>Hello</w:t></w:r><w:r><w:tab/><w:t>World &amp; co</w:t></w:r></w:p>\
+                   <w:p><w:r><w:t>Next</w:t><w:br/><w:t>line</w:t></w:r></w:p><w:sectPr>ignored</w:sectPr></w:body>";
+
+        let text = xml_text(xml, &[b"w:t"], &[b"w:p"], &[(b"w:tab", '\t'), (b"w:br", '\n')]).unwrap();
+
+        assert_eq!(text, "Hello\tWorld & co\nNext\nline\n");
+    }
+
+    #[test]
+    fn extracts_docx_text_and_layout() {
+        let document = docx(
+            "<w:document><w:body><w:tbl><w:tr><w:tc><w:p><w:r><w:t>Rust   developer</w:t></w:r></w:p></w:tc></w:tr></w:tbl>\
+             <w:p/><w:p/><w:p><w:r><w:t>Berlin</w:t></w:r></w:p></w:body></w:document>",
+        );
+
+        let extracted = extract("cv.docx", &document).unwrap();
+
+        assert_eq!(extracted.format, DocumentFormat::Docx);
+        assert_eq!(extracted.text, "Rust developer\n\nBerlin");
+        assert_eq!(extracted.word_count, 3);
+        assert_eq!(extracted.layout.tables, 1);
+    }
+
+    #[test]
+    fn refuses_office_parts_that_inflate_too_much() {
+        let padding = " ".repeat(4 * 1024 * 1024);
+        let document = docx(&format!("<w:document><w:p><w:r><w:t>Hi{}</w:t></w:r></w:p></w:document>", padding));
+
+        let error = extract("cv.docx", &document).unwrap_err();
+
+        assert!(error.to_string().contains("compressed too heavily"), "{}", error);
+    }
+
+    #[test]
+    fn guesses_columns_from_spaced_out_lines() {
+        let two_columns = "Experience      Skills\n".repeat(6);
+        let one_column = "Experience\nSkills\n".repeat(6);
+
+        assert_eq!(text_columns(&two_columns), 2);
+        assert_eq!(text_columns(&one_column), 1);
+        assert_eq!(text_columns(&format!("{}{}", "Name\tEmail\n".repeat(5), "Line\n".repeat(20))), 1);
+    }
+
+    #[test]
+    fn collapses_whitespace_and_blank_lines() {
+        assert_eq!(normalize_whitespace("\n  Jane   Doe \t\n\n\n\n Rust\u{a0}developer\n\n"), "Jane Doe\n\nRust developer");
+    }
+}
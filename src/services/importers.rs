@@ -0,0 +1,1019 @@
+use std::{collections::HashMap, io::{Cursor, Read}, path::Path, sync::LazyLock};
+
+use quick_xml::{events::Event, Reader};
+use regex::Regex;
+use zip::ZipArchive;
+
+use crate::{models::analysis_result::{AnalysisResult, ContactInfo, EducationEntry, ExperienceEntry}, services::json_resume::JsonResume};
+
+/// Recorded as the analysis model version of imported resumes.
+pub const IMPORT_MODEL_VERSION: &str = "1";
+
+/// Largest CSV read from a LinkedIn archive, the export's files are far smaller.
+const MAX_CSV_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Europass and HR-XML nest a dozen levels at most, deeper documents are refused rather than
+/// walked recursively.
+const MAX_XML_DEPTH: usize = 64;
+
+const LINKEDIN_FILES: &[&str] = &["profile.csv", "positions.csv", "education.csv", "skills.csv"];
+
+static HTML_TAG: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"<[^>]*>").unwrap());
+
+static WEB_URL: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"https?://[^\s,\]"]+"#).unwrap());
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImportSource {
+    LinkedIn,
+    Europass,
+    HrXml,
+}
+
+impl ImportSource {
+    /// Stored as the analysis model, so imported results are told apart from NLP ones.
+    pub fn model(&self) -> &str {
+        match self {
+            ImportSource::LinkedIn => "linkedin-import",
+            ImportSource::Europass => "europass-import",
+            ImportSource::HrXml => "hr-xml-import",
+        }
+    }
+}
+
+/// A resume read from a structured source, already in the shape the NLP service returns.
+#[derive(Debug, Clone)]
+pub struct ImportedResume {
+    pub source: ImportSource,
+    pub analysis: AnalysisResult,
+}
+
+impl ImportedResume {
+    /// Plain text of the resume for search, ATS checks and embeddings.
+    pub fn to_text(&self) -> String {
+        JsonResume::from_analysis(&self.analysis).to_text()
+    }
+}
+
+/// Whether the ZIP holds a LinkedIn "Download your data" export.
+pub fn is_linkedin_export(archive: &ZipArchive<Cursor<&[u8]>>) -> bool {
+    archive
+        .file_names()
+        .any(|name| LINKEDIN_FILES.contains(&base_name(name).as_str()))
+}
+
+fn base_name(path: &str) -> String {
+    Path::new(path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+}
+
+fn clean(value: &str) -> Option<String> {
+    let value = value.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!value.is_empty()).then_some(value)
+}
+
+/// Text of a description that may hold HTML, as Europass rich text fields do.
+fn plain_text(value: &str) -> Option<String> {
+    let text = HTML_TAG.replace_all(&value.replace("</p>", "\n").replace("<br", "\n<br"), " ").into_owned();
+    let text = text
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'");
+    let lines: Vec<String> = text.lines().filter_map(clean).collect();
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+fn finish(source: ImportSource, mut analysis: AnalysisResult) -> Result<ImportedResume, String> {
+    analysis.skills.retain(|skill| !skill.trim().is_empty());
+    let mut seen = std::collections::HashSet::new();
+    analysis.skills.retain(|skill| seen.insert(skill.to_lowercase()));
+
+    let empty = analysis.contact.name.is_none()
+        && analysis.summary.is_none()
+        && analysis.skills.is_empty()
+        && analysis.experience.is_empty()
+        && analysis.education.is_empty();
+    if empty {
+        return Err("The document has no resume content".to_string());
+    }
+
+    analysis.validate()?;
+    Ok(ImportedResume { source, analysis })
+}
+
+/// Splits CSV text into records, following RFC 4180 quoting.
+fn parse_csv(text: &str) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.trim_start_matches('\u{feff}').chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => record.push(std::mem::take(&mut field)),
+            '\r' if !quoted => {}
+            '\n' if !quoted => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            c => field.push(c),
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+
+    records
+}
+
+/// Rows of a CSV as column name to value. Some exports open with notes, the header is the first
+/// row naming `key_column`.
+fn csv_rows(text: &str, key_column: &str) -> Vec<HashMap<String, String>> {
+    let mut records = parse_csv(text).into_iter();
+    let Some(header) = records.by_ref().find(|record| record.iter().any(|column| column.trim() == key_column)) else {
+        return Vec::new();
+    };
+    let header: Vec<String> = header.iter().map(|column| column.trim().to_string()).collect();
+
+    records
+        .filter(|record| record.iter().any(|value| !value.trim().is_empty()))
+        .map(|record| header.iter().cloned().zip(record).collect())
+        .collect()
+}
+
+fn column(row: &HashMap<String, String>, name: &str) -> Option<String> {
+    row.get(name).and_then(|value| clean(value))
+}
+
+fn read_csv(archive: &mut ZipArchive<Cursor<&[u8]>>, file: &str) -> Result<Option<String>, String> {
+    let Some(index) = (0..archive.len()).find(|&index| {
+        archive
+            .name_for_index(index)
+            .is_some_and(|name| base_name(name) == file)
+    }) else {
+        return Ok(None);
+    };
+
+    let entry = archive.by_index(index).map_err(|e| format!("Could not read {}: {}", file, e))?;
+    if entry.encrypted() {
+        return Err("Encrypted archives are not supported".to_string());
+    }
+
+    let mut bytes = Vec::new();
+    entry
+        .take(MAX_CSV_BYTES + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|e| format!("Could not read {}: {}", file, e))?;
+    if bytes.len() as u64 > MAX_CSV_BYTES {
+        return Err(format!("{} is larger than {} bytes", file, MAX_CSV_BYTES));
+    }
+
+    Ok(Some(String::from_utf8_lossy(&bytes).into_owned()))
+}
+
+/// Reads a LinkedIn data export: the profile, positions, education, skills, email and phone CSVs.
+pub fn import_linkedin(bytes: &[u8]) -> Result<ImportedResume, String> {
+    let mut archive = ZipArchive::new(Cursor::new(bytes)).map_err(|e| format!("Could not read archive: {}", e))?;
+    if !is_linkedin_export(&archive) {
+        return Err("The archive is not a LinkedIn data export".to_string());
+    }
+
+    let mut analysis = AnalysisResult::default();
+
+    if let Some(csv) = read_csv(&mut archive, "profile.csv")?
+        && let Some(profile) = csv_rows(&csv, "First Name").into_iter().next()
+    {
+        let name = [column(&profile, "First Name"), column(&profile, "Last Name")]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(" ");
+        analysis.contact = ContactInfo {
+            name: clean(&name),
+            location: column(&profile, "Geo Location").or_else(|| column(&profile, "Address")),
+            links: column(&profile, "Websites")
+                .map(|websites| WEB_URL.find_iter(&websites).map(|url| url.as_str().to_string()).collect())
+                .unwrap_or_default(),
+            ..Default::default()
+        };
+        analysis.summary = column(&profile, "Summary").or_else(|| column(&profile, "Headline"));
+    }
+
+    if let Some(csv) = read_csv(&mut archive, "email addresses.csv")? {
+        let emails = csv_rows(&csv, "Email Address");
+        analysis.contact.email = emails
+            .iter()
+            .find(|row| column(row, "Primary").is_some_and(|primary| primary.eq_ignore_ascii_case("yes")))
+            .or(emails.first())
+            .and_then(|row| column(row, "Email Address"));
+    }
+
+    if let Some(csv) = read_csv(&mut archive, "phonenumbers.csv")? {
+        analysis.contact.phone = csv_rows(&csv, "Number").iter().find_map(|row| column(row, "Number"));
+    }
+
+    if let Some(csv) = read_csv(&mut archive, "positions.csv")? {
+        analysis.experience = csv_rows(&csv, "Company Name")
+            .iter()
+            .map(|row| ExperienceEntry {
+                title: column(row, "Title"),
+                company: column(row, "Company Name"),
+                start_date: column(row, "Started On"),
+                end_date: column(row, "Finished On"),
+                description: column(row, "Description"),
+            })
+            .collect();
+    }
+
+    if let Some(csv) = read_csv(&mut archive, "education.csv")? {
+        analysis.education = csv_rows(&csv, "School Name")
+            .iter()
+            .map(|row| EducationEntry {
+                institution: column(row, "School Name"),
+                degree: column(row, "Degree Name"),
+                field: None,
+                start_date: column(row, "Start Date"),
+                end_date: column(row, "End Date"),
+            })
+            .collect();
+    }
+
+    if let Some(csv) = read_csv(&mut archive, "skills.csv")? {
+        analysis.skills = csv_rows(&csv, "Name").iter().filter_map(|row| column(row, "Name")).collect();
+    }
+
+    finish(ImportSource::LinkedIn, analysis)
+}
+
+/// Just enough of an XML tree to read Europass and HR-XML documents. Namespace prefixes are
+/// dropped, the formats mix several namespaces for the same concepts.
+#[derive(Debug, Default)]
+struct XmlNode {
+    name: String,
+    attributes: Vec<(String, String)>,
+    text: String,
+    children: Vec<XmlNode>,
+}
+
+impl XmlNode {
+    fn parse(bytes: &[u8]) -> Result<XmlNode, String> {
+        let mut reader = Reader::from_reader(bytes);
+        let mut stack = vec![XmlNode::default()];
+
+        loop {
+            match reader.read_event() {
+                Ok(Event::Start(e)) => {
+                    if stack.len() > MAX_XML_DEPTH {
+                        return Err("The XML document is nested too deeply".to_string());
+                    }
+                    stack.push(XmlNode::from_start(&e));
+                }
+                Ok(Event::Empty(e)) => {
+                    let node = XmlNode::from_start(&e);
+                    if let Some(parent) = stack.last_mut() {
+                        parent.children.push(node);
+                    }
+                }
+                Ok(Event::End(_)) => {
+                    if stack.len() < 2 {
+                        return Err("Malformed XML".to_string());
+                    }
+                    if let (Some(node), Some(parent)) = (stack.pop(), stack.last_mut()) {
+                        parent.children.push(node);
+                    }
+                }
+                Ok(Event::Text(e)) => {
+                    let text = e.unescape().map_err(|e| format!("Malformed XML: {}", e))?;
+                    if let Some(node) = stack.last_mut() {
+                        node.text.push_str(&text);
+                    }
+                }
+                Ok(Event::CData(e)) => {
+                    if let Some(node) = stack.last_mut() {
+                        node.text.push_str(&String::from_utf8_lossy(&e));
+                    }
+                }
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(format!("Malformed XML: {}", e)),
+                _ => {}
+            }
+        }
+
+        stack
+            .pop()
+            .filter(|_| stack.is_empty())
+            .and_then(|document| document.children.into_iter().next())
+            .ok_or_else(|| "Malformed XML".to_string())
+    }
+
+    fn from_start(e: &quick_xml::events::BytesStart) -> XmlNode {
+        XmlNode {
+            name: String::from_utf8_lossy(e.local_name().as_ref()).into_owned(),
+            attributes: e
+                .attributes()
+                .flatten()
+                .map(|attribute| {
+                    let key = String::from_utf8_lossy(attribute.key.local_name().as_ref()).into_owned();
+                    let value = attribute.unescape_value().map(|value| value.into_owned()).unwrap_or_default();
+                    (key, value)
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn child(&self, name: &str) -> Option<&XmlNode> {
+        self.children.iter().find(|child| child.name == name)
+    }
+
+    fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a XmlNode> {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+
+    /// The node at the end of `path`, each step a child of the one before.
+    fn at(&self, path: &[&str]) -> Option<&XmlNode> {
+        path.iter().try_fold(self, |node, name| node.child(name))
+    }
+
+    fn descendant(&self, name: &str) -> Option<&XmlNode> {
+        self.children
+            .iter()
+            .find_map(|child| if child.name == name { Some(child) } else { child.descendant(name) })
+    }
+
+    fn descendants<'a>(&'a self, name: &str, found: &mut Vec<&'a XmlNode>) {
+        for child in &self.children {
+            if child.name == name {
+                found.push(child);
+            } else {
+                child.descendants(name, found);
+            }
+        }
+    }
+
+    fn all(&self, name: &str) -> Vec<&XmlNode> {
+        let mut found = Vec::new();
+        self.descendants(name, &mut found);
+        found
+    }
+
+    fn text(&self) -> Option<String> {
+        clean(&self.text)
+    }
+
+    fn text_at(&self, path: &[&str]) -> Option<String> {
+        self.at(path).and_then(XmlNode::text)
+    }
+
+    /// All text under the node, for containers that wrap their value in an element of varying name.
+    fn deep_text(&self) -> Option<String> {
+        self.text().or_else(|| self.children.iter().find_map(XmlNode::deep_text))
+    }
+}
+
+const RESUME_XML_ROOTS: &[&str] = &["SkillsPassport", "Candidate", "Resume"];
+
+/// Whether the bytes are XML whose root element is one `import_xml` reads, without parsing the rest.
+pub fn is_resume_xml(bytes: &[u8]) -> bool {
+    let mut reader = Reader::from_reader(bytes);
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e)) => {
+                return RESUME_XML_ROOTS.contains(&String::from_utf8_lossy(e.local_name().as_ref()).as_ref());
+            }
+            Ok(Event::Eof) | Err(_) => return false,
+            Ok(Event::Text(e)) if !e.iter().all(u8::is_ascii_whitespace) => return false,
+            _ => {}
+        }
+    }
+}
+
+/// Reads a Europass CV (the XML of the classic Europass editor, or the newer HR Open Standards
+/// based one) or an HR-XML resume.
+pub fn import_xml(bytes: &[u8]) -> Result<ImportedResume, String> {
+    let root = XmlNode::parse(bytes)?;
+
+    match root.name.as_str() {
+        "SkillsPassport" => import_europass_v3(&root),
+        "Candidate" => import_candidate(&root),
+        "Resume" => import_hr_xml_resume(&root),
+        other => Err(format!("Unsupported XML document <{}>, expected a Europass CV or an HR-XML resume", other)),
+    }
+}
+
+/// Europass dates are split into `year`, `month` ("--MM") and `day` ("---DD") attributes.
+fn europass_date(node: Option<&XmlNode>) -> Option<String> {
+    let node = node?;
+    let year = node.attribute("year")?.trim().to_string();
+    match node.attribute("month").map(|month| month.trim_start_matches('-')) {
+        Some(month) if !month.is_empty() => Some(format!("{}-{}", year, month)),
+        _ => Some(year),
+    }
+}
+
+/// Splits free-text skill descriptions into individual skills, keeping only short entries.
+fn skill_list(description: &str) -> Vec<String> {
+    plain_text(description)
+        .unwrap_or_default()
+        .split([',', ';', '\n', '•', '|'])
+        .filter_map(clean)
+        .map(|skill| skill.trim_start_matches(['-', '*']).trim().to_string())
+        .filter(|skill| !skill.is_empty() && skill.split_whitespace().count() <= 4)
+        .collect()
+}
+
+fn import_europass_v3(root: &XmlNode) -> Result<ImportedResume, String> {
+    let learner = root
+        .child("LearnerInfo")
+        .ok_or_else(|| "The Europass CV has no LearnerInfo".to_string())?;
+    let identification = learner.child("Identification");
+    let contact_info = identification.and_then(|identification| identification.child("ContactInfo"));
+
+    let name = identification
+        .and_then(|identification| identification.child("PersonName"))
+        .map(|person| {
+            [person.text_at(&["FirstName"]), person.text_at(&["Surname"])]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join(" ")
+        });
+
+    let location = contact_info.and_then(|info| info.at(&["Address", "Contact"])).and_then(|address| {
+        let parts: Vec<String> = [address.text_at(&["Municipality"]), address.text_at(&["Country", "Label"])]
+            .into_iter()
+            .flatten()
+            .collect();
+        (!parts.is_empty()).then(|| parts.join(", "))
+    });
+
+    let contact = ContactInfo {
+        name: name.as_deref().and_then(clean),
+        email: contact_info.and_then(|info| info.text_at(&["Email", "Contact"])),
+        phone: contact_info.and_then(|info| info.at(&["TelephoneList", "Telephone", "Contact"])).and_then(XmlNode::text),
+        location,
+        links: contact_info
+            .and_then(|info| info.child("WebsiteList"))
+            .map(|list| list.children("Website").filter_map(|site| site.text_at(&["Contact"])).collect())
+            .unwrap_or_default(),
+    };
+
+    let experience = learner
+        .child("WorkExperienceList")
+        .map(|list| {
+            list.children("WorkExperience")
+                .map(|work| {
+                    let period = work.child("Period");
+                    let current = period
+                        .and_then(|period| period.text_at(&["Current"]))
+                        .is_some_and(|current| current == "true");
+                    ExperienceEntry {
+                        title: work.text_at(&["Position", "Label"]),
+                        company: work.text_at(&["Employer", "Name"]),
+                        start_date: europass_date(period.and_then(|period| period.child("From"))),
+                        end_date: if current { None } else { europass_date(period.and_then(|period| period.child("To"))) },
+                        description: work.child("Activities").and_then(|activities| plain_text(&activities.text)),
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let education = learner
+        .child("EducationList")
+        .map(|list| {
+            list.children("Education")
+                .map(|education| {
+                    let period = education.child("Period");
+                    EducationEntry {
+                        institution: education.text_at(&["Organisation", "Name"]),
+                        degree: education.text_at(&["Title"]),
+                        field: education.text_at(&["Field", "Label"]),
+                        start_date: europass_date(period.and_then(|period| period.child("From"))),
+                        end_date: europass_date(period.and_then(|period| period.child("To"))),
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let skills = ["Computer", "JobRelated", "Other"]
+        .iter()
+        .filter_map(|kind| learner.at(&["Skills", kind, "Description"]))
+        .flat_map(|description| skill_list(&description.text))
+        .collect();
+
+    finish(
+        ImportSource::Europass,
+        AnalysisResult {
+            contact,
+            summary: learner.text_at(&["Headline", "Description", "Label"]),
+            skills,
+            experience,
+            education,
+            ..Default::default()
+        },
+    )
+}
+
+/// HR Open Standards dates wrap the value in `FormattedDateTime`, `DateText` or similar.
+fn candidate_date(node: Option<&XmlNode>) -> Option<String> {
+    let text = node?.deep_text()?;
+    (!["current", "present", "notknown", "notapplicable"].contains(&text.to_lowercase().as_str())).then_some(text)
+}
+
+fn import_candidate(root: &XmlNode) -> Result<ImportedResume, String> {
+    let person = root.child("CandidatePerson");
+    let profile = root.child("CandidateProfile");
+
+    let name = person.and_then(|person| person.child("PersonName")).and_then(|name| {
+        name.text_at(&["FormattedName"]).or_else(|| {
+            clean(
+                &[name.text_at(&["GivenName"]), name.text_at(&["FamilyName"])]
+                    .into_iter()
+                    .flatten()
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            )
+        })
+    });
+
+    let mut contact = ContactInfo { name, ..Default::default() };
+    for communication in person.map(|person| person.all("Communication")).unwrap_or_default() {
+        let channel = communication.text_at(&["ChannelCode"]).unwrap_or_default().to_lowercase();
+        match channel.as_str() {
+            "email" => contact.email = contact.email.take().or_else(|| communication.text_at(&["URI"])),
+            "telephone" | "mobile" | "phone" => {
+                let number = communication.text_at(&["FormattedNumber"]).or_else(|| {
+                    let dial = communication.text_at(&["DialNumber"])?;
+                    Some(match communication.text_at(&["CountryDialing"]) {
+                        Some(country) => format!("+{} {}", country.trim_start_matches('+'), dial),
+                        None => dial,
+                    })
+                });
+                contact.phone = contact.phone.take().or(number);
+            }
+            "web" | "instantmessage" => contact.links.extend(communication.text_at(&["URI"])),
+            _ => {}
+        }
+        if contact.location.is_none()
+            && let Some(address) = communication.child("Address")
+        {
+            let parts: Vec<String> = [address.text_at(&["CityName"]), address.text_at(&["CountryCode"]).map(|code| code.to_uppercase())]
+                .into_iter()
+                .flatten()
+                .collect();
+            contact.location = (!parts.is_empty()).then(|| parts.join(", "));
+        }
+    }
+
+    let experience = profile
+        .map(|profile| profile.all("EmployerHistory"))
+        .unwrap_or_default()
+        .into_iter()
+        .flat_map(|employer| {
+            let company = employer.text_at(&["OrganizationName"]);
+            let positions: Vec<&XmlNode> = employer.children("PositionHistory").collect();
+            positions.into_iter().map(move |position| {
+                let period = position.child("EmploymentPeriod");
+                ExperienceEntry {
+                    title: position.text_at(&["PositionTitle"]),
+                    company: company.clone(),
+                    start_date: candidate_date(period.and_then(|period| period.child("StartDate"))),
+                    end_date: candidate_date(period.and_then(|period| period.child("EndDate"))),
+                    description: position.child("Description").and_then(|description| plain_text(&description.text)),
+                }
+            })
+        })
+        .collect();
+
+    let education = profile
+        .map(|profile| profile.all("EducationOrganizationAttendance"))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|attendance| {
+            let period = attendance.child("AttendancePeriod");
+            let degree = attendance.child("EducationDegree");
+            EducationEntry {
+                institution: attendance.text_at(&["OrganizationName"]),
+                degree: degree.and_then(|degree| degree.text_at(&["DegreeName"])),
+                field: degree
+                    .and_then(|degree| degree.descendant("DegreeMajor").or(degree.descendant("Major")))
+                    .and_then(XmlNode::deep_text),
+                start_date: candidate_date(period.and_then(|period| period.child("StartDate"))),
+                end_date: candidate_date(period.and_then(|period| period.child("EndDate"))),
+            }
+        })
+        .collect();
+
+    let skills = profile
+        .map(|profile| profile.all("PersonCompetency"))
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|competency| competency.text_at(&["CompetencyName"]))
+        .collect();
+
+    let summary = profile
+        .and_then(|profile| profile.descendant("ExecutiveSummary").or(profile.descendant("ProfileSummary")))
+        .and_then(|summary| plain_text(&summary.text));
+
+    finish(
+        ImportSource::Europass,
+        AnalysisResult {
+            contact,
+            summary,
+            skills,
+            experience,
+            education,
+            ..Default::default()
+        },
+    )
+}
+
+fn import_hr_xml_resume(root: &XmlNode) -> Result<ImportedResume, String> {
+    let resume = root
+        .child("StructuredXMLResume")
+        .ok_or_else(|| "Only structured HR-XML resumes can be imported".to_string())?;
+    let contact_info = resume.child("ContactInfo");
+
+    let name = contact_info.and_then(|info| info.child("PersonName")).and_then(|name| {
+        name.text_at(&["FormattedName"]).or_else(|| {
+            clean(
+                &[name.text_at(&["GivenName"]), name.text_at(&["FamilyName"])]
+                    .into_iter()
+                    .flatten()
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            )
+        })
+    });
+
+    let methods: Vec<&XmlNode> = contact_info.map(|info| info.all("ContactMethod")).unwrap_or_default();
+    let contact = ContactInfo {
+        name,
+        email: methods.iter().find_map(|method| method.text_at(&["InternetEmailAddress"])),
+        phone: methods.iter().find_map(|method| {
+            ["Telephone", "Mobile"]
+                .iter()
+                .find_map(|kind| method.at(&[kind, "FormattedNumber"]).and_then(XmlNode::text))
+        }),
+        location: methods.iter().find_map(|method| {
+            let address = method.child("PostalAddress")?;
+            let parts: Vec<String> = [address.text_at(&["Municipality"]), address.text_at(&["CountryCode"]).map(|code| code.to_uppercase())]
+                .into_iter()
+                .flatten()
+                .collect();
+            (!parts.is_empty()).then(|| parts.join(", "))
+        }),
+        links: methods.iter().filter_map(|method| method.text_at(&["InternetWebAddress"])).collect(),
+    };
+
+    let experience = resume
+        .all("EmployerOrg")
+        .into_iter()
+        .flat_map(|employer| {
+            let company = employer.text_at(&["EmployerOrgName"]);
+            let positions: Vec<&XmlNode> = employer.children("PositionHistory").collect();
+            positions.into_iter().map(move |position| ExperienceEntry {
+                title: position.text_at(&["Title"]),
+                company: position.text_at(&["OrgName", "OrganizationName"]).or_else(|| company.clone()),
+                start_date: candidate_date(position.child("StartDate")),
+                end_date: candidate_date(position.child("EndDate")),
+                description: position.child("Description").and_then(|description| plain_text(&description.text)),
+            })
+        })
+        .collect();
+
+    let education = resume
+        .all("SchoolOrInstitution")
+        .into_iter()
+        .flat_map(|school| {
+            let institution = school.text_at(&["School", "SchoolName"]).or_else(|| school.text_at(&["SchoolName"]));
+            let degrees: Vec<&XmlNode> = school.children("Degree").collect();
+            if degrees.is_empty() {
+                return vec![EducationEntry { institution, ..Default::default() }];
+            }
+            degrees
+                .into_iter()
+                .map(|degree| {
+                    let dates = degree.child("DatesOfAttendance");
+                    EducationEntry {
+                        institution: institution.clone(),
+                        degree: degree.text_at(&["DegreeName"]),
+                        field: degree.text_at(&["DegreeMajor", "Name"]),
+                        start_date: candidate_date(dates.and_then(|dates| dates.child("StartDate"))),
+                        end_date: candidate_date(dates.and_then(|dates| dates.child("EndDate"))),
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    let skills = resume
+        .all("Competency")
+        .into_iter()
+        .filter_map(|competency| competency.attribute("name").and_then(clean))
+        .collect();
+
+    finish(
+        ImportSource::HrXml,
+        AnalysisResult {
+            contact,
+            summary: resume.child("ExecutiveSummary").and_then(|summary| plain_text(&summary.text)),
+            skills,
+            experience,
+            education,
+            ..Default::default()
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use zip::{write::SimpleFileOptions, ZipWriter};
+
+    use super::*;
+
+    fn zip(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, data) in entries {
+            writer.start_file(*name, SimpleFileOptions::default()).unwrap();
+            writer.write_all(data).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn splits_quoted_csv_fields() {
+        let csv = "\u{feff}a,\"b, c\",\"say \"\"hi\"\"\"\r\n\"multi\r\nline\",,x";
+
+        assert_eq!(
+            parse_csv(csv),
+            vec![strings(&["a", "b, c", "say \"hi\""]), strings(&["multi\r\nline", "", "x"])]
+        );
+        assert_eq!(parse_csv("a,\n\nb"), vec![strings(&["a", ""]), strings(&[""]), strings(&["b"])]);
+        assert!(parse_csv("").is_empty());
+    }
+
+    #[test]
+    fn finds_the_header_after_leading_notes() {
+        let csv = "Notes:\n\"Exported by LinkedIn, see the help center\"\n\nFirst Name,Last Name \nJane,Doe\n,\n";
+        let rows = csv_rows(csv, "First Name");
+
+        assert_eq!(rows.len(), 1);
+        assert_eq!(column(&rows[0], "Last Name").as_deref(), Some("Doe"));
+        assert!(csv_rows(csv, "Company Name").is_empty());
+    }
+
+    #[test]
+    fn imports_linkedin_exports() {
+        let profile = "First Name,Last Name,Headline,Summary,Geo Location,Websites\n\
+            Jane,Doe,Engineer,\"Builds things, mostly \"\"APIs\"\"\nin Rust\",\"Berlin, Germany\",\
+            \"[PERSONAL:https://janedoe.dev,COMPANY:https://acme.com]\"\n";
+        let emails = "Email Address,Confirmed,Primary\nold@example.com,Yes,No\njane@example.com,Yes,Yes\n";
+        let positions = "Company Name,Title,Description,Started On,Finished On\r\nAcme,Engineer,\"Billing, invoicing\",Mar 2019,\r\n";
+        let archive = zip(&[
+            ("Basic_LinkedInDataExport/Profile.csv", profile.as_bytes()),
+            ("Basic_LinkedInDataExport/Email Addresses.csv", emails.as_bytes()),
+            ("Basic_LinkedInDataExport/PhoneNumbers.csv", b"Extension,Number,Type\n,+49 30 1234,Mobile\n"),
+            ("Basic_LinkedInDataExport/Positions.csv", positions.as_bytes()),
+            ("Basic_LinkedInDataExport/Skills.csv", b"Name\nRust\nrust\nPostgreSQL\n"),
+        ]);
+
+        let imported = import_linkedin(&archive).unwrap();
+        let analysis = &imported.analysis;
+        assert_eq!(imported.source, ImportSource::LinkedIn);
+        assert_eq!(analysis.contact.name.as_deref(), Some("Jane Doe"));
+        assert_eq!(analysis.contact.email.as_deref(), Some("jane@example.com"));
+        assert_eq!(analysis.contact.phone.as_deref(), Some("+49 30 1234"));
+        assert_eq!(analysis.contact.location.as_deref(), Some("Berlin, Germany"));
+        assert_eq!(analysis.contact.links, strings(&["https://janedoe.dev", "https://acme.com"]));
+        assert_eq!(analysis.summary.as_deref(), Some("Builds things, mostly \"APIs\" in Rust"));
+        assert_eq!(analysis.experience[0].company.as_deref(), Some("Acme"));
+        assert_eq!(analysis.experience[0].description.as_deref(), Some("Billing, invoicing"));
+        assert_eq!(analysis.experience[0].start_date.as_deref(), Some("Mar 2019"));
+        assert_eq!(analysis.experience[0].end_date, None);
+        assert_eq!(analysis.skills, strings(&["Rust", "PostgreSQL"]));
+        assert!(imported.to_text().contains("Engineer, Acme"));
+    }
+
+    #[test]
+    fn rejects_archives_that_are_not_linkedin_exports() {
+        assert_eq!(
+            import_linkedin(&zip(&[("notes.csv", b"Name\nRust\n")])).unwrap_err(),
+            "The archive is not a LinkedIn data export"
+        );
+        assert_eq!(
+            import_linkedin(&zip(&[("Profile.csv", b"First Name,Last Name\n")])).unwrap_err(),
+            "The document has no resume content"
+        );
+        assert!(import_linkedin(b"not a zip").unwrap_err().starts_with("Could not read archive"));
+    }
+
+    #[test]
+    fn refuses_oversized_csv_files() {
+        let profile = vec![b'a'; MAX_CSV_BYTES as usize + 1];
+        let archive = zip(&[("Profile.csv", &profile)]);
+
+        assert_eq!(import_linkedin(&archive).unwrap_err(), "profile.csv is larger than 10485760 bytes");
+    }
+
+    #[test]
+    fn imports_europass_v3_cvs() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <SkillsPassport xmlns="http://europass.cedefop.europa.eu/Europass" locale="en">
+              <LearnerInfo>
+                <Identification>
+                  <PersonName><FirstName>Jane</FirstName><Surname>Doe</Surname></PersonName>
+                  <ContactInfo>
+                    <Address><Contact><Municipality>Berlin</Municipality><Country><Code>DE</Code><Label>Germany</Label></Country></Contact></Address>
+                    <Email><Contact>jane@example.com</Contact></Email>
+                    <TelephoneList><Telephone><Contact>+49 30 1234</Contact></Telephone></TelephoneList>
+                    <WebsiteList><Website><Contact>https://janedoe.dev</Contact></Website></WebsiteList>
+                  </ContactInfo>
+                </Identification>
+                <Headline><Description><Label>Backend engineer</Label></Description></Headline>
+                <WorkExperienceList>
+                  <WorkExperience>
+                    <Period><From year="2019" month="--03"/><To year="2020"/><Current>true</Current></Period>
+                    <Position><Label>Engineer</Label></Position>
+                    <Activities>&lt;p&gt;Billing &amp;amp; invoicing&lt;/p&gt;&lt;p&gt;APIs&lt;/p&gt;</Activities>
+                    <Employer><Name>Acme</Name></Employer>
+                  </WorkExperience>
+                </WorkExperienceList>
+                <EducationList>
+                  <Education>
+                    <Period><From year="2012"/><To year="2015" month="--09"/></Period>
+                    <Title>BSc</Title>
+                    <Organisation><Name>TU Berlin</Name></Organisation>
+                    <Field><Label>Computer Science</Label></Field>
+                  </Education>
+                </EducationList>
+                <Skills><Computer><Description>Rust, PostgreSQL; Docker</Description></Computer></Skills>
+              </LearnerInfo>
+            </SkillsPassport>"#;
+
+        let imported = import_xml(xml.as_bytes()).unwrap();
+        let analysis = &imported.analysis;
+        assert_eq!(imported.source, ImportSource::Europass);
+        assert_eq!(analysis.contact.name.as_deref(), Some("Jane Doe"));
+        assert_eq!(analysis.contact.email.as_deref(), Some("jane@example.com"));
+        assert_eq!(analysis.contact.phone.as_deref(), Some("+49 30 1234"));
+        assert_eq!(analysis.contact.location.as_deref(), Some("Berlin, Germany"));
+        assert_eq!(analysis.contact.links, strings(&["https://janedoe.dev"]));
+        assert_eq!(analysis.summary.as_deref(), Some("Backend engineer"));
+        assert_eq!(analysis.experience[0].start_date.as_deref(), Some("2019-03"));
+        assert_eq!(analysis.experience[0].end_date, None);
+        assert_eq!(analysis.experience[0].description.as_deref(), Some("Billing & invoicing\nAPIs"));
+        assert_eq!(analysis.education[0].field.as_deref(), Some("Computer Science"));
+        assert_eq!(analysis.education[0].end_date.as_deref(), Some("2015-09"));
+        assert_eq!(analysis.skills, strings(&["Rust", "PostgreSQL", "Docker"]));
+    }
+
+    #[test]
+    fn imports_europass_v4_candidates() {
+        let xml = r#"<Candidate xmlns="http://www.europass.eu/1.0" xmlns:oa="http://www.openapplications.org/oagis/9" xmlns:hr="http://www.hr-xml.org/3">
+              <CandidatePerson>
+                <PersonName><oa:GivenName>Jane</oa:GivenName><hr:FamilyName>Doe</hr:FamilyName></PersonName>
+                <Communication><ChannelCode>Email</ChannelCode><oa:URI>jane@example.com</oa:URI></Communication>
+                <Communication><ChannelCode>Telephone</ChannelCode><CountryDialing>49</CountryDialing><oa:DialNumber>30 1234</oa:DialNumber></Communication>
+                <Communication><Address><oa:CityName>Berlin</oa:CityName><CountryCode>de</CountryCode></Address></Communication>
+              </CandidatePerson>
+              <CandidateProfile>
+                <ExecutiveSummary>Backend engineer</ExecutiveSummary>
+                <EmploymentHistory>
+                  <EmployerHistory>
+                    <hr:OrganizationName>Acme</hr:OrganizationName>
+                    <PositionHistory>
+                      <PositionTitle>Engineer</PositionTitle>
+                      <eures:EmploymentPeriod>
+                        <eures:StartDate><hr:FormattedDateTime>2019-03-01</hr:FormattedDateTime></eures:StartDate>
+                        <eures:EndDate><hr:FormattedDateTime>Present</hr:FormattedDateTime></eures:EndDate>
+                      </eures:EmploymentPeriod>
+                    </PositionHistory>
+                  </EmployerHistory>
+                </EmploymentHistory>
+                <EducationHistory>
+                  <EducationOrganizationAttendance>
+                    <hr:OrganizationName>TU Berlin</hr:OrganizationName>
+                    <EducationDegree><hr:DegreeName>BSc</hr:DegreeName><DegreeMajor><Name>Computer Science</Name></DegreeMajor></EducationDegree>
+                  </EducationOrganizationAttendance>
+                </EducationHistory>
+                <PersonQualifications><PersonCompetency><CompetencyName>Rust</CompetencyName></PersonCompetency></PersonQualifications>
+              </CandidateProfile>
+            </Candidate>"#;
+
+        let analysis = import_xml(xml.as_bytes()).unwrap().analysis;
+        assert_eq!(analysis.contact.name.as_deref(), Some("Jane Doe"));
+        assert_eq!(analysis.contact.email.as_deref(), Some("jane@example.com"));
+        assert_eq!(analysis.contact.phone.as_deref(), Some("+49 30 1234"));
+        assert_eq!(analysis.contact.location.as_deref(), Some("Berlin, DE"));
+        assert_eq!(analysis.summary.as_deref(), Some("Backend engineer"));
+        assert_eq!(analysis.experience[0].company.as_deref(), Some("Acme"));
+        assert_eq!(analysis.experience[0].start_date.as_deref(), Some("2019-03-01"));
+        assert_eq!(analysis.experience[0].end_date, None);
+        assert_eq!(analysis.education[0].degree.as_deref(), Some("BSc"));
+        assert_eq!(analysis.education[0].field.as_deref(), Some("Computer Science"));
+        assert_eq!(analysis.skills, strings(&["Rust"]));
+    }
+
+    #[test]
+    fn imports_hr_xml_resumes() {
+        let xml = r#"<Resume xmlns="http://ns.hr-xml.org/2006-02-28">
+              <StructuredXMLResume>
+                <ContactInfo>
+                  <PersonName><FormattedName>Jane Doe</FormattedName></PersonName>
+                  <ContactMethod><InternetEmailAddress>jane@example.com</InternetEmailAddress></ContactMethod>
+                  <ContactMethod><Mobile><FormattedNumber>+49 30 1234</FormattedNumber></Mobile></ContactMethod>
+                  <ContactMethod>
+                    <InternetWebAddress>https://janedoe.dev</InternetWebAddress>
+                    <PostalAddress><CountryCode>de</CountryCode><Municipality>Berlin</Municipality></PostalAddress>
+                  </ContactMethod>
+                </ContactInfo>
+                <ExecutiveSummary>Backend engineer</ExecutiveSummary>
+                <EmploymentHistory>
+                  <EmployerOrg>
+                    <EmployerOrgName>Acme</EmployerOrgName>
+                    <PositionHistory>
+                      <Title>Engineer</Title>
+                      <StartDate><AnyDate>2019-03</AnyDate></StartDate>
+                      <EndDate><AnyDate>notKnown</AnyDate></EndDate>
+                    </PositionHistory>
+                    <PositionHistory><Title>Intern</Title><OrgName><OrganizationName>Acme Labs</OrganizationName></OrgName></PositionHistory>
+                  </EmployerOrg>
+                </EmploymentHistory>
+                <EducationHistory>
+                  <SchoolOrInstitution>
+                    <School><SchoolName>TU Berlin</SchoolName></School>
+                    <Degree>
+                      <DegreeName>BSc</DegreeName>
+                      <DegreeMajor><Name>Computer Science</Name></DegreeMajor>
+                      <DatesOfAttendance><StartDate><Year>2012</Year></StartDate></DatesOfAttendance>
+                    </Degree>
+                  </SchoolOrInstitution>
+                  <SchoolOrInstitution><SchoolName>Gymnasium</SchoolName></SchoolOrInstitution>
+                </EducationHistory>
+                <Qualifications><Competency name="Rust"/><Competency name=" "/></Qualifications>
+              </StructuredXMLResume>
+            </Resume>"#;
+
+        let imported = import_xml(xml.as_bytes()).unwrap();
+        let analysis = &imported.analysis;
+        assert_eq!(imported.source, ImportSource::HrXml);
+        assert_eq!(analysis.contact.name.as_deref(), Some("Jane Doe"));
+        assert_eq!(analysis.contact.phone.as_deref(), Some("+49 30 1234"));
+        assert_eq!(analysis.contact.location.as_deref(), Some("Berlin, DE"));
+        assert_eq!(analysis.contact.links, strings(&["https://janedoe.dev"]));
+        assert_eq!(analysis.experience.len(), 2);
+        assert_eq!(analysis.experience[0].start_date.as_deref(), Some("2019-03"));
+        assert_eq!(analysis.experience[0].end_date, None);
+        assert_eq!(analysis.experience[1].company.as_deref(), Some("Acme Labs"));
+        assert_eq!(analysis.education[0].start_date.as_deref(), Some("2012"));
+        assert_eq!(analysis.education[1].institution.as_deref(), Some("Gymnasium"));
+        assert_eq!(analysis.skills, strings(&["Rust"]));
+    }
+
+    #[test]
+    fn rejects_unsupported_xml() {
+        let error = |xml: &str| import_xml(xml.as_bytes()).unwrap_err();
+
+        assert!(error("<html><body/></html>").starts_with("Unsupported XML document <html>"));
+        assert_eq!(error("<Resume><NonXMLResume/></Resume>"), "Only structured HR-XML resumes can be imported");
+        assert_eq!(error("<Resume><StructuredXMLResume>"), "Malformed XML");
+        assert!(error("<Resume></Candidate>").starts_with("Malformed XML"));
+        assert_eq!(error(&"<a>".repeat(MAX_XML_DEPTH + 2)), "The XML document is nested too deeply");
+    }
+
+    #[test]
+    fn recognizes_resume_xml_by_its_root() {
+        assert!(is_resume_xml(b"<?xml version=\"1.0\"?>\n<!-- CV -->\n<eu:SkillsPassport/>"));
+        assert!(is_resume_xml(b"<Candidate xmlns=\"http://www.europass.eu/1.0\"><CandidatePerson/>"));
+        assert!(!is_resume_xml(b"<html><Resume/></html>"));
+        assert!(!is_resume_xml(b"Resume"));
+        assert!(!is_resume_xml(b""));
+    }
+}
@@ -0,0 +1,744 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use validator::{Validate, ValidationError};
+
+use crate::{models::analysis_result::{AnalysisResult, ContactInfo, EducationEntry, ExperienceEntry}, services::matching::MONTHS};
+
+/// Version of the JSON Resume schema this module reads and writes.
+pub const JSON_RESUME_VERSION: &str = "v1.0.0";
+
+const JSON_RESUME_SCHEMA: &str = "https://raw.githubusercontent.com/jsonresume/resume-schema/v1.0.0/schema.json";
+
+/// The schema's `iso8601` definition: a year, a year and month, or a full date.
+static ISO_DATE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^([1-2][0-9]{3}-[0-1][0-9]-[0-3][0-9]|[1-2][0-9]{3}-[0-1][0-9]|[1-2][0-9]{3})$").unwrap()
+});
+
+// Exporters commonly write "" for fields they have no value for, those count as missing
+
+fn validate_email(email: &str) -> Result<(), ValidationError> {
+    if email.is_empty() || validator::validate_email(email) {
+        Ok(())
+    } else {
+        Err(ValidationError::new("email"))
+    }
+}
+
+fn validate_url(url: &str) -> Result<(), ValidationError> {
+    if url.is_empty() || validator::validate_url(url) {
+        Ok(())
+    } else {
+        Err(ValidationError::new("url"))
+    }
+}
+
+fn validate_date(date: &str) -> Result<(), ValidationError> {
+    if date.is_empty() || ISO_DATE.is_match(date) {
+        Ok(())
+    } else {
+        let mut error = ValidationError::new("iso8601");
+        error.message = Some("Dates must be YYYY, YYYY-MM or YYYY-MM-DD".into());
+        Err(error)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, Validate)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Location {
+    pub address: Option<String>,
+    pub postal_code: Option<String>,
+    pub city: Option<String>,
+    pub country_code: Option<String>,
+    pub region: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, Validate)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Profile {
+    pub network: Option<String>,
+    pub username: Option<String>,
+    #[validate(custom = "validate_url")]
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, Validate)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Basics {
+    pub name: Option<String>,
+    pub label: Option<String>,
+    #[validate(custom = "validate_url")]
+    pub image: Option<String>,
+    #[validate(custom = "validate_email")]
+    pub email: Option<String>,
+    pub phone: Option<String>,
+    #[serde(alias = "website")]
+    #[validate(custom = "validate_url")]
+    pub url: Option<String>,
+    pub summary: Option<String>,
+    pub location: Option<Location>,
+    #[validate]
+    pub profiles: Vec<Profile>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, Validate)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Work {
+    #[serde(alias = "company")]
+    pub name: Option<String>,
+    pub location: Option<String>,
+    pub description: Option<String>,
+    pub position: Option<String>,
+    #[serde(alias = "website")]
+    #[validate(custom = "validate_url")]
+    pub url: Option<String>,
+    #[validate(custom = "validate_date")]
+    pub start_date: Option<String>,
+    #[validate(custom = "validate_date")]
+    pub end_date: Option<String>,
+    pub summary: Option<String>,
+    pub highlights: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, Validate)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Volunteer {
+    pub organization: Option<String>,
+    pub position: Option<String>,
+    #[serde(alias = "website")]
+    #[validate(custom = "validate_url")]
+    pub url: Option<String>,
+    #[validate(custom = "validate_date")]
+    pub start_date: Option<String>,
+    #[validate(custom = "validate_date")]
+    pub end_date: Option<String>,
+    pub summary: Option<String>,
+    pub highlights: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, Validate)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Education {
+    pub institution: Option<String>,
+    #[validate(custom = "validate_url")]
+    pub url: Option<String>,
+    pub area: Option<String>,
+    pub study_type: Option<String>,
+    #[validate(custom = "validate_date")]
+    pub start_date: Option<String>,
+    #[validate(custom = "validate_date")]
+    pub end_date: Option<String>,
+    #[serde(alias = "gpa")]
+    pub score: Option<String>,
+    pub courses: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, Validate)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Award {
+    pub title: Option<String>,
+    #[validate(custom = "validate_date")]
+    pub date: Option<String>,
+    pub awarder: Option<String>,
+    pub summary: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, Validate)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Certificate {
+    pub name: Option<String>,
+    #[validate(custom = "validate_date")]
+    pub date: Option<String>,
+    #[validate(custom = "validate_url")]
+    pub url: Option<String>,
+    pub issuer: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, Validate)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Publication {
+    pub name: Option<String>,
+    pub publisher: Option<String>,
+    #[validate(custom = "validate_date")]
+    pub release_date: Option<String>,
+    #[serde(alias = "website")]
+    #[validate(custom = "validate_url")]
+    pub url: Option<String>,
+    pub summary: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, Validate)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Skill {
+    pub name: Option<String>,
+    pub level: Option<String>,
+    pub keywords: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, Validate)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Language {
+    pub language: Option<String>,
+    pub fluency: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, Validate)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Interest {
+    pub name: Option<String>,
+    pub keywords: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, Validate)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Project {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub highlights: Vec<String>,
+    pub keywords: Vec<String>,
+    #[validate(custom = "validate_date")]
+    pub start_date: Option<String>,
+    #[validate(custom = "validate_date")]
+    pub end_date: Option<String>,
+    #[validate(custom = "validate_url")]
+    pub url: Option<String>,
+    pub roles: Vec<String>,
+    pub entity: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Meta {
+    pub canonical: Option<String>,
+    pub version: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+/// A resume in the JSON Resume format (https://jsonresume.org/schema). References are accepted
+/// but ignored, they are about other people.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, Validate)]
+#[serde(default, rename_all = "camelCase")]
+pub struct JsonResume {
+    #[serde(rename = "$schema", skip_deserializing)]
+    pub schema: Option<String>,
+    #[validate]
+    pub basics: Basics,
+    #[validate]
+    pub work: Vec<Work>,
+    #[validate]
+    pub volunteer: Vec<Volunteer>,
+    #[validate]
+    pub education: Vec<Education>,
+    #[validate]
+    pub awards: Vec<Award>,
+    #[validate]
+    pub certificates: Vec<Certificate>,
+    #[validate]
+    pub publications: Vec<Publication>,
+    #[validate]
+    pub skills: Vec<Skill>,
+    #[validate]
+    pub languages: Vec<Language>,
+    #[validate]
+    pub interests: Vec<Interest>,
+    #[validate]
+    pub projects: Vec<Project>,
+    pub meta: Option<Meta>,
+}
+
+/// Non-blank, trimmed text of an optional field.
+fn present(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|value| !value.is_empty())
+}
+
+fn join_present(values: &[&Option<String>], separator: &str) -> Option<String> {
+    let parts: Vec<&str> = values.iter().filter_map(|value| present(value)).collect();
+    (!parts.is_empty()).then(|| parts.join(separator))
+}
+
+fn period(start: &Option<String>, end: &Option<String>) -> Option<String> {
+    match (present(start), present(end)) {
+        (Some(start), Some(end)) => Some(format!("{} - {}", start, end)),
+        (Some(start), None) => Some(format!("{} - Present", start)),
+        (None, Some(end)) => Some(end.to_string()),
+        (None, None) => None,
+    }
+}
+
+/// Builds a resume as plain text, section by section under conventional headings.
+struct TextResume {
+    lines: Vec<String>,
+}
+
+impl TextResume {
+    fn line(&mut self, line: impl Into<String>) {
+        self.lines.push(line.into());
+    }
+
+    fn optional(&mut self, line: Option<impl Into<String>>) {
+        if let Some(line) = line {
+            self.line(line);
+        }
+    }
+
+    fn section(&mut self, heading: &str) {
+        self.lines.push(String::new());
+        self.lines.push(heading.to_string());
+    }
+
+    fn bullets(&mut self, items: &[String]) {
+        for item in items.iter().map(|item| item.trim()).filter(|item| !item.is_empty()) {
+            self.lines.push(format!("- {}", item));
+        }
+    }
+}
+
+impl JsonResume {
+    /// Parses and validates a JSON Resume document.
+    pub fn parse(value: Value) -> Result<Self, String> {
+        if !value.is_object() {
+            return Err("A JSON Resume must be a JSON object".to_string());
+        }
+
+        let resume: JsonResume = serde_json::from_value(value).map_err(|e| format!("Invalid JSON Resume: {}", e))?;
+        resume.validate().map_err(|e| format!("Invalid JSON Resume: {}", e))?;
+
+        if resume.to_text().trim().is_empty() {
+            return Err("The JSON Resume has no content".to_string());
+        }
+
+        Ok(resume)
+    }
+
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, String> {
+        let value = serde_json::from_slice(bytes).map_err(|e| format!("Invalid JSON: {}", e))?;
+        Self::parse(value)
+    }
+
+    /// Name to store the document under when it is imported.
+    pub fn file_name(&self) -> String {
+        let name: String = present(&self.basics.name)
+            .unwrap_or("resume")
+            .chars()
+            .map(|c| if c.is_alphanumeric() || c == '-' { c } else { '_' })
+            .collect();
+        format!("{}.json", name)
+    }
+
+    /// Renders the resume the way it would read on paper, which is what the analysis works on.
+    pub fn to_text(&self) -> String {
+        let mut text = TextResume { lines: Vec::new() };
+        let basics = &self.basics;
+
+        text.optional(present(&basics.name));
+        text.optional(present(&basics.label));
+        let location = basics.location.as_ref().and_then(|location| {
+            join_present(
+                &[&location.address, &location.city, &location.region, &location.postal_code, &location.country_code],
+                ", ",
+            )
+        });
+        text.optional(join_present(&[&basics.email, &basics.phone, &basics.url, &location], " | "));
+        for profile in &basics.profiles {
+            let handle = present(&profile.url).or(present(&profile.username));
+            match (present(&profile.network), handle) {
+                (Some(network), Some(handle)) => text.line(format!("{}: {}", network, handle)),
+                (None, Some(handle)) => text.line(handle),
+                _ => {}
+            }
+        }
+
+        if let Some(summary) = present(&basics.summary) {
+            text.section("Summary");
+            text.line(summary);
+        }
+
+        if !self.work.is_empty() {
+            text.section("Work Experience");
+            for work in &self.work {
+                text.line("");
+                text.optional(join_present(&[&work.position, &work.name], ", "));
+                text.optional(join_present(&[&period(&work.start_date, &work.end_date), &work.location], " | "));
+                text.optional(present(&work.description));
+                text.optional(present(&work.summary));
+                text.bullets(&work.highlights);
+            }
+        }
+
+        if !self.education.is_empty() {
+            text.section("Education");
+            for education in &self.education {
+                text.line("");
+                let degree = match (present(&education.study_type), present(&education.area)) {
+                    (Some(study_type), Some(area)) => Some(format!("{} in {}", study_type, area)),
+                    (study_type, area) => study_type.or(area).map(str::to_string),
+                };
+                text.optional(join_present(&[&degree, &education.institution], ", "));
+                text.optional(period(&education.start_date, &education.end_date));
+                text.optional(present(&education.score).map(|score| format!("Score: {}", score)));
+                if !education.courses.is_empty() {
+                    text.line(format!("Courses: {}", education.courses.join(", ")));
+                }
+            }
+        }
+
+        let skills: Vec<String> = self
+            .skills
+            .iter()
+            .filter_map(|skill| {
+                let name = present(&skill.name)?;
+                let mut line = match present(&skill.level) {
+                    Some(level) => format!("{} ({})", name, level),
+                    None => name.to_string(),
+                };
+                if !skill.keywords.is_empty() {
+                    line = format!("{}: {}", line, skill.keywords.join(", "));
+                }
+                Some(line)
+            })
+            .collect();
+        if !skills.is_empty() {
+            text.section("Skills");
+            text.bullets(&skills);
+        }
+
+        if !self.projects.is_empty() {
+            text.section("Projects");
+            for project in &self.projects {
+                text.line("");
+                text.optional(join_present(&[&project.name, &project.entity], ", "));
+                text.optional(period(&project.start_date, &project.end_date));
+                text.optional(present(&project.description));
+                if !project.roles.is_empty() {
+                    text.line(format!("Roles: {}", project.roles.join(", ")));
+                }
+                text.bullets(&project.highlights);
+                if !project.keywords.is_empty() {
+                    text.line(format!("Technologies: {}", project.keywords.join(", ")));
+                }
+            }
+        }
+
+        if !self.volunteer.is_empty() {
+            text.section("Volunteer Experience");
+            for volunteer in &self.volunteer {
+                text.line("");
+                text.optional(join_present(&[&volunteer.position, &volunteer.organization], ", "));
+                text.optional(period(&volunteer.start_date, &volunteer.end_date));
+                text.optional(present(&volunteer.summary));
+                text.bullets(&volunteer.highlights);
+            }
+        }
+
+        let certificates: Vec<String> = self
+            .certificates
+            .iter()
+            .filter_map(|certificate| join_present(&[&certificate.name, &certificate.issuer, &certificate.date], ", "))
+            .collect();
+        if !certificates.is_empty() {
+            text.section("Certifications");
+            text.bullets(&certificates);
+        }
+
+        let awards: Vec<String> = self
+            .awards
+            .iter()
+            .filter_map(|award| join_present(&[&award.title, &award.awarder, &award.date, &award.summary], ", "))
+            .collect();
+        if !awards.is_empty() {
+            text.section("Awards");
+            text.bullets(&awards);
+        }
+
+        let publications: Vec<String> = self
+            .publications
+            .iter()
+            .filter_map(|publication| {
+                join_present(&[&publication.name, &publication.publisher, &publication.release_date], ", ")
+            })
+            .collect();
+        if !publications.is_empty() {
+            text.section("Publications");
+            text.bullets(&publications);
+        }
+
+        let languages: Vec<String> = self
+            .languages
+            .iter()
+            .filter_map(|language| {
+                let name = present(&language.language)?;
+                Some(match present(&language.fluency) {
+                    Some(fluency) => format!("{} ({})", name, fluency),
+                    None => name.to_string(),
+                })
+            })
+            .collect();
+        if !languages.is_empty() {
+            text.section("Languages");
+            text.bullets(&languages);
+        }
+
+        let interests: Vec<String> = self.interests.iter().filter_map(|interest| present(&interest.name).map(str::to_string)).collect();
+        if !interests.is_empty() {
+            text.section("Interests");
+            text.line(interests.join(", "));
+        }
+
+        text.lines.join("\n").trim().to_string()
+    }
+
+    /// Maps an analysis onto JSON Resume. Dates the schema can't represent are left out.
+    pub fn from_analysis(analysis: &AnalysisResult) -> Self {
+        let contact: &ContactInfo = &analysis.contact;
+        let (profiles, urls): (Vec<&String>, Vec<&String>) = contact.links.iter().partition(|link| profile_network(link).is_some());
+
+        JsonResume {
+            schema: Some(JSON_RESUME_SCHEMA.to_string()),
+            basics: Basics {
+                name: contact.name.clone(),
+                email: contact.email.clone(),
+                phone: contact.phone.clone(),
+                url: urls.first().map(|url| url.to_string()),
+                summary: analysis.summary.clone(),
+                location: contact.location.clone().map(|address| Location {
+                    address: Some(address),
+                    ..Default::default()
+                }),
+                profiles: profiles
+                    .into_iter()
+                    .map(|url| Profile {
+                        network: profile_network(url).map(str::to_string),
+                        username: None,
+                        url: Some(url.to_string()),
+                    })
+                    .collect(),
+                ..Default::default()
+            },
+            work: analysis.experience.iter().map(work_from_experience).collect(),
+            education: analysis.education.iter().map(education_from_entry).collect(),
+            skills: analysis
+                .skills
+                .iter()
+                .map(|skill| Skill {
+                    name: Some(skill.clone()),
+                    ..Default::default()
+                })
+                .collect(),
+            meta: Some(Meta {
+                version: Some(JSON_RESUME_VERSION.to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    /// The resume as JSON without the empty fields and sections serialization leaves behind.
+    pub fn to_value(&self) -> Value {
+        prune(serde_json::to_value(self).unwrap_or(Value::Null)).unwrap_or(Value::Null)
+    }
+}
+
+fn work_from_experience(entry: &ExperienceEntry) -> Work {
+    Work {
+        name: entry.company.clone(),
+        position: entry.title.clone(),
+        start_date: entry.start_date.as_deref().and_then(iso_date),
+        end_date: entry.end_date.as_deref().and_then(iso_date),
+        summary: entry.description.clone(),
+        ..Default::default()
+    }
+}
+
+fn education_from_entry(entry: &EducationEntry) -> Education {
+    Education {
+        institution: entry.institution.clone(),
+        area: entry.field.clone(),
+        study_type: entry.degree.clone(),
+        start_date: entry.start_date.as_deref().and_then(iso_date),
+        end_date: entry.end_date.as_deref().and_then(iso_date),
+        ..Default::default()
+    }
+}
+
+fn profile_network(url: &str) -> Option<&'static str> {
+    let url = url.to_lowercase();
+    [
+        ("linkedin.com", "LinkedIn"),
+        ("github.com", "GitHub"),
+        ("gitlab.com", "GitLab"),
+        ("stackoverflow.com", "Stack Overflow"),
+        ("twitter.com", "Twitter"),
+        ("x.com/", "X"),
+        ("behance.net", "Behance"),
+        ("dribbble.com", "Dribbble"),
+    ]
+    .into_iter()
+    .find(|(host, _)| url.contains(host))
+    .map(|(_, network)| network)
+}
+
+/// Rewrites dates like "2019", "03/2019", "March 2019" or "2019-03-15" in the schema's ISO 8601
+/// form, keeping only as much precision as the input has. "Present" and unreadable dates give `None`.
+pub fn iso_date(date: &str) -> Option<String> {
+    let date = date.trim().to_lowercase();
+    if ISO_DATE.is_match(&date) {
+        return Some(date);
+    }
+
+    let parts: Vec<&str> = date
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect();
+
+    let year = parts
+        .iter()
+        .filter(|part| part.len() == 4)
+        .filter_map(|part| part.parse::<i32>().ok())
+        .find(|year| (1000..=2999).contains(year))?;
+
+    let month = parts.iter().find_map(|part| {
+        MONTHS
+            .iter()
+            .position(|month| part.starts_with(month))
+            .map(|index| index as u32 + 1)
+            .or_else(|| part.parse::<u32>().ok().filter(|m| part.len() <= 2 && (1..=12).contains(m)))
+    });
+
+    Some(match month {
+        Some(month) => format!("{}-{:02}", year, month),
+        None => year.to_string(),
+    })
+}
+
+fn prune(value: Value) -> Option<Value> {
+    match value {
+        Value::Null => None,
+        Value::String(s) if s.trim().is_empty() => None,
+        Value::Array(items) => {
+            let items: Vec<Value> = items.into_iter().filter_map(prune).collect();
+            (!items.is_empty()).then_some(Value::Array(items))
+        }
+        Value::Object(map) => {
+            let map: serde_json::Map<String, Value> = map
+                .into_iter()
+                .filter_map(|(key, value)| prune(value).map(|value| (key, value)))
+                .collect();
+            (!map.is_empty()).then_some(Value::Object(map))
+        }
+        value => Some(value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn analysis() -> AnalysisResult {
+        AnalysisResult {
+            contact: ContactInfo {
+                name: Some("Jane Doe".to_string()),
+                email: Some("jane@example.com".to_string()),
+                phone: Some("+1 555 0100".to_string()),
+                location: Some("Berlin".to_string()),
+                links: vec!["https://www.linkedin.com/in/janedoe".to_string(), "https://janedoe.dev".to_string()],
+            },
+            summary: Some("Backend engineer.".to_string()),
+            skills: vec!["Rust".to_string(), "PostgreSQL".to_string()],
+            experience: vec![ExperienceEntry {
+                title: Some("Engineer".to_string()),
+                company: Some("Acme".to_string()),
+                start_date: Some("March 2019".to_string()),
+                end_date: Some("Present".to_string()),
+                description: Some("Built the billing service.".to_string()),
+            }],
+            education: vec![EducationEntry {
+                institution: Some("TU Berlin".to_string()),
+                degree: Some("BSc".to_string()),
+                field: Some("Computer Science".to_string()),
+                start_date: Some("2012".to_string()),
+                end_date: Some("09/2015".to_string()),
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn exports_analyses_and_reads_them_back() {
+        let value = JsonResume::from_analysis(&analysis()).to_value();
+
+        assert_eq!(value["$schema"], JSON_RESUME_SCHEMA);
+        assert_eq!(value["basics"]["url"], "https://janedoe.dev");
+        assert_eq!(value["basics"]["profiles"][0]["network"], "LinkedIn");
+        assert_eq!(value["work"][0]["startDate"], "2019-03");
+        assert!(value["work"][0].get("endDate").is_none());
+        assert_eq!(value["education"][0]["endDate"], "2015-09");
+        assert_eq!(value["skills"][1]["name"], "PostgreSQL");
+        assert!(value.get("volunteer").is_none());
+
+        let resume = JsonResume::parse(value).unwrap();
+        assert_eq!(resume.basics.name.as_deref(), Some("Jane Doe"));
+        assert_eq!(resume.work[0].start_date.as_deref(), Some("2019-03"));
+        assert_eq!(resume.file_name(), "Jane_Doe.json");
+    }
+
+    #[test]
+    fn renders_resumes_as_text() {
+        let text = JsonResume::from_analysis(&analysis()).to_text();
+
+        assert!(text.starts_with("Jane Doe\njane@example.com | +1 555 0100 | https://janedoe.dev | Berlin\n"));
+        assert!(text.contains("LinkedIn: https://www.linkedin.com/in/janedoe"));
+        assert!(text.contains("Work Experience\n\nEngineer, Acme\n2019-03 - Present\nBuilt the billing service."));
+        assert!(text.contains("BSc in Computer Science, TU Berlin\n2012 - 2015-09"));
+        assert!(text.contains("Skills\n- Rust\n- PostgreSQL"));
+    }
+
+    #[test]
+    fn accepts_blank_fields_and_aliases() {
+        let resume = JsonResume::parse(json!({
+            "basics": { "name": "Jane Doe", "email": "", "website": "https://janedoe.dev" },
+            "work": [{ "company": "Acme", "startDate": "", "endDate": "2020-01-31" }],
+        }))
+        .unwrap();
+
+        assert_eq!(resume.basics.url.as_deref(), Some("https://janedoe.dev"));
+        assert_eq!(resume.work[0].name.as_deref(), Some("Acme"));
+    }
+
+    #[test]
+    fn rejects_invalid_documents() {
+        let error = |value: Value| JsonResume::parse(value).unwrap_err();
+
+        assert_eq!(error(json!([])), "A JSON Resume must be a JSON object");
+        assert_eq!(error(json!({ "basics": { "name": " " } })), "The JSON Resume has no content");
+        assert!(error(json!({ "basics": { "name": "Jane", "email": "jane" } })).contains("email"));
+        assert!(error(json!({ "basics": { "name": "Jane", "profiles": [{ "url": "janedoe" }] } })).contains("url"));
+        assert!(error(json!({ "work": [{ "name": "Acme", "startDate": "March 2019" }] })).contains("YYYY-MM"));
+        assert!(error(json!({ "basics": { "name": 42 } })).starts_with("Invalid JSON Resume"));
+        assert!(JsonResume::from_slice(b"{").unwrap_err().starts_with("Invalid JSON"));
+    }
+
+    #[test]
+    fn converts_dates_to_iso_8601() {
+        assert_eq!(iso_date("2019").as_deref(), Some("2019"));
+        assert_eq!(iso_date("2019-03-15").as_deref(), Some("2019-03-15"));
+        assert_eq!(iso_date("03/2019").as_deref(), Some("2019-03"));
+        assert_eq!(iso_date("15.03.2019").as_deref(), Some("2019-03"));
+        assert_eq!(iso_date(" March 2019 ").as_deref(), Some("2019-03"));
+        assert_eq!(iso_date("Sept. 2021").as_deref(), Some("2021-09"));
+        assert_eq!(iso_date("Present"), None);
+        assert_eq!(iso_date("03/19"), None);
+    }
+
+    #[test]
+    fn names_files_after_the_candidate() {
+        let mut resume = JsonResume::default();
+        assert_eq!(resume.file_name(), "resume.json");
+
+        resume.basics.name = Some(" Zoë O'Neil-Smith ".to_string());
+        assert_eq!(resume.file_name(), "Zoë_O_Neil-Smith.json");
+    }
+}
@@ -0,0 +1,531 @@
+use std::{collections::{BTreeMap, HashSet}, sync::Arc};
+
+use chrono::{Datelike, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{models::{analysis_result::{AnalysisResult, ExperienceEntry}, job::JobDescription, resume::Resume}, utils::error::HttpError, AppState};
+
+use super::{database::JobActions, taxonomy::SkillTaxonomy};
+
+const REQUIRED_SKILLS_WEIGHT: f64 = 0.5;
+const PREFERRED_SKILLS_WEIGHT: f64 = 0.15;
+const EXPERIENCE_WEIGHT: f64 = 0.2;
+const KEYWORDS_WEIGHT: f64 = 0.15;
+
+/// Resumes scored per round trip when refreshing cached job scores.
+const SCORE_BATCH_SIZE: usize = 100;
+
+/// How many of the most frequent job description words are checked for coverage.
+const MAX_KEYWORDS: usize = 25;
+
+const STOPWORDS: &[&str] = &[
+    "about", "above", "after", "all", "also", "and", "any", "are", "able", "as", "at", "be",
+    "been", "being", "both", "but", "by", "can", "candidate", "company", "could", "do", "does",
+    "each", "etc", "experience", "for", "from", "good", "has", "have", "help", "how", "ideal",
+    "including", "into", "its", "join", "just", "knowledge", "like", "looking", "may", "more",
+    "most", "must", "new", "not", "our", "out", "over", "plus", "preferred", "required",
+    "requirements", "role", "should", "skills", "strong", "such", "team", "than", "that", "the", "their",
+    "them", "then", "there", "these", "they", "this", "those", "through", "use", "using", "very",
+    "want", "was", "we", "well", "were", "what", "when", "where", "which", "while", "who", "will",
+    "with", "within", "work", "working", "would", "year", "years", "you", "your",
+];
+
+pub const MONTHS: &[&str] = &[
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoreComponent {
+    pub name: String,
+    pub weight: f64,
+    pub score: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillMatch {
+    pub matched_required: Vec<String>,
+    pub missing_required: Vec<String>,
+    pub matched_preferred: Vec<String>,
+    pub missing_preferred: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExperienceMatch {
+    pub required_years: Option<f64>,
+    pub estimated_years: f64,
+    pub gap_years: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeywordCoverage {
+    pub matched: Vec<String>,
+    pub missing: Vec<String>,
+    pub coverage: f64,
+}
+
+/// Resume-to-job fit on a 0-100 scale, with the parts it was built from.
+///
+/// Components that have nothing to compare (e.g. a job without preferred skills)
+/// are left out and the remaining weights are scaled up to compensate.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchReport {
+    pub score: f64,
+    pub components: Vec<ScoreComponent>,
+    pub skills: SkillMatch,
+    pub experience: ExperienceMatch,
+    pub keywords: KeywordCoverage,
+}
+
+/// Everything the matcher knows about a resume, lowercased and tokenized once.
+pub struct ResumeProfile {
+    taxonomy: Arc<SkillTaxonomy>,
+    skills: HashSet<String>,
+    tokens: Vec<String>,
+    token_set: HashSet<String>,
+    experience: Vec<ExperienceEntry>,
+}
+
+impl ResumeProfile {
+    pub fn new(resume: &Resume, taxonomy: Arc<SkillTaxonomy>) -> Self {
+        let analysis = resume.analysis().unwrap_or_default();
+        Self::from_parts(&analysis, resume.extracted_text.as_deref(), taxonomy)
+    }
+
+    pub fn from_parts(analysis: &AnalysisResult, text: Option<&str>, taxonomy: Arc<SkillTaxonomy>) -> Self {
+        let mut corpus = String::new();
+        if let Some(text) = text {
+            corpus.push_str(text);
+        }
+        for part in analysis.summary.iter().chain(analysis.skills.iter()) {
+            corpus.push('\n');
+            corpus.push_str(part);
+        }
+        for entry in &analysis.experience {
+            for part in [&entry.title, &entry.company, &entry.description].into_iter().flatten() {
+                corpus.push('\n');
+                corpus.push_str(part);
+            }
+        }
+
+        // Listing a skill implies its parents, React counts towards a JavaScript requirement
+        let mut skills = HashSet::new();
+        for skill in &analysis.skills {
+            skills.insert(normalize(&taxonomy.canonical_name(skill)));
+            for ancestor in taxonomy.ancestors(skill) {
+                skills.insert(normalize(&ancestor.name));
+            }
+        }
+
+        let tokens = tokenize(&corpus);
+        ResumeProfile {
+            taxonomy,
+            skills,
+            token_set: tokens.iter().cloned().collect(),
+            tokens,
+            experience: analysis.experience.clone(),
+        }
+    }
+
+    /// A skill counts when the analysis lists it or it, or one of its aliases, appears as a phrase in the resume.
+    fn has_skill(&self, skill: &str) -> bool {
+        let canonical = self.taxonomy.lookup(skill);
+        if self.skills.contains(&normalize(skill))
+            || canonical.is_some_and(|canonical| self.skills.contains(&normalize(&canonical.name)))
+        {
+            return true;
+        }
+
+        let names = std::iter::once(skill)
+            .chain(canonical.map(|canonical| canonical.name.as_str()))
+            .chain(canonical.into_iter().flat_map(|canonical| canonical.aliases.iter().map(String::as_str)));
+
+        names.map(tokenize).any(|phrase| {
+            !phrase.is_empty() && self.tokens.windows(phrase.len()).any(|window| window == phrase)
+        })
+    }
+}
+
+pub fn match_resume(resume: &Resume, job: &JobDescription, taxonomy: Arc<SkillTaxonomy>) -> MatchReport {
+    match_profile(&ResumeProfile::new(resume, taxonomy), job)
+}
+
+pub fn match_profile(profile: &ResumeProfile, job: &JobDescription) -> MatchReport {
+    let (matched_required, missing_required) = split_skills(profile, &job.required_skills);
+    let (matched_preferred, missing_preferred) = split_skills(profile, &job.preferred_skills);
+
+    let required_years = job
+        .min_years_experience
+        .map(|years| years as f64)
+        .or_else(|| job.seniority.map(|seniority| seniority.typical_years()));
+    let estimated_years = round(estimate_years(&profile.experience));
+    let experience = ExperienceMatch {
+        required_years,
+        estimated_years,
+        gap_years: round(required_years.map_or(0.0, |required| (required - estimated_years).max(0.0))),
+    };
+
+    let keywords = job_keywords(job);
+    let (matched_keywords, missing_keywords): (Vec<String>, Vec<String>) = keywords
+        .into_iter()
+        .partition(|keyword| profile.token_set.contains(keyword));
+    let keyword_total = matched_keywords.len() + missing_keywords.len();
+    let keywords = KeywordCoverage {
+        coverage: round(ratio(matched_keywords.len(), keyword_total) * 100.0),
+        matched: matched_keywords,
+        missing: missing_keywords,
+    };
+
+    let mut components = Vec::new();
+    if !job.required_skills.is_empty() {
+        components.push(component(
+            "required_skills",
+            REQUIRED_SKILLS_WEIGHT,
+            ratio(matched_required.len(), job.required_skills.len()),
+        ));
+    }
+    if !job.preferred_skills.is_empty() {
+        components.push(component(
+            "preferred_skills",
+            PREFERRED_SKILLS_WEIGHT,
+            ratio(matched_preferred.len(), job.preferred_skills.len()),
+        ));
+    }
+    if let Some(required) = required_years {
+        let fraction = if required <= 0.0 { 1.0 } else { (estimated_years / required).min(1.0) };
+        components.push(component("experience", EXPERIENCE_WEIGHT, fraction));
+    }
+    if keyword_total > 0 {
+        components.push(component("keywords", KEYWORDS_WEIGHT, ratio(keywords.matched.len(), keyword_total)));
+    }
+
+    let total_weight: f64 = components.iter().map(|c| c.weight).sum();
+    for c in components.iter_mut() {
+        c.weight = round(c.weight / total_weight);
+    }
+    let score = components.iter().map(|c| c.weight * c.score).sum::<f64>();
+
+    MatchReport {
+        score: round(score.min(100.0)),
+        components,
+        skills: SkillMatch {
+            matched_required,
+            missing_required,
+            matched_preferred,
+            missing_preferred,
+        },
+        experience,
+        keywords,
+    }
+}
+
+/// Scores every resume of the job owner whose cached score is missing or stale.
+///
+/// A cached score is stale once the job's matching fields change (`match_version`) or the
+/// resume has been re-analyzed, so after an edit only the affected rows are recomputed.
+pub async fn refresh_job_scores(app_state: &AppState, job: &JobDescription) -> Result<usize, HttpError> {
+    let mut scored = 0;
+
+    loop {
+        let resumes = app_state
+            .db_client
+            .get_unscored_resumes(job, SCORE_BATCH_SIZE)
+            .await
+            .map_err(|e| HttpError::server_error(e.to_string()))?;
+        if resumes.is_empty() {
+            break;
+        }
+
+        let batch_job = job.clone();
+        let taxonomy = app_state.taxonomy.get();
+        let scores = tokio::task::spawn_blocking(move || {
+            resumes
+                .into_iter()
+                .map(|resume| {
+                    let report = match_resume(&resume, &batch_job, taxonomy.clone());
+                    let value = serde_json::to_value(&report).unwrap_or_default();
+                    (resume, report.score, value)
+                })
+                .collect::<Vec<_>>()
+        })
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+        app_state
+            .db_client
+            .save_match_scores(job, &scores)
+            .await
+            .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+        scored += scores.len();
+    }
+
+    if scored > 0 {
+        tracing::debug!("Scored {} resumes against job {}", scored, job.id);
+    }
+
+    Ok(scored)
+}
+
+fn component(name: &str, weight: f64, fraction: f64) -> ScoreComponent {
+    ScoreComponent {
+        name: name.to_string(),
+        weight,
+        score: round(fraction * 100.0),
+    }
+}
+
+fn split_skills(profile: &ResumeProfile, skills: &[String]) -> (Vec<String>, Vec<String>) {
+    skills.iter().cloned().partition(|skill| profile.has_skill(skill))
+}
+
+fn ratio(part: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 / total as f64
+    }
+}
+
+fn round(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+fn normalize(value: &str) -> String {
+    value.trim().to_lowercase()
+}
+
+/// Lowercase words, keeping `+` and `#` so C++ and C# survive.
+fn tokenize(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !(c.is_alphanumeric() || c == '+' || c == '#'))
+        .filter(|token| !token.is_empty())
+        .map(|token| token.to_string())
+        .collect()
+}
+
+/// The most frequent meaningful words of the job title and description.
+fn job_keywords(job: &JobDescription) -> Vec<String> {
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    for token in tokenize(&format!("{}\n{}", job.title, job.description)) {
+        let meaningful = token.chars().count() >= 3
+            && !token.chars().all(|c| c.is_ascii_digit())
+            && !STOPWORDS.contains(&token.as_str());
+        if meaningful {
+            *counts.entry(token).or_default() += 1;
+        }
+    }
+
+    let mut keywords: Vec<(String, usize)> = counts.into_iter().collect();
+    // BTreeMap iteration is alphabetical, so the stable sort breaks ties by name
+    keywords.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    keywords.into_iter().take(MAX_KEYWORDS).map(|(keyword, _)| keyword).collect()
+}
+
+/// Total years covered by the experience entries, counting overlapping positions once.
+pub fn estimate_years(experience: &[ExperienceEntry]) -> f64 {
+    let now = Utc::now().date_naive();
+    let current_month = now.year() * 12 + now.month0() as i32;
+
+    let mut periods: Vec<(i32, i32)> = experience
+        .iter()
+        .filter_map(|entry| {
+            let start = entry.start_date.as_deref().and_then(|date| parse_month(date, current_month))?;
+            // Parsers leave the end date empty for the current position
+            let end = entry
+                .end_date
+                .as_deref()
+                .and_then(|date| parse_month(date, current_month))
+                .unwrap_or(current_month);
+            (end >= start).then_some((start, end.min(current_month)))
+        })
+        .collect();
+    periods.sort();
+
+    let mut months = 0;
+    let mut covered: Option<(i32, i32)> = None;
+    for (start, end) in periods {
+        covered = match covered {
+            Some((covered_start, covered_end)) if start <= covered_end => Some((covered_start, covered_end.max(end))),
+            Some((covered_start, covered_end)) => {
+                months += covered_end - covered_start;
+                Some((start, end))
+            }
+            None => Some((start, end)),
+        };
+    }
+    if let Some((start, end)) = covered {
+        months += end - start;
+    }
+
+    months as f64 / 12.0
+}
+
+/// Parses dates like "2019", "03/2019", "2019-03", "March 2019" or "Present" into a month index.
+fn parse_month(date: &str, current_month: i32) -> Option<i32> {
+    let date = date.trim().to_lowercase();
+    if ["present", "current", "now", "today"].iter().any(|word| date.contains(word)) {
+        return Some(current_month);
+    }
+
+    let parts: Vec<&str> = date
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect();
+
+    let year = parts
+        .iter()
+        .filter_map(|part| part.parse::<i32>().ok())
+        .find(|year| (1950..=2100).contains(year))?;
+
+    let month = parts
+        .iter()
+        .find_map(|part| {
+            MONTHS
+                .iter()
+                .position(|month| part.starts_with(month))
+                .map(|index| index as i32)
+                .or_else(|| part.parse::<i32>().ok().filter(|m| (1..=12).contains(m)).map(|m| m - 1))
+        })
+        .unwrap_or(0);
+
+    Some(year * 12 + month)
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::models::skill::{Skill, SkillAlias};
+
+    use super::*;
+
+    fn skill(name: &str, parent_id: Option<Uuid>) -> Skill {
+        Skill {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            category: "language".to_string(),
+            parent_id,
+            created_at: None,
+        }
+    }
+
+    fn taxonomy() -> Arc<SkillTaxonomy> {
+        let javascript = skill("JavaScript", None);
+        let react = skill("React", Some(javascript.id));
+        let alias = SkillAlias {
+            alias: "js".to_string(),
+            skill_id: javascript.id,
+            created_at: None,
+        };
+        Arc::new(SkillTaxonomy::new(
+            vec![javascript, react, skill("Rust", None), skill("Go", None), skill("Docker", None)],
+            vec![alias],
+        ))
+    }
+
+    fn job(required: &[&str], preferred: &[&str]) -> JobDescription {
+        JobDescription {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            title: String::new(),
+            description: String::new(),
+            required_skills: required.iter().map(|s| s.to_string()).collect(),
+            preferred_skills: preferred.iter().map(|s| s.to_string()).collect(),
+            seniority: None,
+            min_years_experience: None,
+            match_version: 1,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    fn profile(skills: &[&str], text: &str) -> ResumeProfile {
+        let analysis = AnalysisResult {
+            skills: skills.iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        };
+        ResumeProfile::from_parts(&analysis, Some(text), taxonomy())
+    }
+
+    fn experience(start: &str, end: &str) -> ExperienceEntry {
+        ExperienceEntry {
+            start_date: Some(start.to_string()),
+            end_date: Some(end.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn scales_weights_to_the_components_present() {
+        let report = match_profile(&profile(&["Rust", "Docker"], ""), &job(&["Rust", "Go"], &["Docker"]));
+
+        let weights: Vec<(&str, f64)> = report.components.iter().map(|c| (c.name.as_str(), c.weight)).collect();
+        assert_eq!(weights, vec![("required_skills", 0.77), ("preferred_skills", 0.23)]);
+        assert_eq!(report.score, 61.5);
+    }
+
+    #[test]
+    fn reports_missing_skills() {
+        let report = match_profile(&profile(&["Rust"], ""), &job(&["Rust", "Go"], &["Docker"]));
+
+        assert_eq!(report.skills.matched_required, vec!["Rust"]);
+        assert_eq!(report.skills.missing_required, vec!["Go"]);
+        assert!(report.skills.matched_preferred.is_empty());
+        assert_eq!(report.skills.missing_preferred, vec!["Docker"]);
+    }
+
+    #[test]
+    fn matches_skills_through_parents_and_aliases() {
+        let javascript = job(&["JavaScript"], &[]);
+
+        assert_eq!(match_profile(&profile(&["React"], ""), &javascript).score, 100.0);
+        assert_eq!(match_profile(&profile(&[], "Wrote JS daily"), &javascript).score, 100.0);
+        assert_eq!(match_profile(&profile(&[], "Wrote Java daily"), &javascript).score, 0.0);
+    }
+
+    #[test]
+    fn scores_an_empty_job_as_zero() {
+        let report = match_profile(&profile(&["Rust"], "Rust developer"), &job(&[], &[]));
+
+        assert!(report.components.is_empty());
+        assert_eq!(report.score, 0.0);
+        assert_eq!(report.keywords.coverage, 0.0);
+        assert_eq!(report.experience.required_years, None);
+    }
+
+    #[test]
+    fn keeps_the_score_between_0_and_100() {
+        let mut senior = job(&["Rust"], &["Docker"]);
+        senior.title = "Rust engineer".to_string();
+        senior.min_years_experience = Some(2);
+
+        let mut overqualified = profile(&["Rust", "Docker"], "Rust engineer");
+        overqualified.experience = vec![experience("2000", "2020")];
+        let best = match_profile(&overqualified, &senior);
+        assert_eq!(best.score, 100.0);
+        assert_eq!(best.experience.gap_years, 0.0);
+
+        let worst = match_profile(&profile(&[], ""), &senior);
+        assert_eq!(worst.score, 0.0);
+        assert_eq!(worst.experience.gap_years, 2.0);
+    }
+
+    #[test]
+    fn counts_overlapping_positions_once() {
+        let years = estimate_years(&[experience("2015", "2017"), experience("2016", "2018"), experience("2020", "2021")]);
+
+        assert_eq!(years, 4.0);
+    }
+
+    #[test]
+    fn parses_month_formats() {
+        let march_2019 = 2019 * 12 + 2;
+
+        assert_eq!(parse_month("March 2019", 0), Some(march_2019));
+        assert_eq!(parse_month("03/2019", 0), Some(march_2019));
+        assert_eq!(parse_month("2019-03", 0), Some(march_2019));
+        assert_eq!(parse_month("2019", 0), Some(2019 * 12));
+        assert_eq!(parse_month("Present", 42), Some(42));
+        assert_eq!(parse_month("someday", 42), None);
+    }
+}
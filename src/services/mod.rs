@@ -1,3 +1,22 @@
+pub mod analysis;
+pub mod circuit_breaker;
 pub mod database;
+pub mod embeddings;
+pub mod extraction;
 pub mod middleware;
-pub mod nlp;
\ No newline at end of file
+pub mod nlp;
+pub mod matching;
+pub mod taxonomy;
+pub mod ats;
+pub mod diff;
+pub mod storage;
+pub mod bulk;
+pub mod redaction;
+pub mod sharing;
+pub mod trash;
+pub mod scheduler;
+pub mod retention;
+pub mod quota;
+pub mod export;
+pub mod json_resume;
+pub mod importers;
\ No newline at end of file
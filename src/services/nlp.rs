@@ -1,39 +1,242 @@
-use std::fs;
+use std::{fmt, sync::Arc, time::Duration};
 
-use reqwest::multipart;
+use async_trait::async_trait;
+use axum::http::StatusCode;
+use reqwest::{header::AUTHORIZATION, multipart};
 use serde_json::Value;
+use tokio::fs;
 
-use crate::utils::error::HttpError;
-
-pub async fn call_nlp_service(
-    http_client: &reqwest::Client,
-    file_path: &str,
-    file_name: &str,
-) -> Result<Value, HttpError>{
-    println!("Reading file from path: {}", file_path);
-    let file_bytes = fs::read(file_path)
-        .map_err(|e| HttpError::server_error(e.to_string()))?;
-
-    let form = multipart::Form::new()
-    .part(
-        "file",
-        multipart::Part::bytes(file_bytes)
-        .file_name(file_name.to_string()),
-    );
-
-    println!("Sending request to NLP service at http://host.docker.internal:8000/analyze_resume/");
-    let response = http_client
-        .post("http://host.docker.internal:8000/analyze_resume/")
-        // Uncomment when running locally
-        // .post("http://host.docker.internal:8000/analyze_resume/")
-        .multipart(form)
-        .send()
-        .await
-        .map_err(|e| HttpError::server_error(e.to_string()))?;
-        
-    let json: Value = response.json().await
-        .map_err(|e| HttpError::server_error(e.to_string()))?;
-    
-    println!("Successfully parsed response");
-    Ok(json)
-}
\ No newline at end of file
+use crate::{config::Config, models::analysis_result::AnalysisResult, utils::error::HttpError};
+
+use super::circuit_breaker::{CircuitBreaker, CircuitBreakerAnalyzer};
+
+#[derive(Debug)]
+pub enum NlpError {
+    Io(String),
+    Transport(String),
+    Timeout,
+    Status { status: u16 },
+    MalformedResponse(String),
+    InvalidSchema(String),
+    CircuitOpen,
+}
+
+impl NlpError {
+    /// Whether another attempt against the provider could succeed.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            NlpError::Transport(_) | NlpError::Timeout => true,
+            NlpError::Status { status } => *status >= 500 || *status == 429,
+            NlpError::Io(_)
+            | NlpError::MalformedResponse(_)
+            | NlpError::InvalidSchema(_)
+            | NlpError::CircuitOpen => false,
+        }
+    }
+}
+
+impl fmt::Display for NlpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NlpError::Io(e) => write!(f, "Could not read resume file: {}", e),
+            NlpError::Transport(e) => write!(f, "Could not reach NLP service: {}", e),
+            NlpError::Timeout => write!(f, "NLP service timed out"),
+            NlpError::Status { status } => write!(f, "NLP service responded with status {}", status),
+            NlpError::MalformedResponse(e) => write!(f, "NLP service returned malformed JSON: {}", e),
+            NlpError::InvalidSchema(e) => write!(f, "NLP service returned an invalid analysis: {}", e),
+            NlpError::CircuitOpen => write!(f, "NLP service is unavailable, try again later"),
+        }
+    }
+}
+
+impl std::error::Error for NlpError {}
+
+impl From<NlpError> for HttpError {
+    fn from(err: NlpError) -> Self {
+        let status = match err {
+            NlpError::Io(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            NlpError::Timeout => StatusCode::GATEWAY_TIMEOUT,
+            NlpError::CircuitOpen => StatusCode::SERVICE_UNAVAILABLE,
+            NlpError::Transport(_)
+            | NlpError::Status { .. }
+            | NlpError::MalformedResponse(_)
+            | NlpError::InvalidSchema(_) => StatusCode::BAD_GATEWAY,
+        };
+
+        HttpError::new(err.to_string(), status)
+    }
+}
+
+#[async_trait]
+pub trait Analyzer: fmt::Debug + Send + Sync {
+    async fn analyze(
+        &self,
+        file_path: &str,
+        file_name: &str,
+        text: Option<&str>,
+        model: &str,
+    ) -> Result<AnalysisResult, NlpError>;
+}
+
+pub fn build_analyzer(
+    config: &Config,
+    http_client: reqwest::Client,
+    circuit_breaker: Arc<CircuitBreaker>,
+) -> Arc<dyn Analyzer> {
+    let analyzer: Arc<dyn Analyzer> = match config.nlp_provider.as_str() {
+        "stub" => Arc::new(StubAnalyzer),
+        // Config::init only accepts http and stub
+        _ => Arc::new(HttpAnalyzer::new(http_client, config)),
+    };
+
+    Arc::new(CircuitBreakerAnalyzer::new(analyzer, circuit_breaker))
+}
+
+enum AnalyzerContent {
+    Text(String),
+    File(Vec<u8>),
+}
+
+/// Posts the resume as multipart form data to the Python NLP service.
+#[derive(Debug, Clone)]
+pub struct HttpAnalyzer {
+    http_client: reqwest::Client,
+    url: String,
+    timeout: Duration,
+    auth_header: Option<String>,
+    max_retries: u32,
+    retry_backoff: Duration,
+}
+
+impl HttpAnalyzer {
+    pub fn new(http_client: reqwest::Client, config: &Config) -> Self {
+        HttpAnalyzer {
+            http_client,
+            url: config.nlp_service_url.clone(),
+            timeout: Duration::from_secs(config.nlp_timeout_secs),
+            auth_header: config.nlp_auth_header.clone(),
+            max_retries: config.nlp_max_retries,
+            retry_backoff: Duration::from_millis(config.nlp_retry_backoff_ms),
+        }
+    }
+
+    async fn send(&self, content: &AnalyzerContent, file_name: &str, model: &str) -> Result<Value, NlpError> {
+        let form = match content {
+            AnalyzerContent::Text(text) => multipart::Form::new()
+                .text("text", text.clone())
+                .text("file_name", file_name.to_string()),
+            AnalyzerContent::File(file_bytes) => multipart::Form::new().part(
+                "file",
+                multipart::Part::bytes(file_bytes.clone()).file_name(file_name.to_string()),
+            ),
+        }
+        .text("model", model.to_string());
+
+        let mut request = self
+            .http_client
+            .post(&self.url)
+            .timeout(self.timeout)
+            .multipart(form);
+
+        if let Some(auth_header) = &self.auth_header {
+            request = request.header(AUTHORIZATION, auth_header);
+        }
+
+        let response = request.send().await.map_err(|e| {
+            if e.is_timeout() {
+                NlpError::Timeout
+            } else {
+                NlpError::Transport(e.to_string())
+            }
+        })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            // The body can echo the resume back, so it goes to the log rather than to the client
+            let body = response.text().await.unwrap_or_default();
+            tracing::warn!("NLP service responded with status {}: {}", status, body);
+            return Err(NlpError::Status { status: status.as_u16() });
+        }
+
+        let body = response.bytes().await.map_err(|e| {
+            if e.is_timeout() {
+                NlpError::Timeout
+            } else {
+                NlpError::Transport(e.to_string())
+            }
+        })?;
+
+        serde_json::from_slice(&body).map_err(|e| NlpError::MalformedResponse(e.to_string()))
+    }
+}
+
+#[async_trait]
+impl Analyzer for HttpAnalyzer {
+    async fn analyze(
+        &self,
+        file_path: &str,
+        file_name: &str,
+        text: Option<&str>,
+        model: &str,
+    ) -> Result<AnalysisResult, NlpError> {
+        // Send the text we already extracted, only fall back to the raw file when there is none
+        let content = match text {
+            Some(text) => AnalyzerContent::Text(text.to_string()),
+            None => {
+                tracing::debug!("Reading file from path: {}", file_path);
+                let file_bytes = fs::read(file_path)
+                    .await
+                    .map_err(|e| NlpError::Io(e.to_string()))?;
+                AnalyzerContent::File(file_bytes)
+            }
+        };
+
+        let mut attempt = 0;
+        loop {
+            tracing::debug!("Sending request to NLP service at {} (attempt {})", self.url, attempt + 1);
+            match self.send(&content, file_name, model).await {
+                Ok(json) => {
+                    tracing::debug!("Successfully parsed response");
+                    return AnalysisResult::from_response(json).map_err(NlpError::InvalidSchema);
+                }
+                Err(e) if e.is_retryable() && attempt < self.max_retries => {
+                    tracing::warn!("NLP request failed, retrying: {}", e);
+                    attempt += 1;
+                    tokio::time::sleep(self.retry_backoff * attempt).await;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+/// Deterministic offline analyzer for tests and local development without the NLP service.
+#[derive(Debug, Clone, Copy)]
+pub struct StubAnalyzer;
+
+#[async_trait]
+impl Analyzer for StubAnalyzer {
+    async fn analyze(
+        &self,
+        file_path: &str,
+        file_name: &str,
+        text: Option<&str>,
+        model: &str,
+    ) -> Result<AnalysisResult, NlpError> {
+        let size = match text {
+            Some(text) => text.len(),
+            None => fs::read(file_path)
+                .await
+                .map_err(|e| NlpError::Io(e.to_string()))?
+                .len(),
+        };
+
+        Ok(AnalysisResult {
+            summary: Some(format!(
+                "Stub analysis of {} ({} bytes) using {}",
+                file_name, size, model
+            )),
+            ..Default::default()
+        })
+    }
+}
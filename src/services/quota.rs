@@ -0,0 +1,134 @@
+use std::sync::Arc;
+
+use axum::http::StatusCode;
+use chrono::{DateTime, Days, NaiveDate, Utc};
+use tokio::fs;
+use uuid::Uuid;
+
+use crate::{models::quota::QuotaUsage, services::database::QuotaActions, utils::error::{ErrorMessage, HttpError}, AppState};
+
+const BACKFILL_BATCH_SIZE: i64 = 100;
+
+pub async fn get_usage(app_state: &AppState, user_id: Uuid) -> Result<QuotaUsage, HttpError> {
+    app_state
+        .db_client
+        .get_quota_usage(user_id)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?
+        .ok_or_else(|| HttpError::not_found(ErrorMessage::UserNotFound.to_string()))
+}
+
+/// When today's analysis allowance starts over.
+pub fn analyses_reset_at() -> DateTime<Utc> {
+    let today = Utc::now().date_naive();
+    today
+        .checked_add_days(Days::new(1))
+        .unwrap_or(today)
+        .and_hms_opt(0, 0, 0)
+        .unwrap_or_default()
+        .and_utc()
+}
+
+fn analysis_quota_error(limit: i32) -> HttpError {
+    HttpError::new(
+        format!(
+            "Daily analysis quota of {} reached, it resets at {}",
+            limit,
+            analyses_reset_at().to_rfc3339()
+        ),
+        StatusCode::TOO_MANY_REQUESTS,
+    )
+}
+
+/// Refuses an upload of `bytes` that would take the user past their resume or storage quota.
+pub async fn check_storage_quota(app_state: &AppState, user_id: Uuid, bytes: usize) -> Result<(), HttpError> {
+    let usage = get_usage(app_state, user_id).await?;
+
+    if let Some(limit) = usage.max_resumes
+        && usage.resumes >= limit as i64
+    {
+        return Err(HttpError::new(
+            format!("Resume quota of {} reached, delete resumes and empty the trash to upload more", limit),
+            StatusCode::FORBIDDEN,
+        ));
+    }
+    if let Some(limit) = usage.max_storage_bytes
+        && usage.storage_bytes + bytes as i64 > limit
+    {
+        return Err(HttpError::new(
+            format!(
+                "Storage quota of {} bytes would be exceeded, {} bytes are in use",
+                limit, usage.storage_bytes
+            ),
+            StatusCode::FORBIDDEN,
+        ));
+    }
+
+    Ok(())
+}
+
+/// Refuses work that needs an analysis when none are left for today, without using one up.
+pub async fn check_analysis_quota(app_state: &AppState, user_id: Uuid) -> Result<(), HttpError> {
+    let usage = get_usage(app_state, user_id).await?;
+
+    match usage.max_analyses_per_day {
+        Some(limit) if usage.analyses_today >= limit => Err(analysis_quota_error(limit)),
+        _ => Ok(()),
+    }
+}
+
+/// Uses up one of today's analyses, failing with 429 when there are none left.
+/// Returns the day it was counted against, for `release_analysis`.
+pub async fn reserve_analysis(app_state: &AppState, user_id: Uuid) -> Result<NaiveDate, HttpError> {
+    let reserved = app_state
+        .db_client
+        .reserve_analysis(user_id)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    if let Some(day) = reserved {
+        return Ok(day);
+    }
+
+    let usage = get_usage(app_state, user_id).await?;
+    Err(analysis_quota_error(usage.max_analyses_per_day.unwrap_or_default()))
+}
+
+/// Gives back an analysis reserved for a call that didn't produce one.
+pub async fn release_analysis(app_state: &AppState, user_id: Uuid, day: NaiveDate) {
+    if let Err(e) = app_state.db_client.release_analysis(user_id, day).await {
+        tracing::warn!("Could not give back an analysis to user {}: {}", user_id, e);
+    }
+}
+
+/// Records the size of files uploaded before sizes were stored, so they count against storage quotas.
+/// Files that are gone count as empty.
+pub async fn backfill_file_sizes(app_state: Arc<AppState>) {
+    loop {
+        let files = match app_state.db_client.get_unsized_files(BACKFILL_BATCH_SIZE).await {
+            Ok(files) => files,
+            Err(e) => {
+                tracing::warn!("Could not load files without a size: {}", e);
+                return;
+            }
+        };
+        if files.is_empty() {
+            return;
+        }
+
+        for file_path in files {
+            let file_size = match fs::metadata(&file_path).await {
+                Ok(metadata) => metadata.len() as i64,
+                Err(e) => {
+                    tracing::debug!("Could not read size of {}: {}", file_path, e);
+                    0
+                }
+            };
+
+            if let Err(e) = app_state.db_client.set_file_size(&file_path, file_size).await {
+                tracing::warn!("Could not record size of {}: {}", file_path, e);
+                return;
+            }
+        }
+    }
+}
@@ -0,0 +1,381 @@
+use std::sync::LazyLock;
+
+use regex::{Captures, Regex};
+
+use crate::{
+    models::{analysis::ResumeAnalysis, analysis_result::{AnalysisResult, ContactInfo}, resume::{Resume, ResumeDocument}, users::User},
+    AppState,
+};
+
+static EMAIL: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}").unwrap());
+
+static URL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(?:https?://|www\.|(?:linkedin|github|gitlab|twitter|x|behance|dribbble)\.com/)\S+").unwrap()
+});
+
+static PHONE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\+?\(?\d[\d\s().-]{6,}\d").unwrap());
+
+static STREET_ADDRESS: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)\b\d{1,5}[a-z]?\s+(?:[A-Za-z.'-]+\s+){1,4}(?:street|st|avenue|ave|road|rd|boulevard|blvd|lane|ln|drive|dr|court|ct|way|place|pl|square|sq|terrace|strasse|straße)\b\.?(?:,?\s*(?:apt|suite|unit|#)\.?\s*\w+)?",
+    )
+    .unwrap()
+});
+
+/// "Date of birth: ...", "Gender: ..." and similar lines, keeping the label
+static PERSONAL_DETAIL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?im)^(\s*(?:date of birth|birth ?date|d\.?o\.?b\.?|age|gender|sex|marital status|nationality|citizenship|religion|place of birth|address|photo)\s*[:\-–])[^\n]*$",
+    )
+    .unwrap()
+});
+
+static BORN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)\b(born)(?:\s+(?:on|in))?\s+[^\n,;.]+").unwrap());
+
+/// The following word tells possessive "her team" from "reported to her"
+static PRONOUN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(he|she|him|his|her|hers|himself|herself)\b(\s+[a-z])?").unwrap()
+});
+
+/// Full dates, which in a resume are mostly dates of birth, employment is given by month or year
+static FULL_DATE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(?:\d{1,2}[./-]\d{1,2}[./-]\d{4}|\d{4}-\d{2}-\d{2})\b").unwrap());
+
+/// Only in title case and before a capitalized word, "MS Office" is not an honorific
+static HONORIFIC: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(?:Mr|Mrs|Ms|Miss|Mx)\.?\s+([A-Z])").unwrap());
+
+const REDACTED: &str = "[redacted]";
+
+/// Blind review is on when asked for, and can't be turned off for roles it is enforced for.
+pub fn is_blind_review(app_state: &AppState, user: &User, requested: Option<bool>) -> bool {
+    requested.unwrap_or(false)
+        || app_state
+            .env
+            .blind_review_roles
+            .iter()
+            .any(|role| role == user.role.to_str())
+}
+
+/// Removes personal details from free text: contact details, links, street addresses,
+/// dates of birth and similar personal fields, the candidate's names and gender markers.
+#[derive(Debug, Clone, Default)]
+pub struct Redactor {
+    names: Option<Regex>,
+    literals: Vec<String>,
+}
+
+impl Redactor {
+    /// Names come from the analysis contact details, and from the first line of the text when
+    /// it looks like a name, as resumes usually start with one.
+    pub fn for_resume(analysis: Option<&AnalysisResult>, text: Option<&str>) -> Self {
+        let mut names: Vec<String> = Vec::new();
+
+        if let Some(name) = analysis.and_then(|analysis| analysis.contact.name.as_deref()) {
+            names.push(name.to_string());
+        }
+        if let Some(line) = text.and_then(|text| text.lines().map(str::trim).find(|line| !line.is_empty()))
+            && looks_like_name(line)
+        {
+            names.push(line.to_string());
+        }
+
+        let mut parts: Vec<String> = names
+            .iter()
+            .flat_map(|name| {
+                let mut parts = vec![name.trim().to_string()];
+                parts.extend(name.split_whitespace().map(|part| part.trim_matches(|c: char| !c.is_alphabetic()).to_string()));
+                parts
+            })
+            .filter(|part| part.chars().count() >= 2)
+            .collect();
+        // Longest first so full names are replaced before their parts
+        parts.sort_by_key(|part| std::cmp::Reverse(part.len()));
+        parts.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+
+        let names = (!parts.is_empty())
+            .then(|| {
+                let alternatives: Vec<String> = parts.iter().map(|part| regex::escape(part)).collect();
+                Regex::new(&format!(r"(?i)\b(?:{})\b", alternatives.join("|"))).ok()
+            })
+            .flatten();
+
+        let literals = analysis
+            .and_then(|analysis| analysis.contact.location.clone())
+            .filter(|location| location.trim().len() >= 3)
+            .into_iter()
+            .collect();
+
+        Redactor { names, literals }
+    }
+
+    pub fn from_resume(resume: &Resume) -> Self {
+        Redactor::for_resume(resume.analysis().as_ref(), resume.extracted_text.as_deref())
+    }
+
+    pub fn redact(&self, text: &str) -> String {
+        let text = PERSONAL_DETAIL.replace_all(text, format!("${{1}} {}", REDACTED));
+        let text = BORN.replace_all(&text, format!("${{1}} {}", REDACTED));
+        let text = FULL_DATE.replace_all(&text, "[date]");
+        let text = EMAIL.replace_all(&text, "[email]");
+        let text = URL.replace_all(&text, "[link]");
+        let text = PHONE.replace_all(&text, |caps: &Captures| {
+            // Year ranges and other short numbers look alike, phone numbers have more digits
+            let digits = caps[0].chars().filter(char::is_ascii_digit).count();
+            if (9..=15).contains(&digits) {
+                "[phone]".to_string()
+            } else {
+                caps[0].to_string()
+            }
+        });
+        let text = STREET_ADDRESS.replace_all(&text, "[address]");
+
+        let mut text = text.into_owned();
+        for literal in &self.literals {
+            text = text.replace(literal.as_str(), "[address]");
+        }
+        if let Some(names) = &self.names {
+            text = names.replace_all(&text, "[name]").into_owned();
+        }
+
+        let text = HONORIFIC.replace_all(&text, "$1");
+        PRONOUN
+            .replace_all(&text, |caps: &Captures| {
+                let next = caps.get(2).map(|next| next.as_str()).unwrap_or_default();
+                format!("{}{}", neutral(&caps[1], !next.is_empty()), next)
+            })
+            .into_owned()
+    }
+
+    pub fn redact_analysis(&self, analysis: &AnalysisResult) -> AnalysisResult {
+        let mut analysis = analysis.clone();
+
+        analysis.contact = ContactInfo::default();
+        analysis.summary = analysis.summary.map(|summary| self.redact(&summary));
+        for entry in &mut analysis.experience {
+            entry.description = entry.description.as_ref().map(|description| self.redact(description));
+        }
+
+        analysis
+    }
+}
+
+fn looks_like_name(line: &str) -> bool {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    (2..=4).contains(&words.len())
+        && words.iter().all(|word| {
+            word.chars().next().is_some_and(char::is_uppercase)
+                && word.chars().all(|c| c.is_alphabetic() || c == '-' || c == '\'' || c == '.')
+        })
+}
+
+fn neutral(pronoun: &str, before_word: bool) -> String {
+    let replacement = match pronoun.to_lowercase().as_str() {
+        "he" | "she" => "they",
+        "him" => "them",
+        "her" if !before_word => "them",
+        "his" | "her" => "their",
+        "hers" => "theirs",
+        _ => "themself",
+    };
+
+    if pronoun.chars().next().is_some_and(char::is_uppercase) {
+        let mut chars = replacement.chars();
+        chars
+            .next()
+            .map(|first| first.to_uppercase().chain(chars).collect())
+            .unwrap_or_default()
+    } else {
+        replacement.to_string()
+    }
+}
+
+/// File names often contain the candidate's name, only the extension is kept.
+fn redact_file_name(file_name: &str) -> String {
+    let extension = std::path::Path::new(file_name)
+        .extension()
+        .map(|extension| format!(".{}", extension.to_string_lossy()))
+        .unwrap_or_default();
+    format!("{}{}", REDACTED, extension)
+}
+
+/// Anonymized copy of a resume for blind review.
+pub fn redact_resume(resume: &Resume) -> Resume {
+    let analysis = resume.analysis();
+    let redactor = Redactor::for_resume(analysis.as_ref(), resume.extracted_text.as_deref());
+
+    let mut redacted = resume.clone();
+    let file_name = redact_file_name(&resume.original_file_name());
+    redacted.file_path = resume.file_path.as_ref().map(|_| file_name.clone());
+    redacted.file_name = Some(file_name);
+    redacted.extracted_text = resume.extracted_text.as_deref().map(|text| redactor.redact(text));
+    if let Some(analysis) = analysis {
+        let analysis = redactor.redact_analysis(&analysis);
+        redacted.analysis_schema_version = analysis.schema_version;
+        redacted.analysis_result = Some(analysis.to_value());
+    }
+
+    redacted
+}
+
+/// Anonymized copy of one of a resume's past analyses for blind review. Names are taken from
+/// that analysis, as they may differ between runs.
+pub fn redact_resume_analysis(resume: &Resume, analysis: &ResumeAnalysis) -> ResumeAnalysis {
+    let mut redacted = analysis.clone();
+    match analysis.analysis() {
+        Some(result) => {
+            let redactor = Redactor::for_resume(Some(&result), resume.extracted_text.as_deref());
+            let result = redactor.redact_analysis(&result);
+            redacted.schema_version = result.schema_version;
+            redacted.analysis_result = result.to_value();
+        }
+        None => redacted.analysis_result = serde_json::Value::Null,
+    }
+
+    redacted
+}
+
+pub fn redact_document(document: &ResumeDocument) -> ResumeDocument {
+    let mut redacted = document.clone();
+    redacted.title = redact_file_name(&document.title);
+    redacted
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::analysis_result::ExperienceEntry;
+
+    use super::*;
+
+    fn analysis_for(name: &str, location: &str) -> AnalysisResult {
+        AnalysisResult {
+            contact: ContactInfo {
+                name: Some(name.to_string()),
+                email: Some("jane.doe@example.com".to_string()),
+                phone: Some("+49 30 1234 5678".to_string()),
+                location: Some(location.to_string()),
+                links: vec!["https://github.com/janedoe".to_string()],
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn redacts_contact_details() {
+        let redactor = Redactor::default();
+
+        let text = redactor.redact("Mail jane.doe+cv@example.co.uk or call +49 (30) 1234-5678.");
+
+        assert_eq!(text, "Mail [email] or call [phone].");
+    }
+
+    #[test]
+    fn redacts_links_with_and_without_scheme() {
+        let redactor = Redactor::default();
+
+        let text = redactor.redact("https://janedoe.dev, www.janedoe.dev and linkedin.com/in/janedoe");
+
+        assert_eq!(text, "[link] [link] and [link]");
+    }
+
+    #[test]
+    fn keeps_year_ranges_and_short_numbers() {
+        let redactor = Redactor::default();
+        let text = "Engineer 2015 - 2019, team of 12, 2019-2023";
+
+        assert_eq!(redactor.redact(text), text);
+    }
+
+    #[test]
+    fn redacts_street_addresses() {
+        let redactor = Redactor::default();
+
+        assert_eq!(redactor.redact("Lives at 221B Baker Street, London"), "Lives at [address], London");
+        assert_eq!(redactor.redact("12 Main St. Apt 4"), "[address]");
+    }
+
+    #[test]
+    fn redacts_personal_detail_lines_keeping_the_label() {
+        let redactor = Redactor::default();
+
+        let text = redactor.redact("Date of birth: 1 May 1990\nNationality: German\nMarital status - married\nSkills: Rust");
+
+        assert_eq!(
+            text,
+            "Date of birth: [redacted]\nNationality: [redacted]\nMarital status - [redacted]\nSkills: Rust"
+        );
+    }
+
+    #[test]
+    fn redacts_birth_mentions_and_full_dates() {
+        let redactor = Redactor::default();
+
+        assert_eq!(redactor.redact("Born in Hamburg, raised in Berlin"), "Born [redacted], raised in Berlin");
+        assert_eq!(redactor.redact("DOB 01.05.1990 or 1990-05-01"), "DOB [date] or [date]");
+    }
+
+    #[test]
+    fn redacts_names_from_analysis_and_first_line() {
+        let analysis = analysis_for("Jane Doe", "Berlin");
+        let text = "Jane A. Doe\nSenior engineer in Berlin. Jane led the Doe family business.";
+
+        let redactor = Redactor::for_resume(Some(&analysis), Some(text));
+
+        assert_eq!(
+            redactor.redact(text),
+            "[name]\nSenior engineer in [address]. [name] led the [name] family business."
+        );
+    }
+
+    #[test]
+    fn first_line_is_only_a_name_when_it_looks_like_one() {
+        let text = "Curriculum vitae of a senior engineer\nSkilled in Go";
+
+        let redactor = Redactor::for_resume(None, Some(text));
+
+        assert_eq!(redactor.redact(text), text);
+    }
+
+    #[test]
+    fn neutralizes_pronouns_and_honorifics() {
+        let redactor = Redactor::default();
+
+        assert_eq!(
+            redactor.redact("Mrs. Smith said she reported to her manager. He thanked her. His work was his own."),
+            "Smith said they reported to their manager. They thanked them. Their work was their own."
+        );
+        assert_eq!(redactor.redact("Knows MS Office"), "Knows MS Office");
+    }
+
+    #[test]
+    fn analysis_loses_contact_and_redacts_free_text() {
+        let mut analysis = analysis_for("Jane Doe", "Berlin");
+        analysis.summary = Some("Jane Doe, reachable at jane.doe@example.com".to_string());
+        analysis.skills = vec!["Rust".to_string()];
+        analysis.experience = vec![ExperienceEntry {
+            title: Some("Engineer".to_string()),
+            description: Some("She built payment APIs in Berlin".to_string()),
+            ..Default::default()
+        }];
+        let redactor = Redactor::for_resume(Some(&analysis), None);
+
+        let redacted = redactor.redact_analysis(&analysis);
+
+        assert_eq!(redacted.contact, ContactInfo::default());
+        assert_eq!(redacted.summary.as_deref(), Some("[name], reachable at [email]"));
+        assert_eq!(redacted.skills, vec!["Rust"]);
+        assert_eq!(redacted.experience[0].title.as_deref(), Some("Engineer"));
+        assert_eq!(
+            redacted.experience[0].description.as_deref(),
+            Some("They built payment APIs in [address]")
+        );
+    }
+
+    #[test]
+    fn file_names_keep_only_the_extension() {
+        assert_eq!(redact_file_name("Jane_Doe_CV.pdf"), "[redacted].pdf");
+        assert_eq!(redact_file_name("resume"), "[redacted]");
+    }
+}
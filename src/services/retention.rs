@@ -0,0 +1,83 @@
+use std::sync::Arc;
+
+use crate::{models::retention::{RetentionAction, RetentionCandidate}, services::{database::{AnonymizedResume, RetentionActions, TagActions, UserActions}, redaction::{redact_resume, Redactor}, storage::release_file}, AppState};
+
+const RETENTION_BATCH_SIZE: i64 = 100;
+
+/// Applies retention policies: resumes past their owner's retention period are deleted or
+/// anonymized, and their files removed once no other resume uses them.
+pub async fn apply_retention(app_state: Arc<AppState>) -> Result<u64, sqlx::Error> {
+    let mut total = 0;
+
+    loop {
+        let candidates = app_state.db_client.get_retention_candidates(RETENTION_BATCH_SIZE).await?;
+        let full_batch = candidates.len() as i64 == RETENTION_BATCH_SIZE;
+        let mut handled = 0;
+
+        for candidate in candidates {
+            match expire(&app_state, &candidate).await {
+                Ok(true) => handled += 1,
+                Ok(false) => {}
+                Err(e) => tracing::warn!("Could not apply retention to resume {}: {}", candidate.resume_id, e),
+            }
+        }
+        total += handled;
+
+        // Stop on a failing batch rather than spinning on the same rows
+        if !full_batch || handled == 0 {
+            return Ok(total);
+        }
+    }
+}
+
+async fn expire(app_state: &AppState, candidate: &RetentionCandidate) -> Result<bool, sqlx::Error> {
+    let file_path = match candidate.action {
+        RetentionAction::Delete => app_state.db_client.expire_resume(candidate.resume_id).await?,
+        RetentionAction::Anonymize => {
+            let Some(anonymized) = anonymize(app_state, candidate).await? else {
+                return Ok(false);
+            };
+            app_state.db_client.anonymize_resume(&anonymized).await?
+        }
+    };
+
+    let Some(file_path) = file_path else {
+        return Ok(false);
+    };
+
+    // Anonymized resumes have no file left
+    if let Some(file_path) = file_path {
+        release_file(app_state, &file_path).await;
+    }
+    Ok(true)
+}
+
+async fn anonymize(app_state: &AppState, candidate: &RetentionCandidate) -> Result<Option<AnonymizedResume>, sqlx::Error> {
+    let Some(resume) = app_state
+        .db_client
+        .get_resume(Some(candidate.user_id), Some(candidate.resume_id))
+        .await?
+    else {
+        return Ok(None);
+    };
+
+    let redactor = Redactor::from_resume(&resume);
+    let (note_ids, note_bodies) = app_state
+        .db_client
+        .get_notes(resume.id)
+        .await?
+        .into_iter()
+        .map(|note| (note.id, redactor.redact(&note.body)))
+        .unzip();
+
+    let redacted = redact_resume(&resume);
+    Ok(Some(AnonymizedResume {
+        resume_id: resume.id,
+        file_name: redacted.file_name,
+        extracted_text: redacted.extracted_text,
+        analysis_result: redacted.analysis_result,
+        analysis_schema_version: redacted.analysis_schema_version,
+        note_ids,
+        note_bodies,
+    }))
+}
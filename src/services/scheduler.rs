@@ -0,0 +1,70 @@
+use std::{future::Future, pin::Pin, sync::Arc, time::{Duration, Instant}};
+
+use tokio::time::MissedTickBehavior;
+
+use crate::AppState;
+
+/// A run of a task, resolving to how many items it handled.
+pub type TaskFuture = Pin<Box<dyn Future<Output = Result<u64, sqlx::Error>> + Send>>;
+
+type TaskFn = Arc<dyn Fn(Arc<AppState>) -> TaskFuture + Send + Sync>;
+
+struct ScheduledTask {
+    name: &'static str,
+    every: Duration,
+    run: TaskFn,
+}
+
+/// Periodic maintenance inside the server. Every task gets its own timer, first running at
+/// startup, and never overlaps itself: a run that outlasts its interval delays the next one.
+#[derive(Default)]
+pub struct Scheduler {
+    tasks: Vec<ScheduledTask>,
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Scheduler::default()
+    }
+
+    pub fn every<F, Fut>(mut self, name: &'static str, every: Duration, task: F) -> Self
+    where
+        F: Fn(Arc<AppState>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<u64, sqlx::Error>> + Send + 'static,
+    {
+        self.tasks.push(ScheduledTask {
+            name,
+            every,
+            run: Arc::new(move |app_state| Box::pin(task(app_state))),
+        });
+        self
+    }
+
+    pub fn start(self, app_state: Arc<AppState>) {
+        for task in self.tasks {
+            tracing::info!("Scheduling {} every {:?}", task.name, task.every);
+            tokio::spawn(run_task(task, app_state.clone()));
+        }
+    }
+}
+
+async fn run_task(task: ScheduledTask, app_state: Arc<AppState>) {
+    let mut interval = tokio::time::interval(task.every);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        interval.tick().await;
+
+        let started = Instant::now();
+        match (task.run)(app_state.clone()).await {
+            Ok(0) => tracing::debug!("Scheduled task {} had nothing to do", task.name),
+            Ok(handled) => tracing::info!(
+                "Scheduled task {} handled {} items in {} ms",
+                task.name,
+                handled,
+                started.elapsed().as_millis()
+            ),
+            Err(e) => tracing::warn!("Scheduled task {} failed: {}", task.name, e),
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use axum::http::StatusCode;
+use chrono::{Duration, Utc};
+
+use crate::{models::{resume::Resume, share::ShareLink}, services::{database::{ShareActions, UserActions}, redaction::redact_resume, storage::content_hash}, utils::{error::{ErrorMessage, HttpError}, password}, AppState};
+
+const TOKEN_BYTES: usize = 32;
+pub const TOKEN_PREFIX_LEN: usize = 8;
+
+/// Wrong passwords allowed per link within the window before it stops accepting guesses
+const MAX_PASSWORD_ATTEMPTS: i64 = 10;
+const PASSWORD_ATTEMPT_WINDOW_MINUTES: i64 = 15;
+
+/// Who opened a share link, for the owner's access log.
+#[derive(Debug, Clone, Default)]
+pub struct ShareClient {
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ShareDenial {
+    Revoked,
+    Expired,
+    ViewsExhausted,
+    TooManyAttempts,
+    PasswordRequired,
+    WrongPassword,
+}
+
+impl ShareDenial {
+    fn reason(&self) -> &'static str {
+        match self {
+            ShareDenial::Revoked => "revoked",
+            ShareDenial::Expired => "expired",
+            ShareDenial::ViewsExhausted => "views_exhausted",
+            ShareDenial::TooManyAttempts => "too_many_attempts",
+            ShareDenial::PasswordRequired => "password_required",
+            ShareDenial::WrongPassword => "wrong_password",
+        }
+    }
+
+    /// A revoked link looks the same as one that never existed
+    fn error(&self) -> HttpError {
+        match self {
+            ShareDenial::Revoked => HttpError::not_found(ErrorMessage::ShareLinkNotFound.to_string()),
+            ShareDenial::Expired => HttpError::new("Share link has expired", StatusCode::GONE),
+            ShareDenial::ViewsExhausted => HttpError::new("Share link has reached its view limit", StatusCode::GONE),
+            ShareDenial::TooManyAttempts => HttpError::new("Too many wrong passwords, try again later", StatusCode::TOO_MANY_REQUESTS),
+            ShareDenial::PasswordRequired => HttpError::unauthorized("Share link requires a password"),
+            ShareDenial::WrongPassword => HttpError::unauthorized("Wrong password"),
+        }
+    }
+}
+
+/// A new unguessable link token, hex encoded.
+pub fn generate_token() -> String {
+    let mut bytes = [0u8; TOKEN_BYTES];
+    OsRng.fill_bytes(&mut bytes);
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Tokens are stored hashed, a leaked database doesn't leak working links.
+pub fn token_hash(token: &str) -> String {
+    content_hash(token.as_bytes())
+}
+
+fn is_token(token: &str) -> bool {
+    token.len() == TOKEN_BYTES * 2 && token.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+async fn log_access(app_state: &AppState, link: &ShareLink, client: &ShareClient, denial: Option<ShareDenial>) {
+    if let Err(e) = app_state
+        .db_client
+        .log_share_access(
+            link.id,
+            denial.is_none(),
+            denial.map(|denial| denial.reason()),
+            client.ip.as_deref(),
+            client.user_agent.as_deref(),
+        )
+        .await
+    {
+        tracing::warn!("Could not log access to share link {}: {}", link.id, e);
+    }
+}
+
+async fn deny(app_state: &AppState, link: &ShareLink, client: &ShareClient, denial: ShareDenial) -> HttpError {
+    log_access(app_state, link, client, Some(denial)).await;
+    denial.error()
+}
+
+/// Checks a link and counts the view, returning it with the resume as the link shows it.
+pub async fn open_share_link(
+    app_state: &AppState,
+    token: &str,
+    password: Option<&str>,
+    client: &ShareClient,
+) -> Result<(ShareLink, Resume), HttpError> {
+    if !is_token(token) {
+        return Err(ShareDenial::Revoked.error());
+    }
+
+    let link = app_state
+        .db_client
+        .get_share_link_by_token(&token_hash(token))
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?
+        .ok_or_else(|| ShareDenial::Revoked.error())?;
+
+    if link.revoked_at.is_some() {
+        return Err(deny(app_state, &link, client, ShareDenial::Revoked).await);
+    }
+    if link.is_expired() {
+        return Err(deny(app_state, &link, client, ShareDenial::Expired).await);
+    }
+    if link.views_exhausted() {
+        return Err(deny(app_state, &link, client, ShareDenial::ViewsExhausted).await);
+    }
+
+    if let Some(password_hash) = &link.password_hash {
+        let failed = app_state
+            .db_client
+            .count_failed_share_access(
+                link.id,
+                ShareDenial::WrongPassword.reason(),
+                Utc::now() - Duration::minutes(PASSWORD_ATTEMPT_WINDOW_MINUTES),
+            )
+            .await
+            .map_err(|e| HttpError::server_error(e.to_string()))?;
+        if failed >= MAX_PASSWORD_ATTEMPTS {
+            return Err(deny(app_state, &link, client, ShareDenial::TooManyAttempts).await);
+        }
+
+        let Some(password) = password.filter(|password| !password.is_empty()) else {
+            return Err(deny(app_state, &link, client, ShareDenial::PasswordRequired).await);
+        };
+        if !password::compare(password, password_hash).unwrap_or(false) {
+            return Err(deny(app_state, &link, client, ShareDenial::WrongPassword).await);
+        }
+    }
+
+    // Counting the view re-checks the limits, two viewers can't both take the last view
+    let Some(link) = app_state
+        .db_client
+        .record_share_view(link.id)
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?
+    else {
+        return Err(deny(app_state, &link, client, ShareDenial::ViewsExhausted).await);
+    };
+
+    let resume = app_state
+        .db_client
+        .get_resume(Some(link.user_id), Some(link.resume_id))
+        .await
+        .map_err(|e| HttpError::server_error(e.to_string()))?
+        .ok_or_else(|| ShareDenial::Revoked.error())?;
+
+    log_access(app_state, &link, client, None).await;
+
+    let resume = if link.redacted { redact_resume(&resume) } else { resume };
+    Ok((link, resume))
+}
@@ -0,0 +1,85 @@
+use std::path::Path;
+
+use sha2::{Digest, Sha256};
+use tokio::fs;
+
+use crate::{services::database::UserActions, utils::error::HttpError, AppState};
+
+const UPLOAD_DIR: &str = "./uploads/files";
+
+#[derive(Debug, Clone)]
+pub struct StoredFile {
+    pub file_path: String,
+    pub content_hash: String,
+    /// False when identical content was already on disk
+    pub created: bool,
+}
+
+pub fn content_hash(data: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(data))
+}
+
+/// Writes an upload under its SHA-256, so identical files are stored once whatever they're called.
+/// The extension is kept since analyzers and extraction go by it.
+pub async fn store_upload(file_name: &str, data: &[u8]) -> Result<StoredFile, HttpError> {
+    fs::create_dir_all(UPLOAD_DIR).await.map_err(|e| HttpError::server_error(e.to_string()))?;
+
+    let content_hash = content_hash(data);
+    let extension = Path::new(file_name)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(|extension| format!(".{}", extension.to_lowercase()))
+        .unwrap_or_default();
+    let file_path = format!("{}/{}{}", UPLOAD_DIR, content_hash, extension);
+
+    let created = !fs::try_exists(&file_path).await.unwrap_or(false);
+    if created {
+        fs::write(&file_path, data).await.map_err(|e| HttpError::server_error(e.to_string()))?;
+    }
+
+    Ok(StoredFile {
+        file_path,
+        content_hash,
+        created,
+    })
+}
+
+/// Writes the file again when a purge deleted it between `store_upload` and the resume row that
+/// points at it being committed. Once the row is committed, purges see it and keep the file.
+pub async fn restore_if_missing(file_path: &str, data: &[u8]) -> Result<(), HttpError> {
+    if fs::try_exists(file_path).await.unwrap_or(false) {
+        return Ok(());
+    }
+
+    tracing::info!("{} was deleted while being uploaded again, writing it back", file_path);
+    fs::write(file_path, data).await.map_err(|e| HttpError::server_error(e.to_string()))
+}
+
+/// Deletes a stored file once no resume points at it anymore.
+pub async fn release_file(app_state: &AppState, file_path: &str) {
+    // Stored files are named after their content hash
+    let Some(content_hash) = Path::new(file_path).file_stem().and_then(|stem| stem.to_str()) else {
+        tracing::warn!("Could not tell the content hash of {}, keeping it", file_path);
+        return;
+    };
+
+    // Held until the file is gone, so an upload of the same content can't insert its resume
+    // between the count and the delete
+    let _lock = match app_state.db_client.lock_content(content_hash).await {
+        Ok(lock) => lock,
+        Err(e) => {
+            tracing::warn!("Could not lock {} for deletion: {}", file_path, e);
+            return;
+        }
+    };
+
+    match app_state.db_client.count_file_references(file_path).await {
+        Ok(0) => {
+            if let Err(e) = fs::remove_file(file_path).await {
+                tracing::warn!("Could not delete file {}: {}", file_path, e);
+            }
+        }
+        Ok(_) => tracing::debug!("File {} is still referenced, keeping it", file_path),
+        Err(e) => tracing::warn!("Could not check references to {}: {}", file_path, e),
+    }
+}
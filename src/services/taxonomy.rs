@@ -0,0 +1,326 @@
+use std::{collections::{HashMap, HashSet}, sync::{Arc, RwLock}};
+
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::models::skill::{Skill, SkillAlias};
+
+use super::database::{DBClient, TaxonomyActions};
+
+/// Trailing words that don't change which skill is meant, e.g. "Python programming".
+const FILLER_SUFFIXES: &[&str] = &["programming", "language", "framework", "development", "database", "developer"];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CanonicalSkill {
+    pub id: Uuid,
+    pub name: String,
+    pub category: String,
+    pub parent: Option<String>,
+    pub aliases: Vec<String>,
+}
+
+/// In-memory snapshot of the `skills` and `skill_aliases` tables used to normalize skill names.
+#[derive(Debug, Default)]
+pub struct SkillTaxonomy {
+    skills: Vec<CanonicalSkill>,
+    by_key: HashMap<String, usize>,
+    by_compact_key: HashMap<String, usize>,
+}
+
+/// Lowercases, trims and collapses whitespace, keeping `.`, `+` and `#` which matter in names like ".NET" or "C#".
+pub fn normalize_key(raw: &str) -> String {
+    raw.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+        .trim_matches(|c: char| matches!(c, ',' | ';' | ':' | '*' | '-' | '(' | ')'))
+        .trim()
+        .to_string()
+}
+
+/// The key with separators removed, so "React JS", "react-js" and "react.js" all compare equal.
+fn compact_key(key: &str) -> String {
+    key.chars()
+        .filter(|c| c.is_alphanumeric() || *c == '+' || *c == '#')
+        .collect()
+}
+
+fn is_version(token: &str) -> bool {
+    let digits = token
+        .strip_prefix('v')
+        .or_else(|| token.strip_prefix("es"))
+        .unwrap_or(token);
+    !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit() || c == '.' || c == 'x')
+        && digits.chars().any(|c| c.is_ascii_digit())
+}
+
+impl SkillTaxonomy {
+    pub fn new(skills: Vec<Skill>, aliases: Vec<SkillAlias>) -> Self {
+        let names: HashMap<Uuid, String> = skills.iter().map(|skill| (skill.id, skill.name.clone())).collect();
+        let mut alias_map: HashMap<Uuid, Vec<String>> = HashMap::new();
+        for alias in aliases {
+            alias_map.entry(alias.skill_id).or_default().push(alias.alias);
+        }
+
+        let mut skills: Vec<CanonicalSkill> = skills
+            .into_iter()
+            .map(|skill| {
+                let mut aliases = alias_map.remove(&skill.id).unwrap_or_default();
+                aliases.sort();
+                CanonicalSkill {
+                    id: skill.id,
+                    parent: skill.parent_id.and_then(|id| names.get(&id).cloned()),
+                    name: skill.name,
+                    category: skill.category,
+                    aliases,
+                }
+            })
+            .collect();
+        skills.sort_by_key(|skill| skill.name.to_lowercase());
+
+        let mut taxonomy = SkillTaxonomy {
+            skills,
+            ..Default::default()
+        };
+
+        // Canonical names win over aliases, and exact keys over compacted ones
+        for (index, skill) in taxonomy.skills.iter().enumerate() {
+            let key = normalize_key(&skill.name);
+            taxonomy.by_compact_key.entry(compact_key(&key)).or_insert(index);
+            taxonomy.by_key.insert(key, index);
+        }
+        for (index, skill) in taxonomy.skills.iter().enumerate() {
+            for alias in &skill.aliases {
+                let key = normalize_key(alias);
+                taxonomy.by_compact_key.entry(compact_key(&key)).or_insert(index);
+                taxonomy.by_key.entry(key).or_insert(index);
+            }
+        }
+
+        taxonomy
+    }
+
+    pub fn skills(&self) -> &[CanonicalSkill] {
+        &self.skills
+    }
+
+    fn find(&self, key: &str) -> Option<&CanonicalSkill> {
+        self.by_key
+            .get(key)
+            .or_else(|| self.by_compact_key.get(&compact_key(key)))
+            .map(|index| &self.skills[*index])
+    }
+
+    /// Finds the canonical skill for a raw name, ignoring trailing version numbers and filler words.
+    pub fn lookup(&self, raw: &str) -> Option<&CanonicalSkill> {
+        let key = normalize_key(raw);
+        if key.is_empty() {
+            return None;
+        }
+        if let Some(skill) = self.find(&key) {
+            return Some(skill);
+        }
+
+        let mut tokens: Vec<&str> = key.split(' ').collect();
+        while tokens.len() > 1 {
+            let last = tokens[tokens.len() - 1];
+            if !(is_version(last) || FILLER_SUFFIXES.contains(&last)) {
+                break;
+            }
+            tokens.pop();
+            if let Some(skill) = self.find(&tokens.join(" ")) {
+                return Some(skill);
+            }
+        }
+
+        None
+    }
+
+    /// The canonical spelling of a skill, or the trimmed input when the taxonomy doesn't know it.
+    pub fn canonical_name(&self, raw: &str) -> String {
+        match self.lookup(raw) {
+            Some(skill) => skill.name.clone(),
+            None => raw.split_whitespace().collect::<Vec<_>>().join(" "),
+        }
+    }
+
+    /// Maps every skill to its canonical name and drops blanks and duplicates, keeping the original order.
+    pub fn normalize(&self, skills: &[String]) -> Vec<String> {
+        let mut seen = HashSet::new();
+        skills
+            .iter()
+            .map(|skill| self.canonical_name(skill))
+            .filter(|skill| !skill.is_empty() && seen.insert(skill.to_lowercase()))
+            .collect()
+    }
+
+    /// Parent, grandparent, ... of a skill; someone who knows React also knows JavaScript.
+    pub fn ancestors(&self, raw: &str) -> Vec<&CanonicalSkill> {
+        let mut ancestors: Vec<&CanonicalSkill> = Vec::new();
+        let mut current = self.lookup(raw);
+        while let Some(parent) = current
+            .and_then(|skill| skill.parent.as_deref())
+            .and_then(|parent| self.find(&normalize_key(parent)))
+        {
+            // Guards against cycles an admin could create
+            if ancestors.iter().any(|skill| skill.id == parent.id) {
+                break;
+            }
+            ancestors.push(parent);
+            current = Some(parent);
+        }
+        ancestors
+    }
+
+    /// Skills whose name or one of whose aliases starts with `prefix`, name matches first.
+    pub fn autocomplete(&self, prefix: &str, limit: usize) -> Vec<&CanonicalSkill> {
+        let prefix = normalize_key(prefix);
+        if prefix.is_empty() {
+            return Vec::new();
+        }
+
+        let mut matches: Vec<(u8, &CanonicalSkill)> = self
+            .skills
+            .iter()
+            .filter_map(|skill| {
+                let name = normalize_key(&skill.name);
+                if name == prefix {
+                    Some((0, skill))
+                } else if name.starts_with(&prefix) {
+                    Some((1, skill))
+                } else if skill.aliases.iter().any(|alias| alias.starts_with(&prefix)) {
+                    Some((2, skill))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        matches.sort_by_key(|(priority, _)| *priority);
+
+        matches.into_iter().take(limit).map(|(_, skill)| skill).collect()
+    }
+}
+
+/// Shares the current taxonomy and swaps in a fresh snapshot after admin edits.
+#[derive(Debug, Default)]
+pub struct TaxonomyStore {
+    current: RwLock<Arc<SkillTaxonomy>>,
+}
+
+impl TaxonomyStore {
+    pub fn get(&self) -> Arc<SkillTaxonomy> {
+        self.current.read().unwrap().clone()
+    }
+
+    pub async fn reload(&self, db_client: &DBClient) -> Result<(), sqlx::Error> {
+        let skills = db_client.get_skills().await?;
+        let aliases = db_client.get_skill_aliases().await?;
+        let taxonomy = SkillTaxonomy::new(skills, aliases);
+        tracing::debug!("Loaded skill taxonomy with {} skills", taxonomy.skills().len());
+        *self.current.write().unwrap() = Arc::new(taxonomy);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn skill(name: &str, parent_id: Option<Uuid>) -> Skill {
+        Skill {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            category: "language".to_string(),
+            parent_id,
+            created_at: None,
+        }
+    }
+
+    fn alias(alias: &str, skill: &Skill) -> SkillAlias {
+        SkillAlias {
+            alias: alias.to_string(),
+            skill_id: skill.id,
+            created_at: None,
+        }
+    }
+
+    fn taxonomy() -> SkillTaxonomy {
+        let javascript = skill("JavaScript", None);
+        let react = skill("React", Some(javascript.id));
+        let java = skill("Java", None);
+        let aliases = vec![alias("js", &javascript), alias("java", &javascript), alias("react.js", &react)];
+        SkillTaxonomy::new(vec![javascript, react, java, skill("Python", None), skill("C#", None)], aliases)
+    }
+
+    fn names(skills: Vec<&CanonicalSkill>) -> Vec<&str> {
+        skills.into_iter().map(|skill| skill.name.as_str()).collect()
+    }
+
+    #[test]
+    fn normalizes_keys() {
+        assert_eq!(normalize_key("  Node.js ,"), "node.js");
+        assert_eq!(normalize_key("C#"), "c#");
+        assert_eq!(normalize_key("- Machine   Learning:"), "machine learning");
+        assert_eq!(normalize_key("  "), "");
+    }
+
+    #[test]
+    fn looks_up_aliases_and_spelling_variants() {
+        let taxonomy = taxonomy();
+        let name = |raw: &str| taxonomy.lookup(raw).map(|skill| skill.name.clone());
+
+        assert_eq!(name("JS").as_deref(), Some("JavaScript"));
+        assert_eq!(name("React JS").as_deref(), Some("React"));
+        assert_eq!(name("react-js").as_deref(), Some("React"));
+        assert_eq!(name("c#").as_deref(), Some("C#"));
+        assert_eq!(name("Rust"), None);
+        assert_eq!(name(""), None);
+    }
+
+    #[test]
+    fn prefers_canonical_names_over_aliases() {
+        assert_eq!(taxonomy().lookup("java").unwrap().name, "Java");
+    }
+
+    #[test]
+    fn ignores_versions_and_filler_words() {
+        let taxonomy = taxonomy();
+        let name = |raw: &str| taxonomy.lookup(raw).map(|skill| skill.name.clone());
+
+        assert_eq!(name("Python 3.11").as_deref(), Some("Python"));
+        assert_eq!(name("Python programming language").as_deref(), Some("Python"));
+        assert_eq!(name("JavaScript ES6").as_deref(), Some("JavaScript"));
+        assert_eq!(name("Python Django"), None);
+    }
+
+    #[test]
+    fn normalizes_skill_lists() {
+        let skills = ["js", "JavaScript", " ", "Rust   lang", "rust lang"].map(String::from);
+
+        assert_eq!(taxonomy().normalize(&skills), vec!["JavaScript", "Rust lang"]);
+        assert!(taxonomy().normalize(&[]).is_empty());
+    }
+
+    #[test]
+    fn walks_up_parents_and_stops_at_cycles() {
+        assert_eq!(names(taxonomy().ancestors("react.js")), vec!["JavaScript"]);
+        assert!(taxonomy().ancestors("Python").is_empty());
+
+        let mut first = skill("First", None);
+        let second = skill("Second", Some(first.id));
+        first.parent_id = Some(second.id);
+        let cyclic = SkillTaxonomy::new(vec![first, second], Vec::new());
+        assert_eq!(names(cyclic.ancestors("First")), vec!["Second", "First"]);
+    }
+
+    #[test]
+    fn autocompletes_exact_names_first() {
+        let taxonomy = taxonomy();
+
+        assert_eq!(names(taxonomy.autocomplete("Java", 10)), vec!["Java", "JavaScript"]);
+        assert_eq!(names(taxonomy.autocomplete("rea", 10)), vec!["React"]);
+        assert_eq!(names(taxonomy.autocomplete("js", 10)), vec!["JavaScript"]);
+        assert_eq!(names(taxonomy.autocomplete("java", 1)), vec!["Java"]);
+        assert!(taxonomy.autocomplete(" ", 10).is_empty());
+    }
+}
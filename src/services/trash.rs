@@ -0,0 +1,54 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::{models::resume::Resume, services::{database::TrashActions, storage::release_file}, AppState};
+
+const PURGE_BATCH_SIZE: i64 = 100;
+
+/// When a trashed resume is due to be purged.
+pub fn purge_at(app_state: &AppState, resume: &Resume) -> Option<DateTime<Utc>> {
+    resume
+        .deleted_at
+        .map(|deleted_at| deleted_at + chrono::Duration::days(app_state.env.trash_retention_days))
+}
+
+/// Deletes a trashed resume for good, its file goes too once nothing else points at it.
+pub async fn purge_resume(app_state: &AppState, user_id: Option<Uuid>, resume_id: Uuid) -> Result<bool, sqlx::Error> {
+    let Some(file_path) = app_state.db_client.purge_resume(user_id, resume_id).await? else {
+        return Ok(false);
+    };
+
+    // Anonymized resumes have no file left
+    if let Some(file_path) = file_path {
+        release_file(app_state, &file_path).await;
+    }
+    Ok(true)
+}
+
+/// Purges resumes that have been in the trash longer than the retention period.
+pub async fn purge_expired_trash(app_state: Arc<AppState>) -> Result<u64, sqlx::Error> {
+    let deleted_before = Utc::now() - chrono::Duration::days(app_state.env.trash_retention_days);
+    let mut total = 0;
+
+    loop {
+        let resumes = app_state.db_client.get_expired_trash(deleted_before, PURGE_BATCH_SIZE).await?;
+        let full_batch = resumes.len() as i64 == PURGE_BATCH_SIZE;
+        let mut purged = 0;
+
+        for resume in resumes {
+            match purge_resume(&app_state, None, resume.id).await {
+                Ok(true) => purged += 1,
+                Ok(false) => {}
+                Err(e) => tracing::warn!("Could not purge resume {}: {}", resume.id, e),
+            }
+        }
+        total += purged;
+
+        // Stop on a failing batch rather than spinning on the same rows
+        if !full_batch || purged == 0 {
+            return Ok(total);
+        }
+    }
+}
@@ -1,8 +1,9 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 use validator::{Validate, ValidationError};
 
-use crate::models::{resume::Resume, users::{User, UserRole}};
+use crate::{models::{analysis::ResumeAnalysis, ats::AtsReport, batch::{BatchFileResult, BatchProgress, ResumeBatch}, job::{JobDescription, Seniority}, analysis_result::AnalysisResult, resume::{Resume, ResumeDocument}, pipeline::{PipelineStage, StageTransition}, quota::{Quota, QuotaUsage}, retention::{RetentionAction, RetentionCandidate, RetentionPolicy, RetentionSummary}, search::SearchMode, share::{ShareAccess, ShareLink}, tag::{ResumeNote, ResumeTag, Tag}, users::{User, UserRole}}, services::{circuit_breaker::CircuitStatus, diff::ResumeDiff, export::ExportFormat, matching::MatchReport, taxonomy::CanonicalSkill}};
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default, Validate)]
 pub struct RegisterUserDto {
@@ -90,6 +91,7 @@ pub struct UserResponseDto {
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ResumeData {
     pub resume: FilterResumeDto,
+    pub tags: Vec<FilterResumeTagDto>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -98,13 +100,27 @@ pub struct ResumeResponseDto {
     pub data: ResumeData,
 }
 
+/// Upgrades a stored analysis to the current schema so clients only ever see one format.
 #[derive(Serialize, Deserialize, Debug)]
 pub struct FilterResumeDto {
     pub id: String,
     pub user_id: String,
-    pub file_path: String,
-    pub analysis_result: Option<serde_json::Value>,
+    pub file_path: Option<String>,
+    pub analysis_result: Option<AnalysisResult>,
+    pub latest_analysis_id: Option<String>,
+    pub analysis_status: String,
+    pub document_format: Option<String>,
+    pub page_count: Option<i32>,
+    pub word_count: Option<i32>,
+    pub language: Option<String>,
+    pub ats_report: Option<AtsReport>,
+    pub document_id: String,
+    pub version: i32,
+    pub stage_id: String,
+    pub stage_changed_at: Option<DateTime<Utc>>,
     pub uploaded_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub anonymized_at: Option<DateTime<Utc>>,
 }
 
 impl FilterResumeDto {
@@ -112,9 +128,22 @@ impl FilterResumeDto {
         FilterResumeDto {
             id: resume.id.to_string(),
             user_id: resume.user_id.to_string(),
-            file_path: resume.file_path.to_owned(),
-            analysis_result: resume.analysis_result.clone(),
+            file_path: resume.file_path.clone(),
+            analysis_result: resume.analysis(),
+            latest_analysis_id: resume.latest_analysis_id.map(|id| id.to_string()),
+            analysis_status: resume.analysis_status.to_str().to_string(),
+            document_format: resume.document_format.clone(),
+            page_count: resume.page_count,
+            word_count: resume.word_count,
+            language: resume.language.clone(),
+            ats_report: resume.ats(),
+            document_id: resume.document_id.to_string(),
+            version: resume.version,
+            stage_id: resume.stage_id.to_string(),
+            stage_changed_at: resume.stage_changed_at,
             uploaded_at: resume.uploaded_at.unwrap(),
+            deleted_at: resume.deleted_at,
+            anonymized_at: resume.anonymized_at,
         }
     }
 
@@ -124,12 +153,803 @@ impl FilterResumeDto {
 }
 
 #[derive(Serialize, Deserialize, Debug)]
-pub struct ResumeListResponseDto {
+pub struct ResumeTextData {
+    pub id: String,
+    pub document_format: Option<String>,
+    pub text: Option<String>,
+    pub page_count: Option<i32>,
+    pub word_count: Option<i32>,
+    pub language: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ResumeTextResponseDto {
+    pub status: String,
+    pub data: ResumeTextData,
+}
+
+#[derive(Serialize, Deserialize, Validate, Debug)]
+pub struct ResumeSearchQueryDto {
+    #[validate(length(min = 1, max = 200, message = "Search query must be between 1 and 200 characters"))]
+    pub q: String,
+    pub mode: Option<SearchMode>,
+    /// Comma separated, every listed skill must be present
+    pub skills: Option<String>,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    #[validate(range(min = 1))]
+    pub page: Option<usize>,
+    #[validate(range(min = 1, max = 50))]
+    pub limit: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ResumeSearchHitDto {
+    pub resume: FilterResumeDto,
+    pub rank: f32,
+    pub snippet: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ResumeSearchResponseDto {
+    pub status: String,
+    pub hits: Vec<ResumeSearchHitDto>,
+    pub results: i64,
+}
+
+#[derive(Serialize, Deserialize, Validate, Debug)]
+pub struct SimilarResumesQueryDto {
+    #[validate(range(min = 1, max = 50))]
+    pub limit: Option<usize>,
+    #[validate(range(min = -1.0, max = 1.0))]
+    pub min_score: Option<f32>,
+}
+
+#[derive(Serialize, Deserialize, Validate, Debug)]
+pub struct SimilarTextRequestDto {
+    #[validate(length(min = 1, max = 20000, message = "Text must be between 1 and 20000 characters"))]
+    pub text: String,
+    #[validate(range(min = 1, max = 50))]
+    pub limit: Option<usize>,
+    #[validate(range(min = -1.0, max = 1.0))]
+    pub min_score: Option<f32>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SimilarResumeDto {
+    pub resume: FilterResumeDto,
+    pub score: f32,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SimilarResumesResponseDto {
+    pub status: String,
+    pub resumes: Vec<SimilarResumeDto>,
+    pub results: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TrashedResumeDto {
+    pub resume: FilterResumeDto,
+    /// When the resume and its file are deleted for good
+    pub purge_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TrashListResponseDto {
+    pub status: String,
+    pub resumes: Vec<TrashedResumeDto>,
+    pub results: i64,
+    pub total: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ResumeExportQueryDto {
+    /// `csv` (default) or `ndjson`
+    pub format: Option<ExportFormat>,
+    /// Comma separated tag names, every listed tag must be on the current version
+    pub tags: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ResumeViewQueryDto {
+    /// Anonymize resumes for blind review
+    pub blind: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UploadResumeQueryDto {
+    /// Upload as a new version of this document instead of starting a new one
+    pub document_id: Option<Uuid>,
+    /// Call the analyzer even if identical content was analyzed before
+    pub force_analysis: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SetCurrentVersionDto {
+    pub resume_id: Uuid,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FilterResumeDocumentDto {
+    pub id: String,
+    pub title: String,
+    pub current_resume_id: Option<String>,
+    pub version_count: i64,
+    pub latest_version: i32,
+    pub current: Option<FilterResumeDto>,
+    /// Tags of the current version
+    pub tags: Vec<FilterResumeTagDto>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl FilterResumeDocumentDto {
+    pub fn filter_document(document: &ResumeDocument, current: Option<&Resume>, tags: &[ResumeTag]) -> Self {
+        FilterResumeDocumentDto {
+            id: document.id.to_string(),
+            title: document.title.to_owned(),
+            current_resume_id: document.current_resume_id.map(|id| id.to_string()),
+            version_count: document.version_count,
+            latest_version: document.latest_version,
+            current: current.map(FilterResumeDto::filter_resume),
+            tags: current
+                .map(|resume| FilterResumeTagDto::filter_tags_for(tags, resume.id))
+                .unwrap_or_default(),
+            created_at: document.created_at.unwrap(),
+            updated_at: document.updated_at.unwrap(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ResumeDocumentListResponseDto {
+    pub status: String,
+    pub documents: Vec<FilterResumeDocumentDto>,
+    pub results: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ResumeDocumentData {
+    pub document: FilterResumeDocumentDto,
+    pub versions: Vec<FilterResumeDto>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ResumeDocumentResponseDto {
+    pub status: String,
+    pub data: ResumeDocumentData,
+}
+
+#[derive(Serialize, Deserialize, Validate)]
+pub struct ResumeListQueryDto {
+    #[validate(range(min = 1))]
+    pub page: Option<usize>,
+    #[validate(range(min = 1, max = 50))]
+    pub limit: Option<usize>,
+    /// Comma separated tag names, every listed tag must be on the current version
+    pub tags: Option<String>,
+}
+
+fn validate_colour(colour: &str) -> Result<(), ValidationError> {
+    let hex = colour.strip_prefix('#').unwrap_or_default();
+    if hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        Ok(())
+    } else {
+        let mut error = ValidationError::new("invalid_colour");
+        error.message = Some("Colour must be a hex value such as #22c55e".into());
+        Err(error)
+    }
+}
+
+#[derive(Serialize, Deserialize, Validate, Debug)]
+pub struct CreateTagDto {
+    #[validate(length(min = 1, max = 50, message = "Tag name must be between 1 and 50 characters"))]
+    pub name: String,
+    #[validate(custom = "validate_colour")]
+    pub colour: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Validate, Debug)]
+pub struct UpdateTagDto {
+    #[validate(length(min = 1, max = 50, message = "Tag name must be between 1 and 50 characters"))]
+    pub name: Option<String>,
+    #[validate(custom = "validate_colour")]
+    pub colour: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct FilterTagDto {
+    pub id: String,
+    pub name: String,
+    pub colour: String,
+    pub resume_count: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl FilterTagDto {
+    pub fn filter_tag(tag: &Tag) -> Self {
+        FilterTagDto {
+            id: tag.id.to_string(),
+            name: tag.name.to_owned(),
+            colour: tag.colour.to_owned(),
+            resume_count: tag.resume_count,
+            created_at: tag.created_at.unwrap(),
+            updated_at: tag.updated_at.unwrap(),
+        }
+    }
+
+    pub fn filter_tags(tags: &[Tag]) -> Vec<Self> {
+        tags.iter().map(FilterTagDto::filter_tag).collect()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct FilterResumeTagDto {
+    pub id: String,
+    pub name: String,
+    pub colour: String,
+    pub tagged_at: Option<DateTime<Utc>>,
+}
+
+impl FilterResumeTagDto {
+    pub fn filter_tag(tag: &ResumeTag) -> Self {
+        FilterResumeTagDto {
+            id: tag.id.to_string(),
+            name: tag.name.to_owned(),
+            colour: tag.colour.to_owned(),
+            tagged_at: tag.tagged_at,
+        }
+    }
+
+    /// Picks the tags of one resume out of a bulk lookup.
+    pub fn filter_tags_for(tags: &[ResumeTag], resume_id: Uuid) -> Vec<Self> {
+        tags.iter()
+            .filter(|tag| tag.resume_id == resume_id)
+            .map(FilterResumeTagDto::filter_tag)
+            .collect()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TagData {
+    pub tag: FilterTagDto,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TagResponseDto {
+    pub status: String,
+    pub data: TagData,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TagListResponseDto {
+    pub status: String,
+    pub tags: Vec<FilterTagDto>,
+    pub results: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ResumeTagListResponseDto {
+    pub status: String,
+    pub tags: Vec<FilterResumeTagDto>,
+    pub results: i64,
+}
+
+#[derive(Serialize, Deserialize, Validate, Debug)]
+pub struct BulkTagRequestDto {
+    #[validate(length(min = 1, max = 500, message = "Between 1 and 500 resumes can be tagged at once"))]
+    pub resume_ids: Vec<Uuid>,
+    #[serde(default)]
+    #[validate(length(max = 50))]
+    pub add: Vec<Uuid>,
+    #[serde(default)]
+    #[validate(length(max = 50))]
+    pub remove: Vec<Uuid>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BulkTagData {
+    pub added: u64,
+    pub removed: u64,
+    /// Requested resumes that don't exist or belong to someone else
+    pub missing_resume_ids: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BulkTagResponseDto {
+    pub status: String,
+    pub data: BulkTagData,
+}
+
+#[derive(Serialize, Deserialize, Validate, Debug)]
+pub struct NoteRequestDto {
+    #[validate(length(min = 1, max = 10000, message = "Note must be between 1 and 10000 characters"))]
+    pub body: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct FilterNoteDto {
+    pub id: String,
+    pub resume_id: String,
+    pub author_id: String,
+    pub author_name: String,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl FilterNoteDto {
+    pub fn filter_note(note: &ResumeNote) -> Self {
+        FilterNoteDto {
+            id: note.id.to_string(),
+            resume_id: note.resume_id.to_string(),
+            author_id: note.author_id.to_string(),
+            author_name: note.author_name.to_owned(),
+            body: note.body.to_owned(),
+            created_at: note.created_at.unwrap(),
+            updated_at: note.updated_at.unwrap(),
+        }
+    }
+
+    pub fn filter_notes(notes: &[ResumeNote]) -> Vec<Self> {
+        notes.iter().map(FilterNoteDto::filter_note).collect()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct NoteData {
+    pub note: FilterNoteDto,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct NoteResponseDto {
+    pub status: String,
+    pub data: NoteData,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct NoteListResponseDto {
+    pub status: String,
+    pub notes: Vec<FilterNoteDto>,
+    pub results: i64,
+}
+
+#[derive(Serialize, Deserialize, Validate, Debug)]
+pub struct CreateStageDto {
+    #[validate(length(min = 1, max = 50, message = "Stage name must be between 1 and 50 characters"))]
+    pub name: String,
+    #[validate(range(min = 0))]
+    pub position: Option<i32>,
+    pub terminal: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Validate, Debug)]
+pub struct UpdateStageDto {
+    #[validate(length(min = 1, max = 50, message = "Stage name must be between 1 and 50 characters"))]
+    pub name: Option<String>,
+    #[validate(range(min = 0))]
+    pub position: Option<i32>,
+    pub terminal: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct FilterStageDto {
+    pub id: String,
+    pub name: String,
+    pub position: i32,
+    pub terminal: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl FilterStageDto {
+    pub fn filter_stage(stage: &PipelineStage) -> Self {
+        FilterStageDto {
+            id: stage.id.to_string(),
+            name: stage.name.to_owned(),
+            position: stage.position,
+            terminal: stage.terminal,
+            created_at: stage.created_at.unwrap(),
+            updated_at: stage.updated_at.unwrap(),
+        }
+    }
+
+    pub fn filter_stages(stages: &[PipelineStage]) -> Vec<Self> {
+        stages.iter().map(FilterStageDto::filter_stage).collect()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct StageData {
+    pub stage: FilterStageDto,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct StageResponseDto {
+    pub status: String,
+    pub data: StageData,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct StageListResponseDto {
+    pub status: String,
+    pub stages: Vec<FilterStageDto>,
+    pub results: i64,
+}
+
+#[derive(Serialize, Deserialize, Validate, Debug)]
+pub struct MoveStageDto {
+    pub stage_id: Uuid,
+    #[validate(length(max = 2000, message = "Note must be at most 2000 characters"))]
+    pub note: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct FilterStageTransitionDto {
+    pub id: String,
+    pub resume_id: String,
+    pub version: i32,
+    pub from_stage_id: Option<String>,
+    pub from_stage: Option<String>,
+    pub to_stage_id: Option<String>,
+    pub to_stage: String,
+    pub changed_by: Option<String>,
+    pub changed_by_name: Option<String>,
+    pub note: Option<String>,
+    pub changed_at: DateTime<Utc>,
+}
+
+impl FilterStageTransitionDto {
+    pub fn filter_transition(transition: &StageTransition) -> Self {
+        FilterStageTransitionDto {
+            id: transition.id.to_string(),
+            resume_id: transition.resume_id.to_string(),
+            version: transition.version,
+            from_stage_id: transition.from_stage_id.map(|id| id.to_string()),
+            from_stage: transition.from_stage.clone(),
+            to_stage_id: transition.to_stage_id.map(|id| id.to_string()),
+            to_stage: transition.to_stage.to_owned(),
+            changed_by: transition.changed_by.map(|id| id.to_string()),
+            changed_by_name: transition.changed_by_name.clone(),
+            note: transition.note.clone(),
+            changed_at: transition.changed_at.unwrap(),
+        }
+    }
+
+    pub fn filter_transitions(transitions: &[StageTransition]) -> Vec<Self> {
+        transitions.iter().map(FilterStageTransitionDto::filter_transition).collect()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct StageTransitionData {
+    pub transition: FilterStageTransitionDto,
+    pub resume: FilterResumeDto,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct StageTransitionResponseDto {
+    pub status: String,
+    pub data: StageTransitionData,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct StageTimelineResponseDto {
+    pub status: String,
+    pub timeline: Vec<FilterStageTransitionDto>,
+    pub results: i64,
+}
+
+#[derive(Serialize, Deserialize, Validate)]
+pub struct PipelineQueryDto {
+    /// Resumes shown per stage, `count` always has the full number
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<usize>,
+    /// Comma separated tag names, every listed tag must be on the current version
+    pub tags: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PipelineCardDto {
+    pub resume: FilterResumeDto,
+    pub tags: Vec<FilterResumeTagDto>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PipelineColumnDto {
+    pub stage: FilterStageDto,
+    pub count: i64,
+    pub resumes: Vec<PipelineCardDto>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PipelineResponseDto {
+    pub status: String,
+    pub stages: Vec<PipelineColumnDto>,
+    pub results: i64,
+}
+
+#[derive(Serialize, Deserialize, Validate, Debug)]
+pub struct CreateShareLinkDto {
+    #[validate(range(min = 1, message = "Expiry must be at least one hour"))]
+    pub expires_in_hours: Option<i64>,
+    #[validate(length(min = 6, max = 64, message = "Password must be between 6 and 64 characters"))]
+    pub password: Option<String>,
+    #[validate(range(min = 1, max = 10000))]
+    pub max_views: Option<i32>,
+    /// Serve the blind review view instead of the full resume
+    pub redacted: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct FilterShareLinkDto {
+    pub id: String,
+    pub resume_id: String,
+    pub token_prefix: String,
+    pub password_protected: bool,
+    pub redacted: bool,
+    pub expires_at: DateTime<Utc>,
+    pub max_views: Option<i32>,
+    pub view_count: i32,
+    pub last_viewed_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl FilterShareLinkDto {
+    pub fn filter_share_link(link: &ShareLink) -> Self {
+        FilterShareLinkDto {
+            id: link.id.to_string(),
+            resume_id: link.resume_id.to_string(),
+            token_prefix: link.token_prefix.to_owned(),
+            password_protected: link.password_hash.is_some(),
+            redacted: link.redacted,
+            expires_at: link.expires_at,
+            max_views: link.max_views,
+            view_count: link.view_count,
+            last_viewed_at: link.last_viewed_at,
+            revoked_at: link.revoked_at,
+            active: link.is_active(),
+            created_at: link.created_at.unwrap(),
+        }
+    }
+
+    pub fn filter_share_links(links: &[ShareLink]) -> Vec<Self> {
+        links.iter().map(FilterShareLinkDto::filter_share_link).collect()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ShareLinkCreatedData {
+    pub share: FilterShareLinkDto,
+    /// Only returned here, the server keeps a hash
+    pub token: String,
+    pub path: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ShareLinkCreatedResponseDto {
+    pub status: String,
+    pub data: ShareLinkCreatedData,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ShareLinkData {
+    pub share: FilterShareLinkDto,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ShareLinkResponseDto {
+    pub status: String,
+    pub data: ShareLinkData,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ShareLinkListResponseDto {
+    pub status: String,
+    pub shares: Vec<FilterShareLinkDto>,
+    pub results: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct FilterShareAccessDto {
+    pub id: String,
+    pub granted: bool,
+    pub reason: Option<String>,
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+    pub accessed_at: DateTime<Utc>,
+}
+
+impl FilterShareAccessDto {
+    pub fn filter_access(access: &ShareAccess) -> Self {
+        FilterShareAccessDto {
+            id: access.id.to_string(),
+            granted: access.granted,
+            reason: access.reason.clone(),
+            ip: access.ip.clone(),
+            user_agent: access.user_agent.clone(),
+            accessed_at: access.accessed_at.unwrap(),
+        }
+    }
+
+    pub fn filter_access_log(access: &[ShareAccess]) -> Vec<Self> {
+        access.iter().map(FilterShareAccessDto::filter_access).collect()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ShareAccessListResponseDto {
+    pub status: String,
+    pub access: Vec<FilterShareAccessDto>,
+    pub results: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct SharePasswordDto {
+    pub password: Option<String>,
+}
+
+/// Read-only resume for a share link, without storage paths or account ids.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SharedResumeDto {
+    pub file_name: String,
+    pub document_format: Option<String>,
+    pub page_count: Option<i32>,
+    pub word_count: Option<i32>,
+    pub language: Option<String>,
+    pub text: Option<String>,
+    pub analysis_result: Option<AnalysisResult>,
+    pub ats_report: Option<AtsReport>,
+    pub version: i32,
+    pub uploaded_at: DateTime<Utc>,
+}
+
+impl SharedResumeDto {
+    pub fn filter_shared_resume(resume: &Resume) -> Self {
+        SharedResumeDto {
+            file_name: resume.original_file_name(),
+            document_format: resume.document_format.clone(),
+            page_count: resume.page_count,
+            word_count: resume.word_count,
+            language: resume.language.clone(),
+            text: resume.extracted_text.clone(),
+            analysis_result: resume.analysis(),
+            ats_report: resume.ats(),
+            version: resume.version,
+            uploaded_at: resume.uploaded_at.unwrap(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SharedResumeData {
+    pub resume: SharedResumeDto,
+    pub expires_at: DateTime<Utc>,
+    pub views_remaining: Option<i32>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SharedResumeResponseDto {
+    pub status: String,
+    pub data: SharedResumeData,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FilterBatchDto {
+    pub id: String,
+    pub file_count: i32,
+    pub created_count: i32,
+    pub failed_count: i32,
+    pub results: Vec<BatchFileResult>,
+    /// Analysis status of the created resumes
+    pub progress: BatchProgress,
+    pub done: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl FilterBatchDto {
+    pub fn filter_batch(batch: &ResumeBatch, progress: BatchProgress) -> Self {
+        FilterBatchDto {
+            id: batch.id.to_string(),
+            file_count: batch.file_count,
+            created_count: batch.file_count - batch.failed_count,
+            failed_count: batch.failed_count,
+            results: batch.results(),
+            done: progress.pending == 0,
+            progress,
+            created_at: batch.created_at.unwrap(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BatchData {
+    pub batch: FilterBatchDto,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BatchResponseDto {
+    pub status: String,
+    pub data: BatchData,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct FilterAnalysisDto {
+    pub id: String,
+    pub resume_id: String,
+    pub analysis_result: Option<AnalysisResult>,
+    pub model: String,
+    pub model_version: String,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: DateTime<Utc>,
+    pub duration_ms: i64,
+}
+
+impl FilterAnalysisDto {
+    pub fn filter_analysis(analysis: &ResumeAnalysis) -> Self {
+        FilterAnalysisDto {
+            id: analysis.id.to_string(),
+            resume_id: analysis.resume_id.to_string(),
+            analysis_result: analysis.analysis(),
+            model: analysis.model.to_owned(),
+            model_version: analysis.model_version.to_owned(),
+            started_at: analysis.started_at,
+            completed_at: analysis.completed_at,
+            duration_ms: analysis.duration_ms,
+        }
+    }
+
+    pub fn filter_analyses(analyses: &[ResumeAnalysis]) -> Vec<Self> {
+        analyses.iter().map(FilterAnalysisDto::filter_analysis).collect()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AnalysisData {
+    pub analysis: FilterAnalysisDto,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AnalysisResponseDto {
+    pub status: String,
+    pub data: AnalysisData,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AnalysisListResponseDto {
     pub status: String,
-    pub resumes: Vec<FilterResumeDto>,
+    pub analyses: Vec<FilterAnalysisDto>,
     pub results: i64,
 }
 
+#[derive(Serialize, Deserialize, Validate, Debug, Clone)]
+pub struct AnalyzeQueryDto {
+    #[validate(length(min = 1, max = 100, message = "Model must be between 1 and 100 characters"))]
+    pub model: Option<String>,
+    #[validate(length(min = 1, max = 50, message = "Model version must be between 1 and 50 characters"))]
+    pub model_version: Option<String>,
+    pub force: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Validate, Debug, Clone)]
+pub struct ReanalyzeRequestDto {
+    #[validate(length(min = 1, max = 100, message = "Model is required"))]
+    pub model: String,
+    #[validate(length(min = 1, max = 50, message = "Model version must be between 1 and 50 characters"))]
+    pub model_version: Option<String>,
+    pub force: Option<bool>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct HealthResponseDto {
+    pub status: String,
+    pub nlp: CircuitStatus,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct UserListResponseDto {
     pub status: String,
@@ -149,12 +969,6 @@ pub struct Response {
     pub message: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, Default, Validate)]
-pub struct NameUpdateDto {
-    #[validate(length(min = 1, message = "Name is required"))]
-    pub name: String,
-}
-
 #[derive(Debug, Serialize, Deserialize, Clone, Validate)]
 pub struct RoleUpdateDto {
     #[validate(custom = "validate_user_role")]
@@ -163,7 +977,7 @@ pub struct RoleUpdateDto {
 
 fn validate_user_role(role: &UserRole) -> Result<(), ValidationError> {
     match role {
-        UserRole::Admin | UserRole::User => Ok(()),
+        UserRole::Admin | UserRole::User | UserRole::Reviewer => Ok(()),
         // _ => Err(ValidationError::new("invalid_role")),
     }
 }
@@ -183,39 +997,455 @@ pub struct UserPassUpdateDto {
     pub old_password: String,
 }
 
-#[derive(Serialize, Deserialize, Validate)]
-pub struct VerifyEmailQueryDto {
-    #[validate(length(min = 1, message = "Token is required"))]
-    pub token: String,
+#[derive(Serialize, Deserialize, Validate, Debug)]
+pub struct JobDescriptionRequestDto {
+    #[validate(length(min = 1, max = 200, message = "Title must be between 1 and 200 characters"))]
+    pub title: String,
+    #[validate(length(min = 1, max = 20000, message = "Description must be between 1 and 20000 characters"))]
+    pub description: String,
+    #[serde(default)]
+    #[validate(length(max = 100))]
+    pub required_skills: Vec<String>,
+    #[serde(default)]
+    #[validate(length(max = 100))]
+    pub preferred_skills: Vec<String>,
+    pub seniority: Option<Seniority>,
+    #[validate(range(min = 0, max = 50))]
+    pub min_years_experience: Option<i32>,
 }
 
-#[derive(Serialize, Deserialize, Validate, Debug, Clone)]
-pub struct ForgotPasswordRequestDto {
-    #[validate(
-        length(min = 1, message = "Email is required"),
-        email(message = "Email is invalid")
-    )]
-    pub email: String,
+#[derive(Serialize, Deserialize, Debug)]
+pub struct FilterJobDto {
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    pub required_skills: Vec<String>,
+    pub preferred_skills: Vec<String>,
+    pub seniority: Option<String>,
+    pub min_years_experience: Option<i32>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Serialize, Deserialize, Validate, Debug, Clone)]
-pub struct ResetPasswordRequestDto {
-    #[validate(length(min = 1, message = "Token is required"))]
-    pub token: String,
+impl FilterJobDto {
+    pub fn filter_job(job: &JobDescription) -> Self {
+        FilterJobDto {
+            id: job.id.to_string(),
+            title: job.title.to_owned(),
+            description: job.description.to_owned(),
+            required_skills: job.required_skills.clone(),
+            preferred_skills: job.preferred_skills.clone(),
+            seniority: job.seniority.map(|seniority| seniority.to_str().to_string()),
+            min_years_experience: job.min_years_experience,
+            created_at: job.created_at,
+            updated_at: job.updated_at,
+        }
+    }
 
-    #[validate(length(min = 6, message = "Password must be at least 6 characters"))]
-    pub new_password: String,
+    pub fn filter_jobs(jobs: &[JobDescription]) -> Vec<Self> {
+        jobs.iter().map(FilterJobDto::filter_job).collect()
+    }
+}
 
-    #[validate(
-        length(min = 6, message = "Password confirm is required"),
-        must_match(other = "new_password", message = "Passwords do not match")
-    )]
-    pub new_password_confirm: String,
+#[derive(Serialize, Deserialize, Debug)]
+pub struct JobData {
+    pub job: FilterJobDto,
 }
 
-#[derive(Serialize, Deserialize, Validate, Debug, Clone)]
-pub struct ResumeUploadDto {
-    #[validate(length(min = 1, message = "File path is required"))]
-    pub file_path: String,
-    pub analysis_result: Option<serde_json::Value>, 
-}
\ No newline at end of file
+#[derive(Serialize, Deserialize, Debug)]
+pub struct JobResponseDto {
+    pub status: String,
+    pub data: JobData,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct JobListResponseDto {
+    pub status: String,
+    pub jobs: Vec<FilterJobDto>,
+    pub results: i64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct MatchData {
+    pub resume_id: String,
+    pub job_id: String,
+    #[serde(rename = "match")]
+    pub report: MatchReport,
+}
+
+#[derive(Serialize, Debug)]
+pub struct MatchResponseDto {
+    pub status: String,
+    pub data: MatchData,
+}
+
+#[derive(Serialize, Deserialize, Validate, Debug)]
+pub struct JobRankingQueryDto {
+    #[validate(range(min = 1))]
+    pub page: Option<usize>,
+    #[validate(range(min = 1, max = 50))]
+    pub limit: Option<usize>,
+    #[validate(range(min = 0.0, max = 100.0))]
+    pub min_score: Option<f64>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct RankedResumeDto {
+    pub rank: usize,
+    pub resume: FilterResumeDto,
+    pub score: f64,
+    #[serde(rename = "match")]
+    pub report: Option<MatchReport>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct JobRankingResponseDto {
+    pub status: String,
+    pub job: FilterJobDto,
+    pub resumes: Vec<RankedResumeDto>,
+    pub results: i64,
+}
+
+#[derive(Serialize, Deserialize, Validate, Debug)]
+pub struct SkillAutocompleteQueryDto {
+    #[validate(length(min = 1, max = 100, message = "Query must be between 1 and 100 characters"))]
+    pub q: String,
+    #[validate(range(min = 1, max = 50))]
+    pub limit: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize, Validate, Debug)]
+pub struct SkillListQueryDto {
+    pub category: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct FilterSkillDto {
+    pub id: String,
+    pub name: String,
+    pub category: String,
+    pub parent: Option<String>,
+    pub aliases: Vec<String>,
+}
+
+impl FilterSkillDto {
+    pub fn filter_skill(skill: &CanonicalSkill) -> Self {
+        FilterSkillDto {
+            id: skill.id.to_string(),
+            name: skill.name.to_owned(),
+            category: skill.category.to_owned(),
+            parent: skill.parent.clone(),
+            aliases: skill.aliases.clone(),
+        }
+    }
+
+    pub fn filter_skills(skills: &[&CanonicalSkill]) -> Vec<Self> {
+        skills.iter().map(|skill| FilterSkillDto::filter_skill(skill)).collect()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SkillListResponseDto {
+    pub status: String,
+    pub skills: Vec<FilterSkillDto>,
+    pub results: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SkillData {
+    pub skill: FilterSkillDto,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SkillResponseDto {
+    pub status: String,
+    pub data: SkillData,
+}
+
+#[derive(Serialize, Deserialize, Validate, Debug)]
+pub struct CreateSkillDto {
+    #[validate(length(min = 1, max = 100, message = "Name must be between 1 and 100 characters"))]
+    pub name: String,
+    #[validate(length(min = 1, max = 50, message = "Category must be between 1 and 50 characters"))]
+    pub category: String,
+    pub parent: Option<String>,
+    #[serde(default)]
+    #[validate(length(max = 50))]
+    pub aliases: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Validate, Debug)]
+pub struct CreateSkillAliasDto {
+    #[validate(length(min = 1, max = 100, message = "Alias must be between 1 and 100 characters"))]
+    pub alias: String,
+    #[validate(length(min = 1, max = 100, message = "Skill must be between 1 and 100 characters"))]
+    pub skill: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ResumeComparisonData {
+    pub base: FilterResumeDto,
+    pub target: FilterResumeDto,
+    pub diff: ResumeDiff,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ResumeComparisonResponseDto {
+    pub status: String,
+    pub data: ResumeComparisonData,
+}
+
+#[derive(Serialize, Deserialize, Validate, Debug)]
+pub struct RetentionPolicyDto {
+    /// Leave out to keep resumes indefinitely
+    #[validate(range(min = 1, max = 1200, message = "Retention must be between 1 and 1200 months"))]
+    pub retention_months: Option<i32>,
+    pub action: RetentionAction,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct FilterRetentionPolicyDto {
+    pub id: String,
+    pub user_id: Option<String>,
+    pub user_email: Option<String>,
+    pub retention_months: Option<i32>,
+    pub action: String,
+    pub updated_by: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl FilterRetentionPolicyDto {
+    pub fn filter_policy(policy: &RetentionPolicy) -> Self {
+        FilterRetentionPolicyDto {
+            id: policy.id.to_string(),
+            user_id: policy.user_id.map(|user_id| user_id.to_string()),
+            user_email: policy.user_email.clone(),
+            retention_months: policy.retention_months,
+            action: policy.action.to_str().to_string(),
+            updated_by: policy.updated_by.map(|updated_by| updated_by.to_string()),
+            updated_at: policy.updated_at.unwrap(),
+        }
+    }
+
+    pub fn filter_policies(policies: &[RetentionPolicy]) -> Vec<Self> {
+        policies.iter().map(FilterRetentionPolicyDto::filter_policy).collect()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RetentionPolicyData {
+    pub policy: FilterRetentionPolicyDto,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RetentionPolicyResponseDto {
+    pub status: String,
+    pub data: RetentionPolicyData,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RetentionPolicyListResponseDto {
+    pub status: String,
+    pub policies: Vec<FilterRetentionPolicyDto>,
+    pub results: i64,
+}
+
+#[derive(Serialize, Deserialize, Validate, Debug)]
+pub struct RetentionReportQueryDto {
+    #[validate(range(min = 1, max = 500))]
+    pub limit: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct FilterRetentionCandidateDto {
+    pub resume_id: String,
+    pub user_id: String,
+    pub document_id: String,
+    pub file_path: Option<String>,
+    pub uploaded_at: DateTime<Utc>,
+    pub in_trash: bool,
+    pub action: String,
+    pub retention_months: i32,
+    pub user_policy: bool,
+}
+
+impl FilterRetentionCandidateDto {
+    pub fn filter_candidate(candidate: &RetentionCandidate) -> Self {
+        FilterRetentionCandidateDto {
+            resume_id: candidate.resume_id.to_string(),
+            user_id: candidate.user_id.to_string(),
+            document_id: candidate.document_id.to_string(),
+            file_path: candidate.file_path.clone(),
+            uploaded_at: candidate.uploaded_at.unwrap(),
+            in_trash: candidate.deleted_at.is_some(),
+            action: candidate.action.to_str().to_string(),
+            retention_months: candidate.retention_months,
+            user_policy: candidate.user_policy,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RetentionSummaryDto {
+    pub action: String,
+    pub resumes: i64,
+    pub users: i64,
+}
+
+impl RetentionSummaryDto {
+    pub fn filter_summary(summary: &[RetentionSummary]) -> Vec<Self> {
+        summary
+            .iter()
+            .map(|row| RetentionSummaryDto {
+                action: row.action.to_str().to_string(),
+                resumes: row.resumes,
+                users: row.users,
+            })
+            .collect()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RetentionReportData {
+    /// Everything the next run would do, by policy action
+    pub summary: Vec<RetentionSummaryDto>,
+    /// The oldest affected resumes, up to `limit`
+    pub resumes: Vec<FilterRetentionCandidateDto>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RetentionReportResponseDto {
+    pub status: String,
+    pub dry_run: bool,
+    pub data: RetentionReportData,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RetentionRunData {
+    pub handled: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RetentionRunResponseDto {
+    pub status: String,
+    pub data: RetentionRunData,
+}
+
+#[derive(Serialize, Deserialize, Validate, Debug)]
+pub struct QuotaDto {
+    /// Leave a limit out for no limit
+    #[validate(range(min = 0))]
+    pub max_resumes: Option<i32>,
+    #[validate(range(min = 0))]
+    pub max_storage_bytes: Option<i64>,
+    #[validate(range(min = 0))]
+    pub max_analyses_per_day: Option<i32>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct FilterQuotaDto {
+    pub id: String,
+    pub user_id: Option<String>,
+    pub user_email: Option<String>,
+    pub role: Option<String>,
+    pub max_resumes: Option<i32>,
+    pub max_storage_bytes: Option<i64>,
+    pub max_analyses_per_day: Option<i32>,
+    pub updated_by: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl FilterQuotaDto {
+    pub fn filter_quota(quota: &Quota) -> Self {
+        FilterQuotaDto {
+            id: quota.id.to_string(),
+            user_id: quota.user_id.map(|user_id| user_id.to_string()),
+            user_email: quota.user_email.clone(),
+            role: quota.role.map(|role| role.to_str().to_string()),
+            max_resumes: quota.max_resumes,
+            max_storage_bytes: quota.max_storage_bytes,
+            max_analyses_per_day: quota.max_analyses_per_day,
+            updated_by: quota.updated_by.map(|updated_by| updated_by.to_string()),
+            updated_at: quota.updated_at.unwrap(),
+        }
+    }
+
+    pub fn filter_quotas(quotas: &[Quota]) -> Vec<Self> {
+        quotas.iter().map(FilterQuotaDto::filter_quota).collect()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct QuotaData {
+    pub quota: FilterQuotaDto,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct QuotaResponseDto {
+    pub status: String,
+    pub data: QuotaData,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct QuotaListResponseDto {
+    pub status: String,
+    pub quotas: Vec<FilterQuotaDto>,
+    pub results: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UsageLimitDto {
+    pub used: i64,
+    /// No limit when missing
+    pub limit: Option<i64>,
+    pub remaining: Option<i64>,
+}
+
+impl UsageLimitDto {
+    fn new(used: i64, limit: Option<i64>) -> Self {
+        UsageLimitDto {
+            used,
+            limit,
+            remaining: limit.map(|limit| (limit - used).max(0)),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct FilterUsageDto {
+    /// `user` for a quota of the user's own, `role` for their role's, `none` when unlimited
+    pub quota: String,
+    pub resumes: UsageLimitDto,
+    pub storage_bytes: UsageLimitDto,
+    pub analyses_today: UsageLimitDto,
+    pub analyses_reset_at: DateTime<Utc>,
+}
+
+impl FilterUsageDto {
+    pub fn filter_usage(usage: &QuotaUsage, analyses_reset_at: DateTime<Utc>) -> Self {
+        let quota = match (usage.quota_id, usage.user_quota) {
+            (None, _) => "none",
+            (Some(_), true) => "user",
+            (Some(_), false) => "role",
+        };
+
+        FilterUsageDto {
+            quota: quota.to_string(),
+            resumes: UsageLimitDto::new(usage.resumes, usage.max_resumes.map(i64::from)),
+            storage_bytes: UsageLimitDto::new(usage.storage_bytes, usage.max_storage_bytes),
+            analyses_today: UsageLimitDto::new(usage.analyses_today as i64, usage.max_analyses_per_day.map(i64::from)),
+            analyses_reset_at,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UsageData {
+    pub usage: FilterUsageDto,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UsageResponseDto {
+    pub status: String,
+    pub data: UsageData,
+}
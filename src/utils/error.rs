@@ -29,11 +29,23 @@ pub enum ErrorMessage {
     PermissionDenied,
     UserNotAuthorized,
     ResumeNotFound,
+    ResumeNotAnalyzed,
+    ResumeAnonymized,
+    DocumentNotFound,
+    BatchNotFound,
+    JobNotFound,
+    SkillNotFound,
+    TagNotFound,
+    NoteNotFound,
+    StageNotFound,
+    ShareLinkNotFound,
+    RetentionPolicyNotFound,
+    QuotaNotFound,
 }
 
-impl ToString for ErrorMessage {
-    fn to_string(&self) -> String {
-        self.to_str().to_owned()
+impl fmt::Display for ErrorMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_str())
     }
 }
 
@@ -54,6 +66,18 @@ impl ErrorMessage {
             ErrorMessage::PermissionDenied => "Permission denied".to_string(),
             ErrorMessage::UserNotAuthorized => "User not authorized".to_string(),
             ErrorMessage::ResumeNotFound => "Resume not found".to_string(),
+            ErrorMessage::ResumeNotAnalyzed => "Resume has not been analyzed yet".to_string(),
+            ErrorMessage::ResumeAnonymized => "Resume has been anonymized and can no longer be analyzed".to_string(),
+            ErrorMessage::DocumentNotFound => "Resume document not found".to_string(),
+            ErrorMessage::BatchNotFound => "Upload batch not found".to_string(),
+            ErrorMessage::JobNotFound => "Job description not found".to_string(),
+            ErrorMessage::SkillNotFound => "Skill not found".to_string(),
+            ErrorMessage::TagNotFound => "Tag not found".to_string(),
+            ErrorMessage::NoteNotFound => "Note not found".to_string(),
+            ErrorMessage::StageNotFound => "Pipeline stage not found".to_string(),
+            ErrorMessage::ShareLinkNotFound => "Share link not found".to_string(),
+            ErrorMessage::RetentionPolicyNotFound => "Retention policy not found".to_string(),
+            ErrorMessage::QuotaNotFound => "Quota not found".to_string(),
         }
     }
 }
@@ -100,6 +124,13 @@ impl HttpError {
         }
     }
 
+    pub fn not_found(message: impl Into<String>) -> Self {
+        HttpError {
+            message: message.into(),
+            status: StatusCode::NOT_FOUND,
+        }
+    }
+
     pub fn into_http_response(self) -> Response {
         let json_response = Json(ErrorResponse {
             status: "fail".to_string(),
@@ -41,7 +41,7 @@ pub fn compare(password: &str, password_hash: &str) -> Result<bool, ErrorMessage
 
     let password_matched = argon2
         .verify_password(password.as_bytes(), &parsed_hash)
-        .map_or(false, |_| true);
+        .is_ok();
 
     Ok(password_matched)
 }
\ No newline at end of file